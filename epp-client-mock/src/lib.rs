@@ -0,0 +1,51 @@
+//! # Mock EPP server for testing the epp-client Library
+//!
+//! ## Description
+//!
+//! epp-client-mock runs a local EPP server to exercise `EppClient` without a connection to a
+//! real registry. It frames messages as described in RFC 5734, optionally over TLS with a
+//! generated self-signed certificate, sends a configurable greeting, checks logins and answers
+//! commands from scripted handlers or from the response fixtures of the epp-client tests.
+//! The `Registry` handler simulates a registry keeping domains, contacts and hosts in memory.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use epp_client::EppClient;
+//! use epp_client::epp::{EppDomainCheck, EppDomainCheckResponse};
+//! use epp_client_mock::MockServer;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let server = MockServer::new()
+//!         .credentials("username", "password")
+//!         .fixtures()
+//!         .tls("localhost")
+//!         .bind("127.0.0.1:0")
+//!         .await
+//!         .unwrap();
+//!
+//!     let registry_creds = server.connection("username", "password");
+//!     let mut client = EppClient::connect("mock", &registry_creds).await.unwrap();
+//!
+//!     let domain_check = EppDomainCheck::new(vec!["eppdev.com"], "cltrid:1");
+//!     let response = client.transact::<_, EppDomainCheckResponse>(&domain_check).await.unwrap();
+//!     println!("{:?}", response);
+//!
+//!     client.close().await.unwrap();
+//! }
+//! ```
+
+#[macro_use]
+extern crate log;
+
+pub mod registry;
+pub mod request;
+pub mod response;
+pub mod server;
+pub mod tls;
+pub use registry::Registry;
+pub use server::{MockHandler, MockServer};
+
+#[cfg(test)]
+pub mod tests;
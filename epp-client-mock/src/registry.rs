@@ -0,0 +1,201 @@
+//! A simulated registry keeping domains, contacts and hosts in memory
+
+mod contact;
+mod domain;
+mod host;
+mod message;
+
+use chrono::{DateTime, Months, Utc};
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+use std::sync::{Arc, Mutex};
+
+use epp_client::epp::object::data::{AuthInfo, Period, Status};
+use epp_client::epp::object::{ElementName, EppObject, StringValue};
+use epp_client::epp::request::Command;
+use epp_client::epp::xml::EppXml;
+
+use crate::request::MockRequest;
+use crate::response::result_response;
+use crate::server::MockHandler;
+
+/// The response to a command, or the code of the error result to respond with
+type Outcome = Result<String, u16>;
+
+/// A registry that keeps the domains, contacts and hosts created by its clients in memory
+/// and answers check, create, info, update, delete, renew, transfer and poll commands for
+/// them with the result codes of a real registry, e.g. 2302 for an object that exists, 2303
+/// for one that does not, 2304 for a status that prohibits the command and 2201 for an
+/// object sponsored by another client.
+///
+/// Clones share the same registry, so one can be added to a server as a handler and kept to
+/// serve other servers. Commands with extensions are left to the handlers after it.
+#[derive(Clone, Default)]
+pub struct Registry {
+    state: Arc<Mutex<State>>,
+}
+
+/// The objects and messages of the registry
+#[derive(Default)]
+struct State {
+    domains: HashMap<String, domain::Domain>,
+    contacts: HashMap<String, contact::Contact>,
+    hosts: HashMap<String, host::Host>,
+    messages: Vec<message::Message>,
+    next_roid: u64,
+    next_message_id: u64,
+}
+
+impl Registry {
+    /// Creates an empty registry
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+}
+
+impl MockHandler for Registry {
+    fn handle(&self, request: &MockRequest) -> Option<String> {
+        if !request.extensions.is_empty() {
+            return None;
+        }
+
+        let client_id = request.client_id.as_deref()?;
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(e) => e.into_inner(),
+        };
+
+        let outcome = match request.command.as_str() {
+            "domain:check" => state.domain_check(request),
+            "domain:create" => state.domain_create(request, client_id),
+            "domain:info" => state.domain_info(request, client_id),
+            "domain:update" => state.domain_update(request, client_id),
+            "domain:delete" => state.domain_delete(request, client_id),
+            "domain:renew" => state.domain_renew(request, client_id),
+            "domain:transfer" => state.domain_transfer(request, client_id),
+            "contact:check" => state.contact_check(request),
+            "contact:create" => state.contact_create(request, client_id),
+            "contact:info" => state.contact_info(request, client_id),
+            "contact:update" => state.contact_update(request, client_id),
+            "contact:delete" => state.contact_delete(request, client_id),
+            "host:check" => state.host_check(request),
+            "host:create" => state.host_create(request, client_id),
+            "host:info" => state.host_info(request),
+            "host:update" => state.host_update(request, client_id),
+            "host:delete" => state.host_delete(request, client_id),
+            "poll" => state.poll(request, client_id),
+            _ => return None,
+        };
+
+        Some(outcome.unwrap_or_else(|code| result_response(code, request.client_tr_id.as_deref())))
+    }
+}
+
+impl State {
+    /// Returns a new repository object ID with the given prefix, e.g. `D1-MOCK` for domains
+    fn roid(&mut self, prefix: &str) -> String {
+        self.next_roid += 1;
+        format!("{}{}-MOCK", prefix, self.next_roid)
+    }
+}
+
+/// Parses the command of a request with its epp-client request type
+fn parse<T: ElementName + Serialize + DeserializeOwned + Debug>(
+    request: &MockRequest,
+) -> Result<T, u16> {
+    match EppObject::<Command<T>>::deserialize(&request.xml) {
+        Ok(object) => Ok(object.data.command),
+        Err(e) => {
+            warn!("Mock registry failed to parse {}: {}", request.command, e);
+            Err(2001)
+        }
+    }
+}
+
+/// Returns the key of a domain or host name, which are case insensitive
+fn key(name: &StringValue) -> String {
+    name.to_string().to_lowercase()
+}
+
+/// Returns the time a period after the given time, or error code 2004 for an invalid period
+fn add_period(time: &DateTime<Utc>, period: &Period) -> Result<DateTime<Utc>, u16> {
+    let months = match period.unit.as_str() {
+        "y" => u32::from(period.length) * 12,
+        "m" => u32::from(period.length),
+        _ => return Err(2004),
+    };
+
+    match months {
+        1..=1200 => time.checked_add_months(Months::new(months)).ok_or(2004),
+        _ => Err(2004),
+    }
+}
+
+/// Returns the password of an authInfo as stored in the registry, `None` for `<null/>` or an
+/// empty password
+fn password(auth_info: &AuthInfo) -> Option<String> {
+    auth_info
+        .password()
+        .map(|password| password.to_string())
+        .filter(|password| !password.is_empty())
+}
+
+/// Returns whether the authInfo given by a client matches the stored password of an object.
+/// A missing, null or empty password never matches
+fn authorizes(auth_info: Option<&AuthInfo>, auth_password: &Option<String>) -> bool {
+    match (auth_info.and_then(password), auth_password) {
+        (Some(given), Some(stored)) => given == *stored,
+        _ => false,
+    }
+}
+
+/// Returns whether a client or server status of an object prohibits an operation, e.g.
+/// `Delete` for `clientDeleteProhibited`
+fn prohibits(statuses: &[String], operation: &str) -> bool {
+    statuses.iter().any(|status| {
+        *status == format!("client{}Prohibited", operation)
+            || *status == format!("server{}Prohibited", operation)
+    })
+}
+
+/// Returns the statuses of an object as sent in responses, `ok` if it has none
+fn status_list<T: for<'a> From<&'a str>>(statuses: &[String]) -> Vec<Status<T>> {
+    let statuses = match statuses.is_empty() {
+        true => vec!["ok".to_string()],
+        false => statuses.to_vec(),
+    };
+
+    statuses
+        .into_iter()
+        .map(|status| Status::new(T::from(status.as_str())))
+        .collect()
+}
+
+/// Adds statuses to and removes statuses from the statuses of an object. Clients may only
+/// set client statuses, giving error code 2306 otherwise
+fn update_statuses<T: Display>(
+    statuses: &mut Vec<String>,
+    add: &[Status<T>],
+    remove: &[Status<T>],
+) -> Result<(), u16> {
+    let add: Vec<String> = add.iter().map(|s| s.status.to_string()).collect();
+    let remove: Vec<String> = remove.iter().map(|s| s.status.to_string()).collect();
+
+    if add
+        .iter()
+        .chain(&remove)
+        .any(|status| !status.starts_with("client"))
+    {
+        return Err(2306);
+    }
+
+    statuses.retain(|status| !remove.contains(status));
+    for status in add {
+        if !statuses.contains(&status) {
+            statuses.push(status);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,254 @@
+//! Contact commands of the simulated registry
+
+use chrono::{DateTime, Utc};
+
+use epp_client::epp::object::data::{AuthInfo, ContactStatus, Disclose, Phone, PostalInfo};
+use epp_client::epp::object::StringValueTrait;
+use epp_client::epp::request::contact::{check, create, delete, info, update};
+use epp_client::epp::response::contact as response;
+use epp_client::epp::xml::{EPP_CONTACT_SCHEMA_LOCATION, EPP_CONTACT_XMLNS};
+
+use super::{authorizes, parse, password, prohibits, status_list, update_statuses, Outcome, State};
+use crate::request::MockRequest;
+use crate::response::{command_response, result_response};
+
+/// A contact in the registry
+pub(super) struct Contact {
+    roid: String,
+    postal_info: Vec<PostalInfo>,
+    voice: Phone,
+    fax: Option<Phone>,
+    email: String,
+    auth_password: Option<String>,
+    disclose: Option<Disclose>,
+    statuses: Vec<String>,
+    client_id: String,
+    creator_id: String,
+    created_at: DateTime<Utc>,
+    updater_id: Option<String>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl State {
+    /// Returns the contact sponsored by the client, giving error code 2303 if it does not
+    /// exist and 2201 if another client sponsors it
+    fn sponsored_contact(&self, id: &str, client_id: &str) -> Result<&Contact, u16> {
+        match self.contacts.get(id) {
+            Some(contact) if contact.client_id == client_id => Ok(contact),
+            Some(_) => Err(2201),
+            None => Err(2303),
+        }
+    }
+
+    /// Returns whether a domain refers to the contact
+    fn contact_linked(&self, id: &str) -> bool {
+        self.domains
+            .values()
+            .any(|domain| domain.contact_ids().any(|contact_id| contact_id == id))
+    }
+
+    pub(super) fn contact_check(&mut self, request: &MockRequest) -> Outcome {
+        let check: check::ContactCheck = parse(request)?;
+
+        let contact_list = check
+            .list
+            .contact_ids
+            .iter()
+            .map(|id| {
+                let exists = self.contacts.contains_key(&id.to_string());
+                response::check::ContactCheckDataItem {
+                    contact: response::check::ContactCheck {
+                        id: id.clone(),
+                        available: u16::from(!exists),
+                    },
+                    reason: match exists {
+                        true => Some("In use".to_string_value()),
+                        false => None,
+                    },
+                }
+            })
+            .collect();
+
+        let res_data = response::check::ContactCheckResult {
+            check_data: response::check::ContactCheckData {
+                xmlns: EPP_CONTACT_XMLNS.to_string(),
+                schema_location: EPP_CONTACT_SCHEMA_LOCATION.to_string(),
+                contact_list,
+            },
+        };
+
+        Ok(command_response(
+            1000,
+            Some(res_data),
+            None,
+            request.client_tr_id.as_deref(),
+        ))
+    }
+
+    pub(super) fn contact_create(&mut self, request: &MockRequest, client_id: &str) -> Outcome {
+        let create: create::ContactCreate = parse(request)?;
+        let data = create.contact;
+
+        let id = data.id.to_string();
+        if self.contacts.contains_key(&id) {
+            return Err(2302);
+        }
+
+        let now = Utc::now();
+        let contact = Contact {
+            roid: self.roid("C"),
+            postal_info: data.postal_info,
+            voice: data.voice,
+            fax: data.fax,
+            email: data.email.to_string(),
+            auth_password: password(&data.auth_info),
+            disclose: data.disclose,
+            statuses: Vec::new(),
+            client_id: client_id.to_string(),
+            creator_id: client_id.to_string(),
+            created_at: now,
+            updater_id: None,
+            updated_at: None,
+        };
+        self.contacts.insert(id.clone(), contact);
+
+        let res_data = response::create::ContactCreateResult {
+            create_data: response::create::ContactCreateData {
+                xmlns: EPP_CONTACT_XMLNS.to_string(),
+                schema_location: EPP_CONTACT_SCHEMA_LOCATION.to_string(),
+                id: id.to_string_value(),
+                created_at: now,
+            },
+        };
+
+        Ok(command_response(
+            1000,
+            Some(res_data),
+            None,
+            request.client_tr_id.as_deref(),
+        ))
+    }
+
+    pub(super) fn contact_info(&mut self, request: &MockRequest, client_id: &str) -> Outcome {
+        let info: info::ContactInfo = parse(request)?;
+
+        let id = info.info.id.to_string();
+        let contact = self.contacts.get(&id).ok_or(2303_u16)?;
+
+        // Other clients need the authInfo of the contact
+        if contact.client_id != client_id
+            && !authorizes(Some(&info.info.auth_info), &contact.auth_password)
+        {
+            return Err(2201);
+        }
+
+        let mut statuses = contact.statuses.clone();
+        if self.contact_linked(&id) {
+            statuses.push("linked".to_string());
+        }
+
+        let res_data = response::info::ContactInfoResult {
+            info_data: response::info::ContactInfoData {
+                xmlns: EPP_CONTACT_XMLNS.to_string(),
+                schema_location: EPP_CONTACT_SCHEMA_LOCATION.to_string(),
+                id: id.to_string_value(),
+                roid: contact.roid.to_string_value(),
+                statuses: status_list(&statuses),
+                postal_info: contact.postal_info.clone(),
+                voice: contact.voice.clone(),
+                fax: contact.fax.clone(),
+                email: contact.email.to_string_value(),
+                client_id: contact.client_id.to_string_value(),
+                creator_id: contact.creator_id.to_string_value(),
+                created_at: contact.created_at,
+                updater_id: contact.updater_id.as_ref().map(|id| id.to_string_value()),
+                updated_at: contact.updated_at,
+                transferred_at: None,
+                auth_info: contact.auth_password.as_deref().map(AuthInfo::new),
+                disclose: contact.disclose.clone(),
+            },
+        };
+
+        Ok(command_response(
+            1000,
+            Some(res_data),
+            None,
+            request.client_tr_id.as_deref(),
+        ))
+    }
+
+    pub(super) fn contact_update(&mut self, request: &MockRequest, client_id: &str) -> Outcome {
+        let update: update::ContactUpdate = parse(request)?;
+        let data = update.contact;
+
+        let id = data.id.to_string();
+        let contact = self.sponsored_contact(&id, client_id)?;
+
+        let add_statuses = data.add_statuses.map(|s| s.status).unwrap_or_default();
+        let remove_statuses = data.remove_statuses.map(|s| s.status).unwrap_or_default();
+
+        // Updates are prohibited, except for removing clientUpdateProhibited itself
+        let removes_prohibition = remove_statuses
+            .iter()
+            .any(|s| s.status == ContactStatus::ClientUpdateProhibited);
+        if contact
+            .statuses
+            .iter()
+            .any(|s| s == "serverUpdateProhibited")
+            || (prohibits(&contact.statuses, "Update") && !removes_prohibition)
+        {
+            return Err(2304);
+        }
+
+        let contact = self.contacts.get_mut(&id).ok_or(2303_u16)?;
+
+        update_statuses(&mut contact.statuses, &add_statuses, &remove_statuses)?;
+
+        if let Some(change_info) = data.change_info {
+            for postal_info in change_info.postal_info {
+                contact
+                    .postal_info
+                    .retain(|p| p.info_type != postal_info.info_type);
+                contact.postal_info.push(postal_info);
+            }
+            if let Some(voice) = change_info.voice {
+                contact.voice = voice;
+            }
+            if let Some(fax) = change_info.fax {
+                contact.fax = Some(fax);
+            }
+            if let Some(email) = change_info.email {
+                contact.email = email.to_string();
+            }
+            if let Some(auth_info) = change_info.auth_info {
+                contact.auth_password = password(&auth_info);
+            }
+            if let Some(disclose) = change_info.disclose {
+                contact.disclose = Some(disclose);
+            }
+        }
+
+        contact.updater_id = Some(client_id.to_string());
+        contact.updated_at = Some(Utc::now());
+
+        Ok(result_response(1000, request.client_tr_id.as_deref()))
+    }
+
+    pub(super) fn contact_delete(&mut self, request: &MockRequest, client_id: &str) -> Outcome {
+        let delete: delete::ContactDelete = parse(request)?;
+
+        let id = delete.contact.id.to_string();
+        let contact = self.sponsored_contact(&id, client_id)?;
+
+        if prohibits(&contact.statuses, "Delete") {
+            return Err(2304);
+        }
+        if self.contact_linked(&id) {
+            return Err(2305);
+        }
+
+        self.contacts.remove(&id);
+
+        Ok(result_response(1000, request.client_tr_id.as_deref()))
+    }
+}
@@ -0,0 +1,600 @@
+//! Domain commands of the simulated registry
+
+use chrono::{DateTime, Duration, Utc};
+
+use epp_client::epp::object::data::{AuthInfo, DomainContact, DomainStatus, HostObjList, Period};
+use epp_client::epp::object::{ElementName, StringValueTrait};
+use epp_client::epp::request::domain::{check, create, delete, info, renew, transfer, update};
+use epp_client::epp::response::domain as response;
+use epp_client::epp::xml::{EPP_DOMAIN_SCHEMA_LOCATION, EPP_DOMAIN_XMLNS};
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+
+use super::{
+    add_period, authorizes, key, parse, password, prohibits, status_list, update_statuses, Outcome,
+    State,
+};
+use crate::request::MockRequest;
+use crate::response::{command_response, result_response};
+
+/// The days a transfer may be acted on by the sponsor of the domain
+const TRANSFER_ACK_DAYS: i64 = 5;
+
+/// A domain in the registry
+pub(super) struct Domain {
+    roid: String,
+    registrant: Option<String>,
+    contacts: Vec<(String, String)>,
+    pub(super) ns: Vec<String>,
+    statuses: Vec<String>,
+    pub(super) client_id: String,
+    creator_id: String,
+    created_at: DateTime<Utc>,
+    updater_id: Option<String>,
+    updated_at: Option<DateTime<Utc>>,
+    expiring_at: DateTime<Utc>,
+    transferred_at: Option<DateTime<Utc>>,
+    auth_password: Option<String>,
+    transfer: Option<Transfer>,
+}
+
+/// The latest transfer of a domain
+#[derive(Clone)]
+pub(super) struct Transfer {
+    pub(super) status: String,
+    pub(super) requester_id: String,
+    pub(super) requested_at: DateTime<Utc>,
+    pub(super) ack_id: String,
+    pub(super) ack_by: DateTime<Utc>,
+    pub(super) expiring_at: DateTime<Utc>,
+}
+
+impl Domain {
+    /// Returns whether a transfer of the domain is pending
+    fn transfer_pending(&self) -> bool {
+        matches!(&self.transfer, Some(transfer) if transfer.status == "pending")
+    }
+
+    /// Returns the statuses of the domain, including `pendingTransfer`
+    fn all_statuses(&self) -> Vec<String> {
+        let mut statuses = self.statuses.clone();
+        if self.transfer_pending() {
+            statuses.push("pendingTransfer".to_string());
+        }
+        statuses
+    }
+
+    /// Returns the contact IDs the domain refers to
+    pub(super) fn contact_ids(&self) -> impl Iterator<Item = &String> {
+        self.registrant
+            .iter()
+            .chain(self.contacts.iter().map(|(_, id)| id))
+    }
+}
+
+impl Transfer {
+    /// Returns the transfer data of responses for a domain
+    fn data(&self, name: &str) -> response::transfer::DomainTransferData {
+        response::transfer::DomainTransferData {
+            xmlns: EPP_DOMAIN_XMLNS.to_string(),
+            schema_location: EPP_DOMAIN_SCHEMA_LOCATION.to_string(),
+            name: name.to_string_value(),
+            transfer_status: self.status.to_string_value(),
+            requester_id: self.requester_id.to_string_value(),
+            requested_at: self.requested_at,
+            ack_id: self.ack_id.to_string_value(),
+            ack_by: self.ack_by,
+            expiring_at: self.expiring_at,
+        }
+    }
+}
+
+/// Returns the nameservers of a hostObj list
+fn ns_names(ns: Option<HostObjList>) -> Vec<String> {
+    match ns {
+        Some(ns) => ns.hosts.iter().map(key).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Parses a command with hostObj nameservers, giving error code 2102 for hostAttr ones
+fn parse_host_obj<T>(request: &MockRequest) -> Result<T, u16>
+where
+    T: ElementName + Serialize + DeserializeOwned + Debug,
+{
+    match parse(request) {
+        Err(_) if !request.values("hostName").is_empty() => Err(2102),
+        result => result,
+    }
+}
+
+impl State {
+    /// Returns the domain sponsored by the client, giving error code 2303 if it does not
+    /// exist and 2201 if another client sponsors it
+    fn sponsored_domain(&self, name: &str, client_id: &str) -> Result<&Domain, u16> {
+        match self.domains.get(name) {
+            Some(domain) if domain.client_id == client_id => Ok(domain),
+            Some(_) => Err(2201),
+            None => Err(2303),
+        }
+    }
+
+    /// Gives error code 2303 unless all the contacts and hosts exist
+    fn check_references<'a>(
+        &self,
+        contacts: impl IntoIterator<Item = &'a String>,
+        hosts: &[String],
+    ) -> Result<(), u16> {
+        let contacts_exist = contacts
+            .into_iter()
+            .all(|id| self.contacts.contains_key(id));
+        let hosts_exist = hosts.iter().all(|name| self.hosts.contains_key(name));
+
+        match contacts_exist && hosts_exist {
+            true => Ok(()),
+            false => Err(2303),
+        }
+    }
+
+    /// Returns the names of the hosts subordinate to a domain
+    fn subordinate_hosts(&self, name: &str) -> Vec<String> {
+        let suffix = format!(".{}", name);
+
+        self.hosts
+            .keys()
+            .filter(|host| host.ends_with(&suffix))
+            .cloned()
+            .collect()
+    }
+
+    pub(super) fn domain_check(&mut self, request: &MockRequest) -> Outcome {
+        let check: check::DomainCheck = parse(request)?;
+
+        let domain_list = check
+            .list
+            .domains
+            .iter()
+            .map(|name| {
+                let exists = self.domains.contains_key(&key(name));
+                response::check::DomainCheckDataItem {
+                    domain: response::check::DomainCheck {
+                        name: name.clone(),
+                        available: u16::from(!exists),
+                    },
+                    reason: match exists {
+                        true => Some("In use".to_string_value()),
+                        false => None,
+                    },
+                }
+            })
+            .collect();
+
+        let res_data = response::check::DomainCheckResult {
+            check_data: response::check::DomainCheckData {
+                xmlns: EPP_DOMAIN_XMLNS.to_string(),
+                schema_location: EPP_DOMAIN_SCHEMA_LOCATION.to_string(),
+                domain_list,
+            },
+        };
+
+        Ok(command_response(
+            1000,
+            Some(res_data),
+            None,
+            request.client_tr_id.as_deref(),
+        ))
+    }
+
+    pub(super) fn domain_create(&mut self, request: &MockRequest, client_id: &str) -> Outcome {
+        let create: create::DomainCreate<HostObjList> = parse_host_obj(request)?;
+        let data = create.domain;
+
+        let name = key(&data.name);
+        if self.domains.contains_key(&name) {
+            return Err(2302);
+        }
+
+        let registrant = data.registrant.map(|registrant| registrant.to_string());
+        let contacts: Vec<(String, String)> = data
+            .contacts
+            .unwrap_or_default()
+            .into_iter()
+            .map(|contact| (contact.contact_type, contact.id))
+            .collect();
+        let ns = ns_names(data.ns);
+
+        self.check_references(
+            registrant.iter().chain(contacts.iter().map(|(_, id)| id)),
+            &ns,
+        )?;
+
+        let now = Utc::now();
+        let expiring_at = add_period(&now, &data.period)?;
+
+        let domain = Domain {
+            roid: self.roid("D"),
+            registrant,
+            contacts,
+            ns,
+            statuses: Vec::new(),
+            client_id: client_id.to_string(),
+            creator_id: client_id.to_string(),
+            created_at: now,
+            updater_id: None,
+            updated_at: None,
+            expiring_at,
+            transferred_at: None,
+            auth_password: password(&data.auth_info),
+            transfer: None,
+        };
+        self.domains.insert(name.clone(), domain);
+
+        let res_data = response::create::DomainCreateResult {
+            create_data: response::create::DomainCreateData {
+                xmlns: EPP_DOMAIN_XMLNS.to_string(),
+                schema_location: EPP_DOMAIN_SCHEMA_LOCATION.to_string(),
+                name: name.to_string_value(),
+                created_at: now,
+                expiring_at,
+            },
+        };
+
+        Ok(command_response(
+            1000,
+            Some(res_data),
+            None,
+            request.client_tr_id.as_deref(),
+        ))
+    }
+
+    pub(super) fn domain_info(&mut self, request: &MockRequest, client_id: &str) -> Outcome {
+        let info: info::DomainInfo = parse(request)?;
+
+        let name = info.info.domain.name.to_lowercase();
+        let domain = self.domains.get(&name).ok_or(2303_u16)?;
+
+        // Only the sponsor, or clients giving the authInfo, see the authInfo and subordinate
+        // hosts of a domain
+        let sponsor = domain.client_id == client_id
+            || authorizes(info.info.auth_info.as_ref(), &domain.auth_password);
+        let hosts = self.subordinate_hosts(&name);
+
+        let (delegated, subordinate) = match info.info.domain.hosts {
+            info::DomainInfoHosts::All => (true, true),
+            info::DomainInfoHosts::Del => (true, false),
+            info::DomainInfoHosts::Sub => (false, true),
+            info::DomainInfoHosts::None => (false, false),
+        };
+
+        let ns = match !delegated || domain.ns.is_empty() {
+            true => None,
+            false => Some(response::info::DomainNsList {
+                host_obj: Some(domain.ns.iter().map(|ns| ns.to_string_value()).collect()),
+                host_attr: None,
+            }),
+        };
+
+        let res_data = response::info::DomainInfoResult {
+            info_data: response::info::DomainInfoData {
+                xmlns: EPP_DOMAIN_XMLNS.to_string(),
+                schema_location: EPP_DOMAIN_SCHEMA_LOCATION.to_string(),
+                name: name.to_string_value(),
+                roid: domain.roid.to_string_value(),
+                statuses: status_list(&domain.all_statuses()),
+                registrant: domain
+                    .registrant
+                    .as_ref()
+                    .map(|registrant| registrant.to_string_value())
+                    .unwrap_or_default(),
+                contacts: domain
+                    .contacts
+                    .iter()
+                    .map(|(contact_type, id)| DomainContact {
+                        id: id.to_string(),
+                        contact_type: contact_type.to_string(),
+                    })
+                    .collect(),
+                ns,
+                hosts: match sponsor && subordinate && !hosts.is_empty() {
+                    true => Some(hosts.iter().map(|host| host.to_string_value()).collect()),
+                    false => None,
+                },
+                client_id: domain.client_id.to_string_value(),
+                creator_id: domain.creator_id.to_string_value(),
+                created_at: domain.created_at,
+                updater_id: domain
+                    .updater_id
+                    .as_ref()
+                    .unwrap_or(&domain.creator_id)
+                    .to_string_value(),
+                updated_at: domain.updated_at.unwrap_or(domain.created_at),
+                expiring_at: domain.expiring_at,
+                transferred_at: domain.transferred_at,
+                auth_info: match sponsor {
+                    true => domain.auth_password.as_deref().map(AuthInfo::new),
+                    false => None,
+                },
+            },
+        };
+
+        Ok(command_response(
+            1000,
+            Some(res_data),
+            None,
+            request.client_tr_id.as_deref(),
+        ))
+    }
+
+    pub(super) fn domain_update(&mut self, request: &MockRequest, client_id: &str) -> Outcome {
+        let update: update::DomainUpdate<HostObjList> = parse_host_obj(request)?;
+        let data = update.domain;
+
+        let name = key(&data.name);
+        let domain = self.sponsored_domain(&name, client_id)?;
+
+        let (add_ns, add_contacts, add_statuses) = match data.add {
+            Some(add) => (
+                ns_names(add.ns),
+                add.contacts.unwrap_or_default(),
+                add.statuses.unwrap_or_default(),
+            ),
+            None => Default::default(),
+        };
+        let (remove_ns, remove_contacts, remove_statuses) = match data.remove {
+            Some(remove) => (
+                ns_names(remove.ns),
+                remove.contacts.unwrap_or_default(),
+                remove.statuses.unwrap_or_default(),
+            ),
+            None => Default::default(),
+        };
+        let (registrant, auth_info) = match data.change_info {
+            Some(change_info) => (
+                change_info.registrant.map(|r| r.to_string()),
+                change_info.auth_info,
+            ),
+            None => (None, None),
+        };
+
+        // Updates are prohibited, except for removing clientUpdateProhibited itself
+        let removes_prohibition = remove_statuses
+            .iter()
+            .any(|s| s.status == DomainStatus::ClientUpdateProhibited);
+        if domain.transfer_pending()
+            || domain
+                .statuses
+                .iter()
+                .any(|s| s == "serverUpdateProhibited")
+            || (prohibits(&domain.statuses, "Update") && !removes_prohibition)
+        {
+            return Err(2304);
+        }
+
+        self.check_references(
+            registrant.iter().chain(add_contacts.iter().map(|c| &c.id)),
+            &add_ns,
+        )?;
+
+        let domain = self.domains.get_mut(&name).ok_or(2303_u16)?;
+
+        update_statuses(&mut domain.statuses, &add_statuses, &remove_statuses)?;
+
+        domain.ns.retain(|ns| !remove_ns.contains(ns));
+        for ns in add_ns {
+            if !domain.ns.contains(&ns) {
+                domain.ns.push(ns);
+            }
+        }
+
+        domain.contacts.retain(|(contact_type, id)| {
+            !remove_contacts
+                .iter()
+                .any(|c| c.contact_type == *contact_type && c.id == *id)
+        });
+        for contact in add_contacts {
+            domain.contacts.push((contact.contact_type, contact.id));
+        }
+
+        if let Some(registrant) = registrant {
+            domain.registrant = Some(registrant);
+        }
+        if let Some(auth_info) = auth_info {
+            domain.auth_password = password(&auth_info);
+        }
+
+        domain.updater_id = Some(client_id.to_string());
+        domain.updated_at = Some(Utc::now());
+
+        Ok(result_response(1000, request.client_tr_id.as_deref()))
+    }
+
+    pub(super) fn domain_delete(&mut self, request: &MockRequest, client_id: &str) -> Outcome {
+        let delete: delete::DomainDelete = parse(request)?;
+
+        let name = key(&delete.domain.name);
+        let domain = self.sponsored_domain(&name, client_id)?;
+
+        if domain.transfer_pending() || prohibits(&domain.statuses, "Delete") {
+            return Err(2304);
+        }
+        if !self.subordinate_hosts(&name).is_empty() {
+            return Err(2305);
+        }
+
+        self.domains.remove(&name);
+
+        Ok(result_response(1000, request.client_tr_id.as_deref()))
+    }
+
+    pub(super) fn domain_renew(&mut self, request: &MockRequest, client_id: &str) -> Outcome {
+        let renew: renew::DomainRenew = parse(request)?;
+        let data = renew.domain;
+
+        let name = key(&data.name);
+        let domain = self.sponsored_domain(&name, client_id)?;
+
+        if domain.transfer_pending() || prohibits(&domain.statuses, "Renew") {
+            return Err(2304);
+        }
+
+        // The current expiry date guards against renewing twice
+        let current_expiry_date = domain.expiring_at.format("%Y-%m-%d").to_string();
+        if data.current_expiry_date.to_string() != current_expiry_date {
+            return Err(2004);
+        }
+
+        let expiring_at = add_period(&domain.expiring_at, &data.period)?;
+
+        let domain = self.domains.get_mut(&name).ok_or(2303_u16)?;
+        domain.expiring_at = expiring_at;
+
+        let res_data = response::renew::DomainRenewResult {
+            renew_data: response::renew::DomainRenewData {
+                xmlns: EPP_DOMAIN_XMLNS.to_string(),
+                schema_location: EPP_DOMAIN_SCHEMA_LOCATION.to_string(),
+                name: name.to_string_value(),
+                expiring_at,
+            },
+        };
+
+        Ok(command_response(
+            1000,
+            Some(res_data),
+            None,
+            request.client_tr_id.as_deref(),
+        ))
+    }
+
+    pub(super) fn domain_transfer(&mut self, request: &MockRequest, client_id: &str) -> Outcome {
+        let transfer: transfer::DomainTransfer = parse(request)?;
+        let data = transfer.domain;
+
+        let name = key(&data.name);
+        let domain = self.domains.get(&name).ok_or(2303_u16)?;
+
+        let authorized = authorizes(data.auth_info.as_ref(), &domain.auth_password);
+
+        let (code, transfer) = match transfer.operation.as_str() {
+            "query" => {
+                let transfer = domain.transfer.clone().ok_or(2301_u16)?;
+                if client_id != domain.client_id
+                    && client_id != transfer.requester_id
+                    && !authorized
+                {
+                    return Err(2201);
+                }
+                (1000, transfer)
+            }
+            "request" => {
+                if domain.client_id == client_id {
+                    return Err(2106);
+                }
+                if domain.transfer_pending() {
+                    return Err(2300);
+                }
+                if prohibits(&domain.statuses, "Transfer") {
+                    return Err(2304);
+                }
+                if !authorized {
+                    return Err(2202);
+                }
+
+                let now = Utc::now();
+                let period = data.period.unwrap_or_else(|| Period::new(1));
+                let transfer = Transfer {
+                    status: "pending".to_string(),
+                    requester_id: client_id.to_string(),
+                    requested_at: now,
+                    ack_id: domain.client_id.clone(),
+                    ack_by: now + Duration::days(TRANSFER_ACK_DAYS),
+                    expiring_at: add_period(&domain.expiring_at, &period)?,
+                };
+
+                let sponsor = domain.client_id.clone();
+                self.queue_transfer_message(&sponsor, &name, &transfer, "Transfer requested");
+                (1001, transfer)
+            }
+            "approve" | "reject" | "cancel" => {
+                let transfer = match &domain.transfer {
+                    Some(transfer) if transfer.status == "pending" => transfer.clone(),
+                    _ => return Err(2301),
+                };
+                return self.act_on_transfer(request, &name, transfer, client_id);
+            }
+            _ => return Err(2005),
+        };
+
+        let domain = self.domains.get_mut(&name).ok_or(2303_u16)?;
+        domain.transfer = Some(transfer.clone());
+
+        let res_data = response::transfer::DomainTransferResult {
+            transfer_data: transfer.data(&name),
+        };
+
+        Ok(command_response(
+            code,
+            Some(res_data),
+            None,
+            request.client_tr_id.as_deref(),
+        ))
+    }
+
+    /// Approves, rejects or cancels a pending transfer of a domain. The sponsor approves or
+    /// rejects it and the requester cancels it
+    fn act_on_transfer(
+        &mut self,
+        request: &MockRequest,
+        name: &str,
+        mut transfer: Transfer,
+        client_id: &str,
+    ) -> Outcome {
+        let (acting_id, status, notified_id, message) = match request.op.as_deref() {
+            Some("approve") => (
+                &transfer.ack_id,
+                "clientApproved",
+                &transfer.requester_id,
+                "Transfer approved",
+            ),
+            Some("reject") => (
+                &transfer.ack_id,
+                "clientRejected",
+                &transfer.requester_id,
+                "Transfer rejected",
+            ),
+            _ => (
+                &transfer.requester_id,
+                "clientCancelled",
+                &transfer.ack_id,
+                "Transfer cancelled",
+            ),
+        };
+
+        if client_id != acting_id {
+            return Err(2201);
+        }
+
+        let notified_id = notified_id.clone();
+        transfer.status = status.to_string();
+
+        if status == "clientApproved" {
+            // Subordinate hosts move to the new sponsor with their domain
+            for host in self.subordinate_hosts(name) {
+                if let Some(host) = self.hosts.get_mut(&host) {
+                    host.client_id = transfer.requester_id.clone();
+                }
+            }
+
+            let domain = self.domains.get_mut(name).ok_or(2303_u16)?;
+            domain.client_id = transfer.requester_id.clone();
+            domain.expiring_at = transfer.expiring_at;
+            domain.transferred_at = Some(Utc::now());
+        }
+
+        self.queue_transfer_message(&notified_id, name, &transfer, message);
+
+        let domain = self.domains.get_mut(name).ok_or(2303_u16)?;
+        domain.transfer = Some(transfer);
+
+        Ok(result_response(1000, request.client_tr_id.as_deref()))
+    }
+}
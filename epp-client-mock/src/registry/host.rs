@@ -0,0 +1,270 @@
+//! Host commands of the simulated registry
+
+use chrono::{DateTime, Utc};
+
+use epp_client::epp::object::data::{HostAddr, HostStatus};
+use epp_client::epp::object::StringValueTrait;
+use epp_client::epp::request::host::{check, create, delete, info, update};
+use epp_client::epp::response::host as response;
+use epp_client::epp::xml::{EPP_HOST_SCHEMA_LOCATION, EPP_HOST_XMLNS};
+
+use super::{key, parse, prohibits, status_list, update_statuses, Outcome, State};
+use crate::request::MockRequest;
+use crate::response::{command_response, result_response};
+
+/// A host in the registry
+pub(super) struct Host {
+    roid: String,
+    addresses: Vec<HostAddr>,
+    statuses: Vec<String>,
+    pub(super) client_id: String,
+    creator_id: String,
+    created_at: DateTime<Utc>,
+    updater_id: Option<String>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+/// Returns the addresses of a host as stored in the registry
+fn addresses(addresses: Option<Vec<HostAddr>>) -> Vec<HostAddr> {
+    addresses.unwrap_or_default()
+}
+
+impl State {
+    /// Returns the host sponsored by the client, giving error code 2303 if it does not exist
+    /// and 2201 if another client sponsors it
+    fn sponsored_host(&self, name: &str, client_id: &str) -> Result<&Host, u16> {
+        match self.hosts.get(name) {
+            Some(host) if host.client_id == client_id => Ok(host),
+            Some(_) => Err(2201),
+            None => Err(2303),
+        }
+    }
+
+    /// Returns whether a domain delegates to the host
+    fn host_linked(&self, name: &str) -> bool {
+        self.domains
+            .values()
+            .any(|domain| domain.ns.iter().any(|ns| ns == name))
+    }
+
+    /// Checks that the client may create a host with the name and addresses. Hosts subordinate
+    /// to a domain of the registry need addresses and the sponsor of the domain
+    fn check_superordinate(
+        &self,
+        name: &str,
+        has_addresses: bool,
+        client_id: &str,
+    ) -> Result<(), u16> {
+        let superordinate = self
+            .domains
+            .iter()
+            .find(|(domain, _)| name.ends_with(&format!(".{}", domain)));
+
+        match superordinate {
+            Some((_, domain)) if domain.client_id != client_id => Err(2201),
+            Some(_) if !has_addresses => Err(2003),
+            _ => Ok(()),
+        }
+    }
+
+    pub(super) fn host_check(&mut self, request: &MockRequest) -> Outcome {
+        let check: check::HostCheck = parse(request)?;
+
+        let host_list = check
+            .list
+            .hosts
+            .iter()
+            .map(|name| {
+                let exists = self.hosts.contains_key(&key(name));
+                response::check::HostCheckDataItem {
+                    host: response::check::HostCheck {
+                        name: name.clone(),
+                        available: u16::from(!exists),
+                    },
+                    reason: match exists {
+                        true => Some("In use".to_string_value()),
+                        false => None,
+                    },
+                }
+            })
+            .collect();
+
+        let res_data = response::check::HostCheckResult {
+            check_data: response::check::HostCheckData {
+                xmlns: EPP_HOST_XMLNS.to_string(),
+                schema_location: EPP_HOST_SCHEMA_LOCATION.to_string(),
+                host_list,
+            },
+        };
+
+        Ok(command_response(
+            1000,
+            Some(res_data),
+            None,
+            request.client_tr_id.as_deref(),
+        ))
+    }
+
+    pub(super) fn host_create(&mut self, request: &MockRequest, client_id: &str) -> Outcome {
+        let create: create::HostCreate = parse(request)?;
+        let data = create.host;
+
+        let name = key(&data.name);
+        if self.hosts.contains_key(&name) {
+            return Err(2302);
+        }
+
+        let addresses = addresses(data.addresses);
+        self.check_superordinate(&name, !addresses.is_empty(), client_id)?;
+
+        let now = Utc::now();
+        let host = Host {
+            roid: self.roid("H"),
+            addresses,
+            statuses: Vec::new(),
+            client_id: client_id.to_string(),
+            creator_id: client_id.to_string(),
+            created_at: now,
+            updater_id: None,
+            updated_at: None,
+        };
+        self.hosts.insert(name.clone(), host);
+
+        let res_data = response::create::HostCreateResult {
+            create_data: response::create::HostCreateData {
+                xmlns: EPP_HOST_XMLNS.to_string(),
+                schema_location: EPP_HOST_SCHEMA_LOCATION.to_string(),
+                name: name.to_string_value(),
+                created_at: now,
+            },
+        };
+
+        Ok(command_response(
+            1000,
+            Some(res_data),
+            None,
+            request.client_tr_id.as_deref(),
+        ))
+    }
+
+    pub(super) fn host_info(&mut self, request: &MockRequest) -> Outcome {
+        let info: info::HostInfo = parse(request)?;
+
+        let name = key(&info.info.name);
+        let host = self.hosts.get(&name).ok_or(2303_u16)?;
+
+        let mut statuses = host.statuses.clone();
+        if self.host_linked(&name) {
+            statuses.push("linked".to_string());
+        }
+
+        let res_data = response::info::HostInfoResult {
+            info_data: response::info::HostInfoData {
+                xmlns: EPP_HOST_XMLNS.to_string(),
+                schema_location: EPP_HOST_SCHEMA_LOCATION.to_string(),
+                name: name.to_string_value(),
+                roid: host.roid.to_string_value(),
+                statuses: status_list(&statuses),
+                addresses: host.addresses.clone(),
+                client_id: host.client_id.to_string_value(),
+                creator_id: host.creator_id.to_string_value(),
+                created_at: host.created_at,
+                updater_id: host.updater_id.as_ref().map(|id| id.to_string_value()),
+                updated_at: host.updated_at,
+                transferred_at: None,
+            },
+        };
+
+        Ok(command_response(
+            1000,
+            Some(res_data),
+            None,
+            request.client_tr_id.as_deref(),
+        ))
+    }
+
+    pub(super) fn host_update(&mut self, request: &MockRequest, client_id: &str) -> Outcome {
+        let update: update::HostUpdate = parse(request)?;
+        let data = update.host;
+
+        let name = key(&data.name);
+        let host = self.sponsored_host(&name, client_id)?;
+
+        let (add_addresses, add_statuses) = match data.add {
+            Some(add) => (addresses(add.addresses), add.statuses.unwrap_or_default()),
+            None => Default::default(),
+        };
+        let (remove_addresses, remove_statuses) = match data.remove {
+            Some(remove) => (
+                addresses(remove.addresses),
+                remove.statuses.unwrap_or_default(),
+            ),
+            None => Default::default(),
+        };
+        let new_name = data.change_info.map(|change_info| key(&change_info.name));
+
+        // Updates are prohibited, except for removing clientUpdateProhibited itself
+        let removes_prohibition = remove_statuses
+            .iter()
+            .any(|s| s.status == HostStatus::ClientUpdateProhibited);
+        if host.statuses.iter().any(|s| s == "serverUpdateProhibited")
+            || (prohibits(&host.statuses, "Update") && !removes_prohibition)
+        {
+            return Err(2304);
+        }
+
+        let mut statuses = host.statuses.clone();
+        update_statuses(&mut statuses, &add_statuses, &remove_statuses)?;
+
+        if let Some(new_name) = &new_name {
+            if self.hosts.contains_key(new_name) {
+                return Err(2302);
+            }
+            self.check_superordinate(new_name, true, client_id)?;
+        }
+
+        let mut host = self.hosts.remove(&name).ok_or(2303_u16)?;
+        host.statuses = statuses;
+
+        host.addresses
+            .retain(|address| !remove_addresses.contains(address));
+        host.addresses.extend(add_addresses);
+
+        host.updater_id = Some(client_id.to_string());
+        host.updated_at = Some(Utc::now());
+
+        // Domains delegating to a renamed host follow the rename
+        let name = match new_name {
+            Some(new_name) => {
+                for domain in self.domains.values_mut() {
+                    for ns in domain.ns.iter_mut().filter(|ns| **ns == name) {
+                        *ns = new_name.clone();
+                    }
+                }
+                new_name
+            }
+            None => name,
+        };
+        self.hosts.insert(name, host);
+
+        Ok(result_response(1000, request.client_tr_id.as_deref()))
+    }
+
+    pub(super) fn host_delete(&mut self, request: &MockRequest, client_id: &str) -> Outcome {
+        let delete: delete::HostDelete = parse(request)?;
+
+        let name = key(&delete.host.name);
+        let host = self.sponsored_host(&name, client_id)?;
+
+        if prohibits(&host.statuses, "Delete") {
+            return Err(2304);
+        }
+        if self.host_linked(&name) {
+            return Err(2305);
+        }
+
+        self.hosts.remove(&name);
+
+        Ok(result_response(1000, request.client_tr_id.as_deref()))
+    }
+}
@@ -0,0 +1,129 @@
+//! Poll messages of the simulated registry
+
+use chrono::{DateTime, Utc};
+
+use epp_client::epp::object::StringValueTrait;
+use epp_client::epp::request::message::{ack::MessageAck, poll::MessagePoll};
+use epp_client::epp::response::message::poll::{MessageDomainTransferData, MessagePollResult};
+use epp_client::epp::response::MessageQueue;
+use epp_client::epp::xml::EPP_DOMAIN_XMLNS;
+
+use super::domain::Transfer;
+use super::{parse, Outcome, State};
+use crate::request::MockRequest;
+use crate::response::command_response;
+
+/// A message queued for a client about a transfer of one of its domains
+pub(super) struct Message {
+    id: String,
+    client_id: String,
+    date: DateTime<Utc>,
+    text: String,
+    name: String,
+    transfer: Transfer,
+}
+
+impl Message {
+    /// Returns the &lt;msgQ&gt; section of responses for the message
+    fn queue(&self, count: usize, text: bool) -> MessageQueue {
+        MessageQueue {
+            count: count as u32,
+            id: self.id.clone(),
+            date: Some(self.date),
+            message: match text {
+                true => Some(self.text.to_string_value()),
+                false => None,
+            },
+        }
+    }
+}
+
+impl State {
+    /// Queues a message for a client about a transfer of a domain
+    pub(super) fn queue_transfer_message(
+        &mut self,
+        client_id: &str,
+        name: &str,
+        transfer: &Transfer,
+        text: &str,
+    ) {
+        self.next_message_id += 1;
+
+        self.messages.push(Message {
+            id: self.next_message_id.to_string(),
+            client_id: client_id.to_string(),
+            date: Utc::now(),
+            text: text.to_string(),
+            name: name.to_string(),
+            transfer: transfer.clone(),
+        });
+    }
+
+    /// Returns the messages queued for a client, oldest first
+    fn queue(&self, client_id: &str) -> Vec<&Message> {
+        self.messages
+            .iter()
+            .filter(|message| message.client_id == client_id)
+            .collect()
+    }
+
+    pub(super) fn poll(&mut self, request: &MockRequest, client_id: &str) -> Outcome {
+        let client_tr_id = request.client_tr_id.as_deref();
+
+        match request.op.as_deref() {
+            Some("req") => {
+                let queue = self.queue(client_id);
+
+                let message = match queue.first() {
+                    Some(message) => message,
+                    None => return Ok(command_response::<String>(1300, None, None, client_tr_id)),
+                };
+
+                let transfer = &message.transfer;
+                let res_data = MessagePollResult {
+                    message_data: MessageDomainTransferData {
+                        xmlns: EPP_DOMAIN_XMLNS.to_string(),
+                        name: message.name.to_string_value(),
+                        transfer_status: transfer.status.to_string_value(),
+                        requester_id: transfer.requester_id.to_string_value(),
+                        requested_at: transfer.requested_at,
+                        ack_id: transfer.ack_id.to_string_value(),
+                        ack_by: transfer.ack_by,
+                        expiring_at: transfer.expiring_at,
+                    },
+                };
+
+                Ok(command_response(
+                    1301,
+                    Some(res_data),
+                    Some(message.queue(queue.len(), true)),
+                    client_tr_id,
+                ))
+            }
+            Some("ack") => {
+                let ack: MessageAck = parse(request)?;
+
+                let position = self
+                    .messages
+                    .iter()
+                    .position(|m| m.client_id == client_id && m.id == ack.message_id)
+                    .ok_or(2303_u16)?;
+                self.messages.remove(position);
+
+                let queue = self.queue(client_id);
+                let message_queue = queue.first().map(|next| next.queue(queue.len(), false));
+
+                Ok(command_response::<String>(
+                    1000,
+                    None,
+                    message_queue,
+                    client_tr_id,
+                ))
+            }
+            _ => {
+                parse::<MessagePoll>(request)?;
+                Err(2005)
+            }
+        }
+    }
+}
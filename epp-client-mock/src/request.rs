@@ -0,0 +1,183 @@
+//! Parses the parts of incoming EPP requests needed to route them to a response
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use epp_client::error;
+
+/// An EPP request received by the mock server
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockRequest {
+    /// The raw XML of the request
+    pub xml: String,
+    /// The command, e.g. `login`, `poll` or `domain:check` for object commands. The command
+    /// of an EPP hello is `hello`
+    pub command: String,
+    /// The value of the `op` attribute of the command, as on &lt;transfer&gt; and &lt;poll&gt;
+    pub op: Option<String>,
+    /// The client transaction ID of the command
+    pub client_tr_id: Option<String>,
+    /// The names of the elements in the &lt;extension&gt; section, qualified with their
+    /// extension, e.g. `rgp:update`
+    pub extensions: Vec<String>,
+    /// The client ID the session is logged in as, set by the server
+    pub client_id: Option<String>,
+}
+
+/// Returns the qualified name of an element as a string
+fn name(element: &BytesStart) -> String {
+    String::from_utf8_lossy(element.name()).to_string()
+}
+
+/// Returns the object or extension name of a namespace URI, e.g. `domain` for
+/// `urn:ietf:params:xml:ns:domain-1.0`
+fn namespace_name(uri: &str) -> &str {
+    let name = uri.rsplit([':', '/']).next().unwrap_or(uri);
+
+    match name.rsplit_once('-') {
+        Some((name, _version)) => name,
+        None => name,
+    }
+}
+
+/// Returns the name of an element qualified with its object or extension, e.g. `domain:check`
+/// for both `<domain:check>` and `<check xmlns="urn:ietf:params:xml:ns:domain-1.0">`
+fn qualified_name(element: &BytesStart) -> String {
+    let element_name = name(element);
+
+    match attribute(element, b"xmlns") {
+        Some(uri) if !element_name.contains(':') => {
+            format!("{}:{}", namespace_name(&uri), element_name)
+        }
+        _ => element_name,
+    }
+}
+
+/// Returns the value of an attribute of an element, if it is set
+fn attribute(element: &BytesStart, key: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|attr| attr.key == key)
+        .map(|attr| String::from_utf8_lossy(&attr.value).to_string())
+}
+
+impl MockRequest {
+    /// Parses an EPP request
+    pub fn parse(xml: &str) -> Result<MockRequest, error::Error> {
+        let mut reader = Reader::from_str(xml);
+        reader.trim_text(true);
+
+        let mut request = MockRequest {
+            xml: xml.to_string(),
+            command: String::new(),
+            op: None,
+            client_tr_id: None,
+            extensions: Vec::new(),
+            client_id: None,
+        };
+
+        let mut path: Vec<String> = Vec::new();
+        let mut buf = Vec::new();
+
+        loop {
+            let event = reader
+                .read_event(&mut buf)
+                .map_err(|e| error::Error::EppDeserializationError(e.to_string()))?;
+
+            match event {
+                Event::Start(ref element) | Event::Empty(ref element) => {
+                    let element_name = name(element);
+                    request.visit(&path, &element_name, element);
+
+                    if let Event::Start(_) = event {
+                        path.push(element_name);
+                    }
+                }
+                Event::Text(ref text) if path.last().map(String::as_str) == Some("clTRID") => {
+                    let text = text
+                        .unescape_and_decode(&reader)
+                        .map_err(|e| error::Error::EppDeserializationError(e.to_string()))?;
+                    request.client_tr_id = Some(text);
+                }
+                Event::End(_) => {
+                    path.pop();
+                }
+                Event::Eof => break,
+                _ => (),
+            }
+            buf.clear();
+        }
+
+        if request.command.is_empty() {
+            return Err(error::Error::EppDeserializationError(
+                "No EPP command found in request".to_string(),
+            ));
+        }
+
+        Ok(request)
+    }
+
+    /// Records the command details found in an element, given the path of its parents
+    fn visit(&mut self, path: &[String], element_name: &str, element: &BytesStart) {
+        let parents: Vec<&str> = path.iter().map(String::as_str).collect();
+
+        match parents.as_slice() {
+            ["epp"] if element_name == "hello" => self.command = element_name.to_string(),
+            ["epp", "command"] if element_name != "extension" && element_name != "clTRID" => {
+                self.command = element_name.to_string();
+                self.op = attribute(element, b"op");
+            }
+            // Object commands are qualified with the object namespace
+            ["epp", "command", verb] if *verb != "extension" => {
+                let qualified_name = qualified_name(element);
+
+                if qualified_name.contains(':') {
+                    self.command = qualified_name;
+                }
+            }
+            ["epp", "command", "extension"] => self.extensions.push(qualified_name(element)),
+            _ => (),
+        }
+    }
+
+    /// Returns the object of the command, e.g. `domain` for `domain:check`
+    pub fn object(&self) -> Option<&str> {
+        self.command.split_once(':').map(|(object, _)| object)
+    }
+
+    /// Returns the text of the first element with the given name, ignoring namespace
+    /// prefixes, e.g. `name` for both `<name>` and `<domain:name>`
+    pub fn value(&self, element_name: &str) -> Option<String> {
+        self.values(element_name).into_iter().next()
+    }
+
+    /// Returns the text of all elements with the given name, ignoring namespace prefixes
+    pub fn values(&self, element_name: &str) -> Vec<String> {
+        let mut reader = Reader::from_str(&self.xml);
+        reader.trim_text(true);
+
+        let mut values = Vec::new();
+        let mut inside = false;
+        let mut buf = Vec::new();
+
+        loop {
+            match reader.read_event(&mut buf) {
+                Ok(Event::Start(ref element)) => {
+                    inside = element.local_name() == element_name.as_bytes()
+                }
+                Ok(Event::Text(ref text)) if inside => {
+                    if let Ok(text) = text.unescape_and_decode(&reader) {
+                        values.push(text);
+                    }
+                }
+                Ok(Event::End(_)) => inside = false,
+                Ok(Event::Eof) | Err(_) => break,
+                _ => (),
+            }
+            buf.clear();
+        }
+
+        values
+    }
+}
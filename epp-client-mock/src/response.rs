@@ -0,0 +1,187 @@
+//! Builds the EPP responses sent by the mock server
+
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use epp_client::epp::object::{EppObject, StringValueTrait};
+use epp_client::epp::response::{
+    CommandResponse, EppResult, MessageQueue, ResponseTRID, ResultCode, ResultText,
+};
+use epp_client::epp::xml::EppXml;
+
+use crate::request::MockRequest;
+use crate::server::MockHandler;
+
+/// The directory of the response fixtures of the epp-client tests
+pub const FIXTURES_DIR: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../epp-client/test/resources/response"
+);
+
+/// The default greeting, taken from the epp-client test fixtures
+pub const GREETING: &str = include_str!("../../epp-client/test/resources/response/greeting.xml");
+
+static SERVER_TR_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Returns a new server transaction ID
+pub fn server_tr_id() -> String {
+    format!("MOCK-{}", SERVER_TR_ID.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Returns the message for an EPP result code as given in RFC 5730
+pub fn result_message(code: u16) -> &'static str {
+    match ResultCode::from(code) {
+        ResultCode::Unknown(_) => "Command failed",
+        code => code.message(),
+    }
+}
+
+/// Returns the &lt;trID&gt; section of a response
+fn tr_ids(client_tr_id: Option<&str>) -> String {
+    let client_tr_id = match client_tr_id {
+        Some(client_tr_id) => format!("<clTRID>{}</clTRID>", client_tr_id),
+        None => String::new(),
+    };
+
+    format!(
+        "<trID>{}<svTRID>{}</svTRID></trID>",
+        client_tr_id,
+        server_tr_id()
+    )
+}
+
+/// Returns a response with the given result code and its standard message, followed by the
+/// optional response data
+pub fn response(code: u16, res_data: Option<&str>, client_tr_id: Option<&str>) -> String {
+    let res_data = match res_data {
+        Some(res_data) => format!("<resData>{}</resData>", res_data),
+        None => String::new(),
+    };
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?><epp xmlns="urn:ietf:params:xml:ns:epp-1.0" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="urn:ietf:params:xml:ns:epp-1.0 epp-1.0.xsd"><response><result code="{}"><msg>{}</msg></result>{}{}</response></epp>"#,
+        code,
+        result_message(code),
+        res_data,
+        tr_ids(client_tr_id)
+    )
+}
+
+/// Renders a response with the given result code, message queue and response data, using the
+/// epp-client response types
+pub fn command_response<T: Serialize + DeserializeOwned + Debug>(
+    code: u16,
+    res_data: Option<T>,
+    message_queue: Option<MessageQueue>,
+    client_tr_id: Option<&str>,
+) -> String {
+    let response = CommandResponse {
+        results: vec![EppResult {
+            message: ResultText::new(result_message(code)),
+            ..EppResult::new(code.into())
+        }],
+        message_queue,
+        res_data,
+        extension: None,
+        tr_ids: ResponseTRID {
+            client_tr_id: client_tr_id.map(|id| id.to_string_value()),
+            server_tr_id: server_tr_id().to_string_value(),
+        },
+    };
+
+    match EppXml::serialize(&EppObject::build(response)) {
+        Ok(xml) => xml,
+        Err(e) => {
+            warn!("Mock server failed to serialize response: {}", e);
+            result_response(2400, client_tr_id)
+        }
+    }
+}
+
+/// Returns a response with only a result section
+pub fn result_response(code: u16, client_tr_id: Option<&str>) -> String {
+    response(code, None, client_tr_id)
+}
+
+/// Replaces the client transaction ID in a canned response with that of the request, so
+/// fixtures can be sent in response to any request
+pub fn with_client_tr_id(response: &str, client_tr_id: Option<&str>) -> String {
+    let client_tr_id = match client_tr_id {
+        Some(client_tr_id) => client_tr_id,
+        None => return response.to_string(),
+    };
+
+    match (response.find("<clTRID>"), response.find("</clTRID>")) {
+        (Some(start), Some(end)) if start < end => format!(
+            "{}<clTRID>{}{}",
+            &response[..start],
+            client_tr_id,
+            &response[end..]
+        ),
+        _ => response.to_string(),
+    }
+}
+
+/// Answers requests with the response fixtures of the epp-client tests, e.g.
+/// `domain/check.xml` for a domain check or `domain/transfer_query.xml` for a domain
+/// transfer query
+pub struct Fixtures {
+    dir: PathBuf,
+}
+
+impl Default for Fixtures {
+    fn default() -> Self {
+        Fixtures::new(FIXTURES_DIR)
+    }
+}
+
+impl Fixtures {
+    /// Creates a handler answering from the fixtures in the given directory
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Fixtures {
+        Fixtures { dir: dir.into() }
+    }
+
+    /// Returns the path of the fixture for a request, relative to the fixtures directory
+    fn fixture(request: &MockRequest) -> Option<String> {
+        let op = request.op.as_deref();
+
+        let fixture = match (request.command.as_str(), op) {
+            ("poll", Some("ack")) => "message/ack.xml".to_string(),
+            ("poll", _) => "message/poll.xml".to_string(),
+            ("domain:update", _) if request.extensions.iter().any(|e| e == "rgp:update") => {
+                "domain/rgp_restore.xml".to_string()
+            }
+            (command, Some(op)) if command.ends_with(":transfer") => {
+                format!("{}/transfer_{}.xml", request.object()?, op)
+            }
+            (command, _) => match command.split_once(':') {
+                Some((object, command)) => format!("{}/{}.xml", object, command),
+                None => return None,
+            },
+        };
+
+        Some(fixture)
+    }
+}
+
+impl MockHandler for Fixtures {
+    fn handle(&self, request: &MockRequest) -> Option<String> {
+        let path = self.dir.join(Fixtures::fixture(request)?);
+
+        match fs::read_to_string(&path) {
+            Ok(response) => Some(response),
+            Err(e) => {
+                debug!(
+                    "No fixture for {}: {}: {}",
+                    request.command,
+                    path.display(),
+                    e
+                );
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,361 @@
+//! The mock EPP server, handling the session lifecycle and routing commands to handlers
+
+use rustls::Certificate;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio_rustls::TlsAcceptor;
+
+use epp_client::config::EppClientConnection;
+use epp_client::connection::codec::EppCodec;
+use epp_client::error;
+
+use crate::request::MockRequest;
+use crate::response::{result_response, with_client_tr_id, Fixtures, GREETING};
+use crate::tls;
+
+/// Answers the commands of logged-in sessions. Returns `None` to leave the command to the
+/// next handler, or an empty response to drop the connection without answering, as a
+/// registry failing mid-command would. Closures taking a `&MockRequest` are handlers too
+pub trait MockHandler: Send + Sync {
+    /// Returns the response to a request, if the handler answers it
+    fn handle(&self, request: &MockRequest) -> Option<String>;
+}
+
+impl<F: Fn(&MockRequest) -> Option<String> + Send + Sync> MockHandler for F {
+    fn handle(&self, request: &MockRequest) -> Option<String> {
+        self(request)
+    }
+}
+
+/// A mock EPP server that sends a greeting, accepts logins and answers commands with the
+/// first handler that returns a response, or with code 2101 if none does
+pub struct MockServer {
+    greeting: String,
+    credentials: Mutex<Option<(String, String)>>,
+    handlers: Vec<Box<dyn MockHandler>>,
+    tls_server_name: Option<String>,
+    codec: EppCodec,
+    session_limit: Option<usize>,
+    sessions: AtomicUsize,
+    logins: AtomicUsize,
+    logouts: AtomicUsize,
+}
+
+impl Default for MockServer {
+    fn default() -> Self {
+        MockServer {
+            greeting: GREETING.to_string(),
+            credentials: Mutex::new(None),
+            handlers: Vec::new(),
+            tls_server_name: None,
+            codec: EppCodec::default(),
+            session_limit: None,
+            sessions: AtomicUsize::new(0),
+            logins: AtomicUsize::new(0),
+            logouts: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl MockServer {
+    /// Creates a server sending the greeting of the epp-client test fixtures and accepting
+    /// any credentials
+    pub fn new() -> MockServer {
+        MockServer::default()
+    }
+
+    /// Sets the greeting sent on connection and in response to a hello
+    pub fn greeting(mut self, greeting: &str) -> Self {
+        self.greeting = greeting.to_string();
+        self
+    }
+
+    /// Only accepts logins with the given username and password. A login with a new password
+    /// changes the password the server accepts
+    pub fn credentials(self, username: &str, password: &str) -> Self {
+        *self.credentials.lock().unwrap() = Some((username.to_string(), password.to_string()));
+        self
+    }
+
+    /// Rejects logins with code 2502 while the given number of sessions are logged in
+    pub fn session_limit(mut self, session_limit: usize) -> Self {
+        self.session_limit = Some(session_limit);
+        self
+    }
+
+    /// Adds a handler, which is asked after the handlers added before it
+    pub fn handler<H: MockHandler + 'static>(mut self, handler: H) -> Self {
+        self.handlers.push(Box::new(handler));
+        self
+    }
+
+    /// Answers all requests for a command, e.g. `domain:check`, with the given response
+    pub fn respond(self, command: &str, response: &str) -> Self {
+        let command = command.to_string();
+        let response = response.to_string();
+
+        self.handler(
+            move |request: &MockRequest| match request.command == command {
+                true => Some(response.clone()),
+                false => None,
+            },
+        )
+    }
+
+    /// Answers commands without a response from the handlers added before with the response
+    /// fixtures of the epp-client tests
+    pub fn fixtures(self) -> Self {
+        self.handler(Fixtures::default())
+    }
+
+    /// Serves connections over TLS with a self-signed certificate generated for the server name
+    pub fn tls(mut self, server_name: &str) -> Self {
+        self.tls_server_name = Some(server_name.to_string());
+        self
+    }
+
+    /// Returns the response to a request, given the client ID of the session if logged in
+    fn respond_to(&self, request: &mut MockRequest, client_id: &mut Option<String>) -> String {
+        let client_tr_id = request.client_tr_id.clone();
+        let client_tr_id = client_tr_id.as_deref();
+
+        match request.command.as_str() {
+            "hello" => return self.greeting.clone(),
+            "login" if client_id.is_some() => return result_response(2002, client_tr_id),
+            "login" => {
+                let username = request.value("clID").unwrap_or_default();
+                let password = request.value("pw").unwrap_or_default();
+
+                let mut credentials = self.credentials.lock().unwrap_or_else(|e| e.into_inner());
+                return match credentials.as_mut() {
+                    Some((u, p)) if *u != username || *p != password => {
+                        result_response(2200, client_tr_id)
+                    }
+                    _ if self
+                        .session_limit
+                        .is_some_and(|limit| self.sessions.load(Ordering::SeqCst) >= limit) =>
+                    {
+                        result_response(2502, client_tr_id)
+                    }
+                    credentials => {
+                        if let (Some((_, p)), Some(new_password)) =
+                            (credentials, request.value("newPW"))
+                        {
+                            *p = new_password;
+                        }
+                        *client_id = Some(username);
+                        self.sessions.fetch_add(1, Ordering::SeqCst);
+                        self.logins.fetch_add(1, Ordering::SeqCst);
+                        result_response(1000, client_tr_id)
+                    }
+                };
+            }
+            _ if client_id.is_none() => return result_response(2002, client_tr_id),
+            "logout" => {
+                *client_id = None;
+                self.sessions.fetch_sub(1, Ordering::SeqCst);
+                self.logouts.fetch_add(1, Ordering::SeqCst);
+                return result_response(1500, client_tr_id);
+            }
+            _ => (),
+        }
+
+        request.client_id = client_id.clone();
+
+        match self.handlers.iter().find_map(|h| h.handle(request)) {
+            Some(response) => with_client_tr_id(&response, client_tr_id),
+            None => result_response(2101, client_tr_id),
+        }
+    }
+
+    /// Returns the number of successful logins to the server
+    pub fn logins(&self) -> usize {
+        self.logins.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of logouts from the server
+    pub fn logouts(&self) -> usize {
+        self.logouts.load(Ordering::SeqCst)
+    }
+
+    /// Runs an EPP session over a stream, until the client logs out or closes the stream
+    pub async fn serve<S: AsyncRead + AsyncWrite + Unpin>(
+        &self,
+        mut stream: S,
+    ) -> Result<(), error::Error> {
+        self.codec
+            .write_frame(&mut stream, self.greeting.as_bytes())
+            .await?;
+
+        let mut client_id = None;
+        let result = self.serve_requests(&mut stream, &mut client_id).await;
+
+        // Sessions dropped without logging out end with the connection
+        if client_id.is_some() {
+            self.sessions.fetch_sub(1, Ordering::SeqCst);
+        }
+
+        result
+    }
+
+    /// Answers the requests on a stream until the client logs out or closes the stream
+    async fn serve_requests<S: AsyncRead + AsyncWrite + Unpin>(
+        &self,
+        stream: &mut S,
+        client_id: &mut Option<String>,
+    ) -> Result<(), error::Error> {
+        loop {
+            let frame = match self.codec.read_frame(stream).await {
+                Ok(frame) => frame,
+                Err(error::Error::EppConnectionClosed) => return Ok(()),
+                Err(e) => return Err(e),
+            };
+
+            let xml = String::from_utf8(frame)
+                .map_err(|e| error::Error::EppDeserializationError(e.to_string()))?;
+
+            let response = match MockRequest::parse(&xml) {
+                Ok(mut request) => {
+                    debug!("Mock server received {}", request.command);
+                    let response = self.respond_to(&mut request, client_id);
+
+                    if response.is_empty() {
+                        debug!("Mock server dropping the session");
+                        return Ok(());
+                    }
+
+                    if request.command == "logout" {
+                        self.codec.write_frame(stream, response.as_bytes()).await?;
+                        return Ok(());
+                    }
+                    response
+                }
+                Err(e) => {
+                    warn!("Mock server received an invalid request: {}", e);
+                    result_response(2001, None)
+                }
+            };
+
+            self.codec.write_frame(stream, response.as_bytes()).await?;
+        }
+    }
+
+    /// Listens for connections on the address, e.g. `127.0.0.1:0` for any free port, and
+    /// serves each of them on its own task until the returned handle is dropped
+    pub async fn bind(self, addr: &str) -> Result<MockServerHandle, error::Error> {
+        let listener = TcpListener::bind(addr).await?;
+        let addr = listener.local_addr()?;
+
+        let (acceptor, root_cert) = match &self.tls_server_name {
+            Some(server_name) => {
+                let (acceptor, root_cert) = tls::generate(server_name)?;
+                (Some(acceptor), Some(root_cert))
+            }
+            None => (None, None),
+        };
+
+        let tls_server_name = self.tls_server_name.clone();
+        let server = Arc::new(self);
+
+        let handle_server = server.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                let stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        warn!("Mock server failed to accept connection: {}", e);
+                        continue;
+                    }
+                };
+
+                let server = server.clone();
+                let acceptor: Option<TlsAcceptor> = acceptor.clone();
+
+                tokio::spawn(async move {
+                    let result = match acceptor {
+                        Some(acceptor) => match acceptor.accept(stream).await {
+                            Ok(stream) => server.serve(stream).await,
+                            Err(e) => Err(e.into()),
+                        },
+                        None => server.serve(stream).await,
+                    };
+
+                    if let Err(e) = result {
+                        warn!("Mock server session failed: {}", e);
+                    }
+                });
+            }
+        });
+
+        info!("Mock server listening on {}", addr);
+
+        Ok(MockServerHandle {
+            server: handle_server,
+            addr,
+            root_cert,
+            tls_server_name,
+            task,
+        })
+    }
+}
+
+/// A running mock server, which stops listening when dropped
+pub struct MockServerHandle {
+    server: Arc<MockServer>,
+    addr: SocketAddr,
+    root_cert: Option<Certificate>,
+    tls_server_name: Option<String>,
+    task: JoinHandle<()>,
+}
+
+impl MockServerHandle {
+    /// Returns the address the server is listening on
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the number of successful logins to the server, counting every session a
+    /// client opened or reopened
+    pub fn logins(&self) -> usize {
+        self.server.logins()
+    }
+
+    /// Returns the number of logouts from the server
+    pub fn logouts(&self) -> usize {
+        self.server.logouts()
+    }
+
+    /// Returns the self-signed certificate of the server, if it serves TLS
+    pub fn root_cert(&self) -> Option<&Certificate> {
+        self.root_cert.as_ref()
+    }
+
+    /// Returns connection details for connecting an `EppClient` to the server, trusting only
+    /// its self-signed certificate if it serves TLS
+    pub fn connection(&self, username: &str, password: &str) -> EppClientConnection {
+        let host = self.addr.ip().to_string();
+        let mut registry_creds =
+            EppClientConnection::new(&host, self.addr.port(), username, password);
+
+        match (&self.root_cert, &self.tls_server_name) {
+            (Some(root_cert), Some(server_name)) => {
+                registry_creds.set_bundled_roots(false);
+                registry_creds.set_root_certs(vec![root_cert.clone()]);
+                registry_creds.set_server_name(server_name);
+            }
+            _ => registry_creds.set_tls(false),
+        }
+
+        registry_creds
+    }
+}
+
+impl Drop for MockServerHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
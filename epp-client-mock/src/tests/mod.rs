@@ -0,0 +1,19 @@
+//! Module for automated tests
+
+pub mod pool;
+pub mod reconnect;
+pub mod registry;
+pub mod request;
+pub mod server;
+
+use std::fs;
+
+const REQUESTS_DIR: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../epp-client/test/resources/request"
+);
+
+/// Reads an EPP XML request from the epp-client test fixtures
+fn get_request(path: &str) -> String {
+    fs::read_to_string(format!("{}/{}", REQUESTS_DIR, path)).unwrap()
+}
@@ -0,0 +1,138 @@
+//! `EppPool` tests against the mock server
+
+use std::time::Duration;
+use tokio::time::timeout;
+
+use epp_client::epp::{EppDomainCheck, EppDomainCheckResponse, EppHostInfo, EppHostInfoResponse};
+use epp_client::error::Error;
+use epp_client::EppPool;
+
+use crate::response::response;
+use crate::server::MockServerHandle;
+use crate::MockServer;
+
+const CLTRID: &str = "cltrid:pool";
+
+/// Starts a server answering from the fixtures, except host info requests, which it answers
+/// with code 2500 to end the session
+async fn server() -> MockServerHandle {
+    MockServer::new()
+        .respond("host:info", &response(2500, None, None))
+        .fixtures()
+        .bind("127.0.0.1:0")
+        .await
+        .unwrap()
+}
+
+/// Opens a pool of the given size to the server
+async fn pool(server: &MockServerHandle, size: usize) -> EppPool {
+    let registry_creds = server.connection("username", "password");
+    EppPool::build("mock", registry_creds, size).await.unwrap()
+}
+
+#[tokio::test]
+async fn get_waits_for_free_session() {
+    let server = server().await;
+    let pool = pool(&server, 2).await;
+    assert_eq!(server.logins(), 2);
+
+    let mut client_a = pool.get().await.unwrap();
+    let client_b = pool.get().await.unwrap();
+
+    // Both sessions are checked out, so the next one waits
+    assert!(timeout(Duration::from_millis(100), pool.get())
+        .await
+        .is_err());
+
+    drop(client_b);
+    let client_b = timeout(Duration::from_millis(100), pool.get())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let domain_check = EppDomainCheck::new(vec!["eppdev.com"], CLTRID);
+    let response = client_a
+        .transact::<_, EppDomainCheckResponse>(&domain_check)
+        .await
+        .unwrap();
+    assert_eq!(response.data.result().unwrap().code, 1000);
+
+    drop(client_a);
+    drop(client_b);
+    pool.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn dropped_session_returns_to_pool() {
+    let server = server().await;
+    let pool = pool(&server, 1).await;
+
+    let client = pool.get().await.unwrap();
+    drop(client);
+
+    // The same session is checked out again without logging in
+    let client = pool.get().await.unwrap();
+    assert!(client.is_open());
+    assert_eq!(server.logins(), 1);
+
+    drop(client);
+    pool.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn closed_session_replaced() {
+    let server = server().await;
+    let pool = pool(&server, 1).await;
+
+    let mut client = pool.get().await.unwrap();
+    let host_info = EppHostInfo::new("ns1.eppdev.com", CLTRID);
+    match client.transact::<_, EppHostInfoResponse>(&host_info).await {
+        Err(Error::EppCommandError(e)) => assert_eq!(e.code, 2500),
+        _ => panic!("expected EppCommandError"),
+    }
+    assert!(!client.is_open());
+    drop(client);
+
+    // The closed session is discarded and a new one logged in in its place
+    let client = pool.get().await.unwrap();
+    assert!(client.is_open());
+    assert_eq!(server.logins(), 2);
+
+    drop(client);
+    pool.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn close_logs_out_sessions() {
+    let server = server().await;
+    let pool = pool(&server, 3).await;
+
+    let client = pool.get().await.unwrap();
+    drop(client);
+
+    pool.close().await.unwrap();
+    assert_eq!(server.logins(), 3);
+    assert_eq!(server.logouts(), 3);
+}
+
+#[tokio::test]
+async fn failed_build_logs_out_sessions() {
+    let server = MockServer::new()
+        .session_limit(2)
+        .fixtures()
+        .bind("127.0.0.1:0")
+        .await
+        .unwrap();
+    let registry_creds = server.connection("username", "password");
+
+    // The third login exceeds the session limit
+    match EppPool::build("mock", registry_creds, 3).await {
+        Err(Error::EppLoginError(e)) => assert_eq!(e.code, 2502),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("pool opened"),
+    }
+
+    // The two sessions opened before are logged out
+    assert_eq!(server.logins(), 2);
+    assert_eq!(server.logouts(), 2);
+}
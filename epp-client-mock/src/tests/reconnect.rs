@@ -0,0 +1,212 @@
+//! `EppClient` reconnect and resend tests against the mock server
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use epp_client::config::{EppClientConnection, EppClientReconnectPolicy};
+use epp_client::epp::object::data::DomainContact;
+use epp_client::epp::{
+    EppDomainCheck, EppDomainCheckResponse, EppDomainCreate, EppDomainCreateResponse,
+};
+use epp_client::error::Error;
+use epp_client::EppClient;
+
+use crate::request::MockRequest;
+use crate::response::response;
+use crate::server::MockServerHandle;
+use crate::MockServer;
+
+const CLTRID: &str = "cltrid:reconnect";
+
+/// Starts a server that answers the first request for the command with the given response,
+/// or drops the connection if it is empty, and answers everything else from the fixtures.
+/// Also returns the number of requests received for the command
+async fn server(command: &str, first_response: &str) -> (MockServerHandle, Arc<AtomicUsize>) {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let command = command.to_string();
+    let first_response = first_response.to_string();
+
+    let counter = requests.clone();
+    let server = MockServer::new()
+        .credentials("username", "password")
+        .handler(move |request: &MockRequest| {
+            if request.command != command {
+                return None;
+            }
+            match counter.fetch_add(1, Ordering::SeqCst) {
+                0 => Some(first_response.clone()),
+                _ => None,
+            }
+        })
+        .fixtures()
+        .bind("127.0.0.1:0")
+        .await
+        .unwrap();
+
+    (server, requests)
+}
+
+/// Returns connection details for the server with a reconnect policy retrying once
+fn registry_creds(server: &MockServerHandle) -> EppClientConnection {
+    let mut registry_creds = server.connection("username", "password");
+    registry_creds.set_reconnect(EppClientReconnectPolicy {
+        max_attempts: 3,
+        backoff: 50,
+        max_backoff: 80,
+        max_retries: 1,
+    });
+    registry_creds
+}
+
+fn domain_check() -> EppDomainCheck {
+    EppDomainCheck::new(vec!["eppdev.com"], CLTRID)
+}
+
+fn domain_create() -> EppDomainCreate {
+    let contacts = vec![DomainContact {
+        contact_type: "admin".to_string(),
+        id: "eppdev-contact-2".to_string(),
+    }];
+    EppDomainCreate::new(
+        "eppdev-1.com",
+        1,
+        "eppdev-contact-3",
+        "epP4uthd#v",
+        contacts,
+        CLTRID,
+    )
+}
+
+#[tokio::test]
+async fn resend_after_session_ended() {
+    let (server, checks) = server("domain:check", &response(2502, None, None)).await;
+    let mut client = EppClient::connect("mock", &registry_creds(&server))
+        .await
+        .unwrap();
+
+    let response = client
+        .transact::<_, EppDomainCheckResponse>(&domain_check())
+        .await
+        .unwrap();
+    assert_eq!(response.data.result().unwrap().code, 1000);
+
+    // The check was resent once on a second session
+    assert_eq!(checks.load(Ordering::SeqCst), 2);
+    assert_eq!(server.logins(), 2);
+
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn resend_after_session_dropped() {
+    let (server, checks) = server("domain:check", "").await;
+    let mut client = EppClient::connect("mock", &registry_creds(&server))
+        .await
+        .unwrap();
+
+    let response = client
+        .transact::<_, EppDomainCheckResponse>(&domain_check())
+        .await
+        .unwrap();
+    assert_eq!(response.data.result().unwrap().code, 1000);
+
+    assert_eq!(checks.load(Ordering::SeqCst), 2);
+    assert_eq!(server.logins(), 2);
+
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn not_resent_after_authentication_error() {
+    let (server, checks) = server("domain:check", &response(2501, None, None)).await;
+    let mut client = EppClient::connect("mock", &registry_creds(&server))
+        .await
+        .unwrap();
+
+    // 2501 ends the session, but resending the check would fail the same way
+    let result = client
+        .transact::<_, EppDomainCheckResponse>(&domain_check())
+        .await;
+    match result {
+        Err(Error::EppCommandError(e)) => assert_eq!(e.code, 2501),
+        _ => panic!("expected EppCommandError"),
+    }
+    assert_eq!(checks.load(Ordering::SeqCst), 1);
+    assert_eq!(server.logins(), 1);
+    assert!(!client.is_open());
+}
+
+#[tokio::test]
+async fn create_not_resent_after_session_ended() {
+    let (server, creates) = server("domain:create", &response(2500, None, None)).await;
+    let mut client = EppClient::connect("mock", &registry_creds(&server))
+        .await
+        .unwrap();
+
+    let result = client
+        .transact::<_, EppDomainCreateResponse>(&domain_create())
+        .await;
+    match result {
+        Err(Error::EppCommandError(e)) => assert_eq!(e.code, 2500),
+        _ => panic!("expected EppCommandError"),
+    }
+    assert_eq!(creates.load(Ordering::SeqCst), 1);
+    assert!(!client.is_open());
+
+    // The next request reconnects
+    client
+        .transact::<_, EppDomainCheckResponse>(&domain_check())
+        .await
+        .unwrap();
+    assert_eq!(creates.load(Ordering::SeqCst), 1);
+    assert_eq!(server.logins(), 2);
+
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn create_not_resent_after_session_dropped() {
+    let (server, creates) = server("domain:create", "").await;
+    let mut client = EppClient::connect("mock", &registry_creds(&server))
+        .await
+        .unwrap();
+
+    let result = client
+        .transact::<_, EppDomainCreateResponse>(&domain_create())
+        .await;
+    assert!(matches!(result, Err(Error::EppConnectionClosed)));
+    assert_eq!(creates.load(Ordering::SeqCst), 1);
+    assert_eq!(server.logins(), 1);
+}
+
+#[tokio::test]
+async fn reconnect_backs_off() {
+    let (server, checks) = server("domain:check", &response(2500, None, None)).await;
+    let mut client = EppClient::connect("mock", &registry_creds(&server))
+        .await
+        .unwrap();
+
+    // Another client changes the password, so the first one can no longer log in again
+    let mut other_creds = server.connection("username", "password");
+    other_creds.set_new_password("changed");
+    let other = EppClient::connect("mock", &other_creds).await.unwrap();
+    assert_eq!(server.logins(), 2);
+
+    let start = Instant::now();
+    let result = client
+        .transact::<_, EppDomainCheckResponse>(&domain_check())
+        .await;
+    match result {
+        Err(Error::EppLoginError(e)) => assert_eq!(e.code, 2200),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("reconnect succeeded"),
+    }
+
+    // Three attempts, waiting 50ms and then 80ms, the maximum backoff
+    assert!(start.elapsed() >= Duration::from_millis(130));
+    assert_eq!(checks.load(Ordering::SeqCst), 1);
+    assert_eq!(server.logins(), 2);
+
+    other.close().await.unwrap();
+}
@@ -0,0 +1,491 @@
+//! Provisioning workflows against the simulated registry
+
+use chrono::Datelike;
+use std::fmt::Debug;
+
+use epp_client::epp::object::data::{
+    Address, AuthInfo, ContactStatus, DomainContact, DomainStatus, HostAddr, Phone, PostalInfo,
+    Status,
+};
+use epp_client::epp::object::StringValueTrait;
+use epp_client::epp::request::domain::update::DomainAddRemove;
+use epp_client::epp::response::ResultCode;
+use epp_client::epp::xml::EppXml;
+use epp_client::epp::*;
+use epp_client::error::Error;
+use epp_client::EppClient;
+
+use crate::server::MockServerHandle;
+use crate::{MockServer, Registry};
+
+const CLTRID: &str = "cltrid:registry";
+
+/// Starts a server for the registry accepting any credentials
+async fn server(registry: &Registry) -> MockServerHandle {
+    MockServer::new()
+        .handler(registry.clone())
+        .bind("127.0.0.1:0")
+        .await
+        .unwrap()
+}
+
+/// Logs in to the server as a client
+async fn session(server: &MockServerHandle, client_id: &str) -> EppClient {
+    let registry_creds = server.connection(client_id, "password");
+    EppClient::connect(client_id, &registry_creds)
+        .await
+        .unwrap()
+}
+
+/// Sends a request that the registry should reject, returning the result code
+async fn error_code<T: EppXml + Debug, E: EppXml + Debug>(
+    client: &mut EppClient,
+    request: &T,
+) -> ResultCode {
+    match client.transact::<T, E>(request).await {
+        Err(Error::EppCommandError(e)) => e.code,
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(response) => panic!("unexpected success: {:?}", response),
+    }
+}
+
+/// Creates a contact with the given ID
+async fn create_contact(client: &mut EppClient, id: &str) {
+    let address = Address::new(vec!["58"], "Paris", Some("Paris"), Some("392374"), "FR");
+    let postal_info = PostalInfo::new("int", "John Doe", Some("Acme Widgets"), address);
+    let contact_create = EppContactCreate::new(
+        id,
+        "contact@eppdev.net",
+        postal_info,
+        Phone::new("+33.47237942"),
+        "eppdev-387323",
+        CLTRID,
+    );
+
+    let response = client
+        .transact::<_, EppContactCreateResponse>(&contact_create)
+        .await
+        .unwrap();
+    assert_eq!(
+        response.data.res_data().unwrap().create_data.id,
+        id.to_string_value()
+    );
+}
+
+/// Creates a domain with the given contact for all roles and nameservers
+async fn create_domain(client: &mut EppClient, name: &str, contact: &str, ns: Vec<&str>) {
+    let contacts = ["admin", "tech", "billing"]
+        .iter()
+        .map(|contact_type| DomainContact {
+            contact_type: contact_type.to_string(),
+            id: contact.to_string(),
+        })
+        .collect();
+    let domain_create = match ns.is_empty() {
+        true => EppDomainCreate::new(name, 1, contact, "epP4uthd#v", contacts, CLTRID),
+        false => EppDomainCreate::new_with_ns(name, 1, ns, contact, "epP4uthd#v", contacts, CLTRID),
+    };
+
+    let response = client
+        .transact::<_, EppDomainCreateResponse>(&domain_create)
+        .await
+        .unwrap();
+    assert_eq!(response.data.result().unwrap().code, 1000);
+}
+
+/// Returns the info of a domain
+async fn domain_info(client: &mut EppClient, name: &str) -> EppDomainInfoResponse {
+    let domain_info = EppDomainInfo::new(name, CLTRID);
+
+    client
+        .transact::<_, EppDomainInfoResponse>(&domain_info)
+        .await
+        .unwrap()
+}
+
+/// Returns an update of a domain adding or removing a status
+fn domain_status_update(name: &str, status: DomainStatus, add: bool) -> EppDomainUpdate {
+    let statuses = DomainAddRemove {
+        ns: None,
+        contacts: None,
+        statuses: Some(vec![Status::new(status)]),
+    };
+
+    let mut domain_update = EppDomainUpdate::new(name, CLTRID);
+    match add {
+        true => domain_update.add(statuses),
+        false => domain_update.remove(statuses),
+    }
+    domain_update
+}
+
+#[tokio::test]
+async fn domain_lifecycle() {
+    let registry = Registry::new();
+    let server = server(&registry).await;
+    let mut client = session(&server, "registrar-a").await;
+
+    create_contact(&mut client, "eppdev-contact-1").await;
+    let host_create = EppHostCreate::new("ns1.eppdev.net", vec![], CLTRID);
+    client
+        .transact::<_, EppHostCreateResponse>(&host_create)
+        .await
+        .unwrap();
+
+    let domain_check = EppDomainCheck::new(vec!["eppdev.com"], CLTRID);
+    let response = client
+        .transact::<_, EppDomainCheckResponse>(&domain_check)
+        .await
+        .unwrap();
+    assert_eq!(
+        response.data.res_data().unwrap().check_data.domain_list[0]
+            .domain
+            .available,
+        1
+    );
+
+    create_domain(
+        &mut client,
+        "eppdev.com",
+        "eppdev-contact-1",
+        vec!["ns1.eppdev.net"],
+    )
+    .await;
+
+    let response = client
+        .transact::<_, EppDomainCheckResponse>(&domain_check)
+        .await
+        .unwrap();
+    let result = &response.data.res_data().unwrap().check_data.domain_list[0];
+    assert_eq!(result.domain.available, 0);
+    assert_eq!(result.reason, Some("In use".to_string_value()));
+
+    let response = domain_info(&mut client, "eppdev.com").await;
+    let info = &response.data.res_data().unwrap().info_data;
+    assert_eq!(info.client_id, "registrar-a".to_string_value());
+    assert_eq!(info.registrant, "eppdev-contact-1".to_string_value());
+    assert_eq!(info.contacts.len(), 3);
+    assert_eq!(info.statuses[0].status, DomainStatus::Ok);
+    assert_eq!(
+        info.ns.as_ref().unwrap().host_obj.as_ref().unwrap()[0],
+        "ns1.eppdev.net".to_string_value()
+    );
+    assert_eq!(
+        info.auth_info.as_ref().unwrap().password(),
+        Some(&"epP4uthd#v".to_string_value())
+    );
+
+    let expiry_date = info.expiring_at.date_naive();
+
+    // Creating the domain again fails
+    let domain_create = EppDomainCreate::new_without_contacts("eppdev.com", 1, "pw", CLTRID);
+    let code = error_code::<_, EppDomainCreateResponse>(&mut client, &domain_create).await;
+    assert_eq!(code, 2302);
+
+    // Deletion is prohibited until the status is removed
+    let domain_update =
+        domain_status_update("eppdev.com", DomainStatus::ClientDeleteProhibited, true);
+    client
+        .transact::<_, EppDomainUpdateResponse>(&domain_update)
+        .await
+        .unwrap();
+
+    let domain_delete = EppDomainDelete::new("eppdev.com", CLTRID);
+    let code = error_code::<_, EppDomainDeleteResponse>(&mut client, &domain_delete).await;
+    assert_eq!(code, 2304);
+
+    let domain_update =
+        domain_status_update("eppdev.com", DomainStatus::ClientDeleteProhibited, false);
+    client
+        .transact::<_, EppDomainUpdateResponse>(&domain_update)
+        .await
+        .unwrap();
+
+    // Renewals need the current expiry date
+    let wrong_date = expiry_date.succ_opt().unwrap();
+    let domain_renew = EppDomainRenew::new("eppdev.com", wrong_date, 1, CLTRID);
+    let code = error_code::<_, EppDomainRenewResponse>(&mut client, &domain_renew).await;
+    assert_eq!(code, 2004);
+
+    let domain_renew = EppDomainRenew::new("eppdev.com", expiry_date, 2, CLTRID);
+    let response = client
+        .transact::<_, EppDomainRenewResponse>(&domain_renew)
+        .await
+        .unwrap();
+    let expiring_at = &response.data.res_data().unwrap().renew_data.expiring_at;
+    assert!(expiring_at.to_string().starts_with(
+        &expiry_date
+            .with_year(expiry_date.year() + 2)
+            .unwrap()
+            .format("%Y-%m-%d")
+            .to_string()
+    ));
+
+    // The host and contact are in use until the domain is deleted
+    let host_delete = EppHostDelete::new("ns1.eppdev.net", CLTRID);
+    let code = error_code::<_, EppHostDeleteResponse>(&mut client, &host_delete).await;
+    assert_eq!(code, 2305);
+
+    client
+        .transact::<_, EppDomainDeleteResponse>(&domain_delete)
+        .await
+        .unwrap();
+
+    let domain_info = EppDomainInfo::new("eppdev.com", CLTRID);
+    let code = error_code::<_, EppDomainInfoResponse>(&mut client, &domain_info).await;
+    assert_eq!(code, 2303);
+
+    client
+        .transact::<_, EppHostDeleteResponse>(&host_delete)
+        .await
+        .unwrap();
+    let contact_delete = EppContactDelete::new("eppdev-contact-1", CLTRID);
+    client
+        .transact::<_, EppContactDeleteResponse>(&contact_delete)
+        .await
+        .unwrap();
+
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn objects_of_other_clients() {
+    let registry = Registry::new();
+    let server = server(&registry).await;
+    let mut client_a = session(&server, "registrar-a").await;
+    let mut client_b = session(&server, "registrar-b").await;
+
+    create_contact(&mut client_a, "eppdev-contact-1").await;
+    create_domain(&mut client_a, "eppdev.com", "eppdev-contact-1", vec![]).await;
+
+    // Other clients see the domain without its authInfo
+    let response = domain_info(&mut client_b, "eppdev.com").await;
+    assert!(response
+        .data
+        .res_data()
+        .unwrap()
+        .info_data
+        .auth_info
+        .is_none());
+
+    // Clients giving the authInfo see the full domain data
+    let mut domain_info = EppDomainInfo::new("eppdev.com", CLTRID);
+    domain_info.set_auth_info(AuthInfo::new("epP4uthd#v"));
+    let response = client_b
+        .transact::<_, EppDomainInfoResponse>(&domain_info)
+        .await
+        .unwrap();
+    let info = &response.data.res_data().unwrap().info_data;
+    assert!(info.auth_info.is_some());
+
+    let domain_update = domain_status_update("eppdev.com", DomainStatus::ClientHold, true);
+    let code = error_code::<_, EppDomainUpdateResponse>(&mut client_b, &domain_update).await;
+    assert_eq!(code, 2201);
+
+    let domain_delete = EppDomainDelete::new("eppdev.com", CLTRID);
+    let code = error_code::<_, EppDomainDeleteResponse>(&mut client_b, &domain_delete).await;
+    assert_eq!(code, 2201);
+
+    let contact_info = EppContactInfo::new("eppdev-contact-1", "wrong", CLTRID);
+    let code = error_code::<_, EppContactInfoResponse>(&mut client_b, &contact_info).await;
+    assert_eq!(code, 2201);
+
+    let contact_info = EppContactInfo::new("eppdev-contact-1", "eppdev-387323", CLTRID);
+    let response = client_b
+        .transact::<_, EppContactInfoResponse>(&contact_info)
+        .await
+        .unwrap();
+    let info = &response.data.res_data().unwrap().info_data;
+    assert_eq!(info.client_id, "registrar-a".to_string_value());
+    assert_eq!(info.statuses[0].status, ContactStatus::Linked);
+
+    // Hosts subordinate to the domain belong to its sponsor
+    let addresses = vec![HostAddr::new_v4("29.245.122.14".parse().unwrap())];
+    let host_create = EppHostCreate::new("ns1.eppdev.com", addresses, CLTRID);
+    let code = error_code::<_, EppHostCreateResponse>(&mut client_b, &host_create).await;
+    assert_eq!(code, 2201);
+
+    let host_create = EppHostCreate::new("ns1.eppdev.com", vec![], CLTRID);
+    let code = error_code::<_, EppHostCreateResponse>(&mut client_a, &host_create).await;
+    assert_eq!(code, 2003);
+
+    // Clients may only set client statuses
+    let mut contact_update = EppContactUpdate::new("eppdev-contact-1", CLTRID);
+    contact_update.add(vec![Status::new(ContactStatus::ServerDeleteProhibited)]);
+    let code = error_code::<_, EppContactUpdateResponse>(&mut client_a, &contact_update).await;
+    assert_eq!(code, 2306);
+
+    client_a.close().await.unwrap();
+    client_b.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn null_auth_info() {
+    let registry = Registry::new();
+    let server = server(&registry).await;
+    let mut client_a = session(&server, "registrar-a").await;
+    let mut client_b = session(&server, "registrar-b").await;
+
+    create_contact(&mut client_a, "eppdev-contact-1").await;
+    create_domain(&mut client_a, "eppdev.com", "eppdev-contact-1", vec![]).await;
+
+    // Remove the authInfo of the domain and the contact
+    let mut domain_update = EppDomainUpdate::new("eppdev.com", CLTRID);
+    domain_update.info(DomainChangeInfo {
+        registrant: None,
+        auth_info: Some(AuthInfo::null()),
+    });
+    client_a
+        .transact::<_, EppDomainUpdateResponse>(&domain_update)
+        .await
+        .unwrap();
+
+    let mut contact_update = EppContactUpdate::new("eppdev-contact-1", CLTRID);
+    contact_update.data.command.contact.change_info = Some(ContactChangeInfo {
+        auth_info: Some(AuthInfo::null()),
+        ..ContactChangeInfo::default()
+    });
+    client_a
+        .transact::<_, EppContactUpdateResponse>(&contact_update)
+        .await
+        .unwrap();
+
+    let response = domain_info(&mut client_a, "eppdev.com").await;
+    let info = &response.data.res_data().unwrap().info_data;
+    assert!(info.auth_info.is_none());
+
+    // An empty password does not match the removed authInfo
+    let mut domain_info = EppDomainInfo::new("eppdev.com", CLTRID);
+    domain_info.set_auth_info(AuthInfo::new(""));
+    let response = client_b
+        .transact::<_, EppDomainInfoResponse>(&domain_info)
+        .await
+        .unwrap();
+    let info = &response.data.res_data().unwrap().info_data;
+    assert!(info.auth_info.is_none());
+
+    let transfer_request = EppDomainTransferRequest::request("eppdev.com", 1, "", CLTRID);
+    let code =
+        error_code::<_, EppDomainTransferRequestResponse>(&mut client_b, &transfer_request).await;
+    assert_eq!(code, 2202);
+
+    let contact_info = EppContactInfo::new("eppdev-contact-1", "", CLTRID);
+    let code = error_code::<_, EppContactInfoResponse>(&mut client_b, &contact_info).await;
+    assert_eq!(code, 2201);
+
+    client_a.close().await.unwrap();
+    client_b.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn domain_transfer() {
+    let registry = Registry::new();
+    let server = server(&registry).await;
+    let mut client_a = session(&server, "registrar-a").await;
+    let mut client_b = session(&server, "registrar-b").await;
+
+    create_contact(&mut client_a, "eppdev-contact-1").await;
+    create_domain(&mut client_a, "eppdev.com", "eppdev-contact-1", vec![]).await;
+
+    let transfer_request = EppDomainTransferRequest::request("eppdev.com", 1, "wrong", CLTRID);
+    let code =
+        error_code::<_, EppDomainTransferRequestResponse>(&mut client_b, &transfer_request).await;
+    assert_eq!(code, 2202);
+
+    let transfer_request = EppDomainTransferRequest::request("eppdev.com", 1, "epP4uthd#v", CLTRID);
+    let code =
+        error_code::<_, EppDomainTransferRequestResponse>(&mut client_a, &transfer_request).await;
+    assert_eq!(code, 2106);
+
+    let response = client_b
+        .transact::<_, EppDomainTransferRequestResponse>(&transfer_request)
+        .await
+        .unwrap();
+    assert_eq!(response.data.result().unwrap().code, 1001);
+    let transfer = &response.data.res_data().unwrap().transfer_data;
+    assert_eq!(transfer.transfer_status, "pending".to_string_value());
+    assert_eq!(transfer.requester_id, "registrar-b".to_string_value());
+    assert_eq!(transfer.ack_id, "registrar-a".to_string_value());
+
+    let code =
+        error_code::<_, EppDomainTransferRequestResponse>(&mut client_b, &transfer_request).await;
+    assert_eq!(code, 2300);
+
+    // The pending transfer prohibits updates
+    let domain_update = domain_status_update("eppdev.com", DomainStatus::ClientHold, true);
+    let code = error_code::<_, EppDomainUpdateResponse>(&mut client_a, &domain_update).await;
+    assert_eq!(code, 2304);
+
+    // The sponsor is notified of the request
+    let message_poll = EppMessagePoll::new(CLTRID);
+    let response = client_a
+        .transact::<_, EppMessagePollResponse>(&message_poll)
+        .await
+        .unwrap();
+    assert_eq!(response.data.result().unwrap().code, 1301);
+    let queue = response.data.message_queue().unwrap();
+    assert_eq!(queue.count, 1);
+    assert_eq!(queue.message, Some("Transfer requested".to_string_value()));
+    let message_data = &response.data.res_data().unwrap().message_data;
+    assert_eq!(message_data.name, "eppdev.com".to_string_value());
+    assert_eq!(message_data.transfer_status, "pending".to_string_value());
+
+    let message_ack = EppMessageAck::new(queue.id.parse().unwrap(), CLTRID);
+    let response = client_a
+        .transact::<_, EppMessageAckResponse>(&message_ack)
+        .await
+        .unwrap();
+    assert_eq!(response.data.result().unwrap().code, 1000);
+    assert!(response.data.message_queue().is_none());
+
+    let code = error_code::<_, EppMessageAckResponse>(&mut client_a, &message_ack).await;
+    assert_eq!(code, 2303);
+
+    let response = client_a
+        .transact::<_, EppMessagePollResponse>(&message_poll)
+        .await
+        .unwrap();
+    assert_eq!(response.data.result().unwrap().code, 1300);
+
+    // Only the sponsor approves the transfer
+    let transfer_approve = EppDomainTransferApprove::approve("eppdev.com", CLTRID);
+    let code =
+        error_code::<_, EppDomainTransferApproveResponse>(&mut client_b, &transfer_approve).await;
+    assert_eq!(code, 2201);
+
+    client_a
+        .transact::<_, EppDomainTransferApproveResponse>(&transfer_approve)
+        .await
+        .unwrap();
+
+    let code =
+        error_code::<_, EppDomainTransferApproveResponse>(&mut client_a, &transfer_approve).await;
+    assert_eq!(code, 2301);
+
+    // The requester is notified and now sponsors the domain
+    let response = client_b
+        .transact::<_, EppMessagePollResponse>(&message_poll)
+        .await
+        .unwrap();
+    let message_data = &response.data.res_data().unwrap().message_data;
+    assert_eq!(
+        message_data.transfer_status,
+        "clientApproved".to_string_value()
+    );
+
+    let response = domain_info(&mut client_b, "eppdev.com").await;
+    let info = &response.data.res_data().unwrap().info_data;
+    assert_eq!(info.client_id, "registrar-b".to_string_value());
+    assert!(info.transferred_at.is_some());
+
+    let transfer_query = EppDomainTransferQuery::query("eppdev.com", "", CLTRID);
+    let response = client_b
+        .transact::<_, EppDomainTransferQueryResponse>(&transfer_query)
+        .await
+        .unwrap();
+    let transfer = &response.data.res_data().unwrap().transfer_data;
+    assert_eq!(transfer.transfer_status, "clientApproved".to_string_value());
+
+    client_a.close().await.unwrap();
+    client_b.close().await.unwrap();
+}
@@ -0,0 +1,60 @@
+//! Request parsing tests
+
+use super::get_request;
+use crate::request::MockRequest;
+
+#[test]
+fn login() {
+    let request = MockRequest::parse(&get_request("login.xml")).unwrap();
+
+    assert_eq!(request.command, "login");
+    assert_eq!(request.op, None);
+    assert_eq!(request.client_tr_id.as_deref(), Some("cltrid:1626454866"));
+    assert_eq!(request.value("clID").as_deref(), Some("username"));
+    assert_eq!(request.value("pw").as_deref(), Some("password"));
+}
+
+#[test]
+fn hello() {
+    let request = MockRequest::parse(&get_request("hello.xml")).unwrap();
+
+    assert_eq!(request.command, "hello");
+    assert_eq!(request.client_tr_id, None);
+}
+
+#[test]
+fn object_command() {
+    let request = MockRequest::parse(&get_request("domain/check.xml")).unwrap();
+
+    assert_eq!(request.command, "domain:check");
+    assert_eq!(request.object(), Some("domain"));
+    assert_eq!(
+        request.values("name"),
+        vec!["eppdev.com".to_string(), "eppdev.net".to_string()]
+    );
+}
+
+#[test]
+fn op() {
+    let request = MockRequest::parse(&get_request("domain/transfer_query.xml")).unwrap();
+    assert_eq!(request.command, "domain:transfer");
+    assert_eq!(request.op.as_deref(), Some("query"));
+
+    let request = MockRequest::parse(&get_request("message/ack.xml")).unwrap();
+    assert_eq!(request.command, "poll");
+    assert_eq!(request.op.as_deref(), Some("ack"));
+}
+
+#[test]
+fn extension() {
+    let request = MockRequest::parse(&get_request("domain/rgp_restore_request.xml")).unwrap();
+
+    assert_eq!(request.command, "domain:update");
+    assert_eq!(request.extensions, vec!["rgp:update".to_string()]);
+}
+
+#[test]
+fn invalid() {
+    assert!(MockRequest::parse("<epp><response/></epp>").is_err());
+    assert!(MockRequest::parse("<epp><command>").is_err());
+}
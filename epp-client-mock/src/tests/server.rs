@@ -0,0 +1,204 @@
+//! `EppClient` lifecycle tests against the mock server
+
+use tokio::io::duplex;
+
+use epp_client::connection::registry::ConnectionStream;
+use epp_client::epp::object::StringValueTrait;
+use epp_client::epp::{EppDomainCheck, EppDomainCheckResponse, EppHostInfo, EppHostInfoResponse};
+use epp_client::error::Error;
+use epp_client::EppClient;
+
+use crate::request::MockRequest;
+use crate::response::response;
+use crate::MockServer;
+
+const CLTRID: &str = "cltrid:mock";
+
+/// Checks two domains on the client's session and returns the response
+async fn check_domain(client: &mut EppClient) -> EppDomainCheckResponse {
+    let domain_check = EppDomainCheck::new(vec!["eppdev.com", "eppdev.net"], CLTRID);
+
+    client
+        .transact::<_, EppDomainCheckResponse>(&domain_check)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn lifecycle() {
+    let server = MockServer::new()
+        .credentials("username", "password")
+        .fixtures()
+        .bind("127.0.0.1:0")
+        .await
+        .unwrap();
+
+    let registry_creds = server.connection("username", "password");
+    let mut client = EppClient::connect("mock", &registry_creds).await.unwrap();
+
+    let greeting = client.hello().await.unwrap();
+    assert_eq!(greeting.data.service_id, "ISPAPI EPP Server");
+
+    let response = check_domain(&mut client).await;
+    assert_eq!(response.data.result().unwrap().code, 1000);
+    assert_eq!(
+        response.data.tr_ids.client_tr_id.unwrap(),
+        CLTRID.to_string_value()
+    );
+
+    let response = client.close().await.unwrap();
+    assert_eq!(response.data.result().unwrap().code, 1500);
+}
+
+#[tokio::test]
+async fn tls() {
+    let server = MockServer::new()
+        .fixtures()
+        .tls("localhost")
+        .bind("127.0.0.1:0")
+        .await
+        .unwrap();
+    assert!(server.root_cert().is_some());
+
+    let registry_creds = server.connection("username", "password");
+    let mut client = EppClient::connect("mock", &registry_creds).await.unwrap();
+
+    assert_eq!(
+        check_domain(&mut client).await.data.result().unwrap().code,
+        1000
+    );
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn login_rejected() {
+    let server = MockServer::new()
+        .credentials("username", "password")
+        .bind("127.0.0.1:0")
+        .await
+        .unwrap();
+
+    let registry_creds = server.connection("username", "wrong");
+
+    match EppClient::connect("mock", &registry_creds).await {
+        Err(Error::EppLoginError(e)) => assert_eq!(e.code, 2200),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("login succeeded"),
+    }
+}
+
+#[tokio::test]
+async fn password_change() {
+    let server = MockServer::new()
+        .credentials("username", "password")
+        .bind("127.0.0.1:0")
+        .await
+        .unwrap();
+
+    let mut registry_creds = server.connection("username", "password");
+    registry_creds.set_new_password("new password");
+
+    let client = EppClient::connect("mock", &registry_creds).await.unwrap();
+    let updated_creds = client.registry_creds().clone();
+    assert_eq!(updated_creds.credentials().1, "new password");
+    assert_eq!(updated_creds.new_password(), None);
+    client.close().await.unwrap();
+
+    // The old password is no longer accepted, the updated connection details log in again
+    match EppClient::connect("mock", &server.connection("username", "password")).await {
+        Err(Error::EppLoginError(e)) => assert_eq!(e.code, 2200),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("login succeeded"),
+    }
+
+    let client = EppClient::connect("mock", &updated_creds).await.unwrap();
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn scripted_handlers() {
+    let server = MockServer::new()
+        .handler(|request: &MockRequest| {
+            assert_eq!(request.client_id.as_deref(), Some("username"));
+            match request.values("name").contains(&"taken.com".to_string()) {
+                true => Some(response(2302, None, request.client_tr_id.as_deref())),
+                false => None,
+            }
+        })
+        .fixtures()
+        .bind("127.0.0.1:0")
+        .await
+        .unwrap();
+
+    let registry_creds = server.connection("username", "password");
+    let mut client = EppClient::connect("mock", &registry_creds).await.unwrap();
+
+    assert_eq!(
+        check_domain(&mut client).await.data.result().unwrap().code,
+        1000
+    );
+
+    let domain_check = EppDomainCheck::new(vec!["taken.com"], CLTRID);
+    let result = client
+        .transact::<_, EppDomainCheckResponse>(&domain_check)
+        .await;
+    match result {
+        Err(Error::EppCommandError(e)) => assert_eq!(e.code, 2302),
+        _ => panic!("expected EppCommandError"),
+    }
+
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn unimplemented_command() {
+    let server = MockServer::new()
+        .respond("domain:check", &response(1000, None, None))
+        .bind("127.0.0.1:0")
+        .await
+        .unwrap();
+
+    let registry_creds = server.connection("username", "password");
+    let mut client = EppClient::connect("mock", &registry_creds).await.unwrap();
+    assert_eq!(
+        check_domain(&mut client).await.data.result().unwrap().code,
+        1000
+    );
+
+    let host_info = EppHostInfo::new("ns1.eppdev.com", CLTRID);
+    let result = client.transact::<_, EppHostInfoResponse>(&host_info).await;
+    match result {
+        Err(Error::EppCommandError(e)) => assert_eq!(e.code, 2101),
+        _ => panic!("expected EppCommandError"),
+    }
+
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn in_memory() {
+    let (client_stream, server_stream) = duplex(4096);
+
+    let server = tokio::spawn(async move {
+        MockServer::new()
+            .fixtures()
+            .serve(server_stream)
+            .await
+            .unwrap();
+    });
+
+    let mut client = EppClient::builder()
+        .host("localhost", 700)
+        .credentials("username", "password")
+        .connect_with_stream(ConnectionStream::new(client_stream))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        check_domain(&mut client).await.data.result().unwrap().code,
+        1000
+    );
+    client.close().await.unwrap();
+
+    server.await.unwrap();
+}
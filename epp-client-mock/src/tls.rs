@@ -0,0 +1,26 @@
+//! Generates a self-signed certificate for serving the mock server over TLS
+
+use rustls::{Certificate, NoClientAuth, PrivateKey, ServerConfig};
+use std::sync::Arc;
+use tokio_rustls::TlsAcceptor;
+
+use epp_client::error;
+
+/// Generates a self-signed certificate for the server name and returns an acceptor serving it
+/// along with the certificate, which clients need to trust to connect
+pub fn generate(server_name: &str) -> Result<(TlsAcceptor, Certificate), error::Error> {
+    let generated = rcgen::generate_simple_self_signed(vec![server_name.to_string()])
+        .map_err(|e| error::Error::EppTlsError(format!("Failed to generate certificate: {}", e)))?;
+
+    let certificate = generated.serialize_der().map(Certificate).map_err(|e| {
+        error::Error::EppTlsError(format!("Failed to serialize certificate: {}", e))
+    })?;
+    let key = PrivateKey(generated.serialize_private_key_der());
+
+    let mut config = ServerConfig::new(NoClientAuth::new());
+    config
+        .set_single_cert(vec![certificate.clone()], key)
+        .map_err(|e| error::Error::EppTlsError(format!("Invalid certificate: {}", e)))?;
+
+    Ok((TlsAcceptor::from(Arc::new(config)), certificate))
+}
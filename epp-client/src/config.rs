@@ -1,12 +1,14 @@
 //! Config load module
 //!
-//! Loads the configuration and credentials for each registry connection from
-//! the `$XDG_CONFIG_HOME/epp-client/epp-client.toml` file
+//! Holds the configuration and credentials for each registry connection. With the
+//! `config-file` feature (enabled by default), these are loaded from the
+//! `$XDG_CONFIG_HOME/epp-client/epp-client.toml` file. Without it, connection details
+//! are set up in code with [`EppClient::builder`](../connection/client/struct.EppClient.html#method.builder)
 //!
 //! ## Usage
 //!
-//! The config is automatically loaded when the module is initialized
-//! and is available through the `epp_client::config::CONFIG` variable
+//! The config is loaded with `EppClientConfig::load()`, or automatically when the module
+//! is initialized and is available through the `epp_client::config::CONFIG` variable
 //!
 //! ## Sample config
 //!
@@ -18,12 +20,47 @@
 //! password = 'password'
 //! # service extensions
 //! ext_uris = []
+//! # only request the objects and extensions the greeting announces (optional, defaults to false)
+//! negotiate = true
+//! # extensions the registry must announce, e.g. for DNSSEC workflows (optional)
+//! required_ext_uris = ['urn:ietf:params:xml:ns:secDNS-1.1']
+//! # maximum number of concurrent sessions allowed by the registry (optional)
+//! max_sessions = 4
+//! # maximum size in bytes of a response from the registry (optional, defaults to 4 MiB)
+//! max_frame_size = 4194304
+//! # set to false to connect over plain TCP, e.g. to a local stunnel (optional, defaults to true)
+//! tls = true
+//! # PEM file with certificates to trust, e.g. a private CA for a test environment (optional)
+//! ca_file = '/path/to/ca/pemfile'
+//! # set to false to only trust the certificates in ca_file (optional, defaults to true)
+//! bundled_roots = true
+//! # base64 SHA-256 hashes of the accepted server public keys (optional)
+//! spki_pins = ['47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=']
+//! # name to verify the server certificate against, if it differs from host (optional)
+//! server_name = 'epp.verisign-grs.com'
 //!
 //! [registry.hexonet.tls_files]
 //! # the full client certificate chain in PEM format
 //! cert_chain = '/path/to/certificate/chain/pemfile'
-//! # the RSA private key for your certificate
+//! # the private key for your certificate, in RSA, PKCS#8 or SEC1 EC format
 //! key = '/path/to/private/key/pemfile'
+//!
+//! # reconnect and log in again when the registry closes the session (optional)
+//! [registry.verisign.reconnect]
+//! # connection attempts before giving up
+//! max_attempts = 3
+//! # delay before the first retry in milliseconds, doubled after each failed attempt
+//! backoff = 1000
+//! max_backoff = 30000
+//! # times to resend a check, info or poll command interrupted by a closed session
+//! max_retries = 1
+//!
+//! # deadlines in milliseconds, no deadline is applied when a setting is omitted (optional)
+//! [registry.verisign.timeouts]
+//! connect = 10000
+//! handshake = 10000
+//! greeting = 10000
+//! response = 30000
 //! ```
 //!
 //! ## Example
@@ -49,39 +86,124 @@
 //! }
 //! ```
 
-use confy;
+#[cfg(feature = "config-file")]
 use lazy_static::lazy_static;
 use rustls::{Certificate, PrivateKey};
-use rustls_pemfile;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::default;
-use std::{fs, io};
+use std::fs;
+use std::time::Duration;
+
+use crate::connection::codec::EppCodec;
+use crate::connection::tls::{certs_from_pem, decode_spki_pin, private_key_from_pem};
+use crate::connection::transcript::TranscriptRecorder;
+use crate::error;
 
+#[cfg(feature = "config-file")]
 lazy_static! {
-    /// Static reference to the config file
-    pub static ref CONFIG: EppClientConfig = match confy::load("epp-client") {
+    /// Static reference to the config file. Panics on first use if the file cannot be read,
+    /// use `EppClientConfig::load()` to handle errors instead
+    pub static ref CONFIG: EppClientConfig = match EppClientConfig::load() {
         Ok(cfg) => cfg,
         Err(e) => panic!("Config read error: {}", e),
     };
 }
 
 /// Paths to the client certificate and client key PEM files
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct EppClientTlsFiles {
     cert_chain: String,
     key: String,
 }
 
+/// Settings for reconnecting to a registry after it has closed the session
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct EppClientReconnectPolicy {
+    /// The number of attempts to connect and log in again before giving up
+    pub max_attempts: u32,
+    /// The delay in milliseconds before the first retry, doubled after each failed attempt
+    pub backoff: u64,
+    /// The upper limit in milliseconds for the delay between attempts
+    pub max_backoff: u64,
+    /// The number of times an idempotent command (hello, check, info, poll request or
+    /// transfer query) is resent after the session was closed while it was being executed
+    pub max_retries: u32,
+}
+
+impl default::Default for EppClientReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: 1000,
+            max_backoff: 30000,
+            max_retries: 1,
+        }
+    }
+}
+
+/// Deadlines in milliseconds for the stages of connecting to and transacting with a registry
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct EppClientTimeouts {
+    /// Resolving the registry host and establishing the TCP connection
+    pub connect: Option<u64>,
+    /// The TLS handshake
+    pub handshake: Option<u64>,
+    /// Receiving the greeting after the connection is established
+    pub greeting: Option<u64>,
+    /// Receiving the response to each command
+    pub response: Option<u64>,
+}
+
+impl EppClientTimeouts {
+    /// Returns the connect timeout
+    pub fn connect(&self) -> Option<Duration> {
+        self.connect.map(Duration::from_millis)
+    }
+    /// Returns the TLS handshake timeout
+    pub fn handshake(&self) -> Option<Duration> {
+        self.handshake.map(Duration::from_millis)
+    }
+    /// Returns the greeting timeout
+    pub fn greeting(&self) -> Option<Duration> {
+        self.greeting.map(Duration::from_millis)
+    }
+    /// Returns the command response timeout
+    pub fn response(&self) -> Option<Duration> {
+        self.response.map(Duration::from_millis)
+    }
+}
+
 /// Connection details to connect to and authenticate with a registry
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct EppClientConnection {
     host: String,
     port: u16,
     username: String,
     password: String,
+    #[serde(skip)]
+    new_password: Option<String>,
     ext_uris: Option<Vec<String>>,
+    negotiate: Option<bool>,
+    required_ext_uris: Option<Vec<String>>,
     tls_files: Option<EppClientTlsFiles>,
+    max_sessions: Option<usize>,
+    reconnect: Option<EppClientReconnectPolicy>,
+    timeouts: Option<EppClientTimeouts>,
+    max_frame_size: Option<usize>,
+    tls: Option<bool>,
+    ca_file: Option<String>,
+    bundled_roots: Option<bool>,
+    spki_pins: Option<Vec<String>>,
+    server_name: Option<String>,
+    #[serde(skip)]
+    tls_identity: Option<(Vec<Certificate>, PrivateKey)>,
+    #[serde(skip)]
+    root_certs: Vec<Certificate>,
+    #[serde(skip)]
+    recorder: Option<TranscriptRecorder>,
 }
 
 /// Config that stores settings for multiple registries
@@ -98,11 +220,26 @@ impl default::Default for EppClientConfig {
             port: 700,
             username: "username".to_string(),
             password: "password".to_string(),
+            new_password: None,
             ext_uris: Some(vec![]),
+            negotiate: None,
+            required_ext_uris: None,
             tls_files: Some(EppClientTlsFiles {
                 cert_chain: "/path/to/certificate/chain/pemfile".to_string(),
                 key: "/path/to/private/key/pemfile".to_string(),
             }),
+            max_sessions: None,
+            reconnect: None,
+            timeouts: None,
+            max_frame_size: None,
+            tls: None,
+            ca_file: None,
+            bundled_roots: None,
+            spki_pins: None,
+            server_name: None,
+            tls_identity: None,
+            root_certs: Vec::new(),
+            recorder: None,
         };
         registries.insert("verisign".to_string(), registrar);
         Self {
@@ -112,6 +249,132 @@ impl default::Default for EppClientConfig {
 }
 
 impl EppClientConnection {
+    /// Creates connection details for a registry with the given host, port and credentials
+    pub fn new(host: &str, port: u16, username: &str, password: &str) -> EppClientConnection {
+        EppClientConnection {
+            host: host.to_string(),
+            port,
+            username: username.to_string(),
+            password: password.to_string(),
+            new_password: None,
+            ext_uris: None,
+            negotiate: None,
+            required_ext_uris: None,
+            tls_files: None,
+            max_sessions: None,
+            reconnect: None,
+            timeouts: None,
+            max_frame_size: None,
+            tls: None,
+            ca_file: None,
+            bundled_roots: None,
+            spki_pins: None,
+            server_name: None,
+            tls_identity: None,
+            root_certs: Vec::new(),
+            recorder: None,
+        }
+    }
+    /// Sets a new password to change the password to on the next login. Once the registry
+    /// accepts the login, the new password replaces the current one
+    pub fn set_new_password(&mut self, new_password: &str) {
+        self.new_password = Some(new_password.to_string());
+    }
+    /// Replaces the password with the new password after the registry accepted the change
+    pub(crate) fn password_changed(&mut self) {
+        if let Some(new_password) = self.new_password.take() {
+            self.password = new_password;
+        }
+    }
+    /// Sets the service extension URIs to be set in the connection to the registry
+    pub fn set_ext_uris(&mut self, ext_uris: Vec<String>) {
+        self.ext_uris = Some(ext_uris);
+    }
+    /// Sets whether the objects and extensions requested at login are limited to those the
+    /// registry announces in its greeting
+    pub fn set_negotiate(&mut self, negotiate: bool) {
+        self.negotiate = Some(negotiate);
+    }
+    /// Sets the service extension URIs the registry must announce in its greeting. These are
+    /// requested at login along with the other extensions
+    pub fn set_required_ext_uris(&mut self, required_ext_uris: Vec<String>) {
+        self.required_ext_uris = Some(required_ext_uris);
+    }
+    /// Sets the paths to the client certificate chain and private key PEM files
+    pub fn set_tls_files(&mut self, cert_chain: &str, key: &str) {
+        self.tls_files = Some(EppClientTlsFiles {
+            cert_chain: cert_chain.to_string(),
+            key: key.to_string(),
+        });
+    }
+    /// Sets the parsed client certificate chain and private key, which take precedence over
+    /// the TLS files
+    pub fn set_tls_identity(&mut self, cert_chain: Vec<Certificate>, key: PrivateKey) {
+        self.tls_identity = Some((cert_chain, key));
+    }
+    /// Sets the client certificate chain and private key from PEM encoded data, e.g. when they
+    /// are kept in a secrets manager rather than in files. The key can be an RSA, PKCS#8 or
+    /// SEC1 EC key
+    pub fn set_tls_pem(&mut self, cert_chain: &[u8], key: &[u8]) -> Result<(), error::Error> {
+        self.tls_identity = Some((certs_from_pem(cert_chain)?, private_key_from_pem(key)?));
+        Ok(())
+    }
+    /// Sets certificates to trust in addition to the bundled webpki roots
+    pub fn set_root_certs(&mut self, root_certs: Vec<Certificate>) {
+        self.root_certs = root_certs;
+    }
+    /// Adds the PEM encoded certificates to the certificates to trust, e.g. the private CA of
+    /// a registry's test environment
+    pub fn add_root_certs_pem(&mut self, pem: &[u8]) -> Result<(), error::Error> {
+        self.root_certs.extend(certs_from_pem(pem)?);
+        Ok(())
+    }
+    /// Sets the path to a PEM file with certificates to trust in addition to the bundled
+    /// webpki roots
+    pub fn set_ca_file(&mut self, ca_file: &str) {
+        self.ca_file = Some(ca_file.to_string());
+    }
+    /// Sets whether the bundled webpki roots are trusted, which is the default. When disabled,
+    /// only the configured root certificates are trusted
+    pub fn set_bundled_roots(&mut self, bundled_roots: bool) {
+        self.bundled_roots = Some(bundled_roots);
+    }
+    /// Sets the base64 encoded SHA-256 hashes of the server public keys (SPKI) to accept.
+    /// When set, the server certificate must match one of the pins in addition to being
+    /// issued by a trusted root
+    pub fn set_spki_pins(&mut self, spki_pins: Vec<String>) {
+        self.spki_pins = Some(spki_pins);
+    }
+    /// Sets the name to verify the server certificate against and send in the SNI extension,
+    /// e.g. when connecting to the registry by IP address
+    pub fn set_server_name(&mut self, server_name: &str) {
+        self.server_name = Some(server_name.to_string());
+    }
+    /// Sets the maximum number of concurrent sessions the registry allows for this account
+    pub fn set_max_sessions(&mut self, max_sessions: usize) {
+        self.max_sessions = Some(max_sessions);
+    }
+    /// Sets the policy for reconnecting when the registry closes the session
+    pub fn set_reconnect(&mut self, reconnect: EppClientReconnectPolicy) {
+        self.reconnect = Some(reconnect);
+    }
+    /// Sets the timeouts for connecting to and transacting with the registry
+    pub fn set_timeouts(&mut self, timeouts: EppClientTimeouts) {
+        self.timeouts = Some(timeouts);
+    }
+    /// Sets the maximum size in bytes of a frame received from the registry
+    pub fn set_max_frame_size(&mut self, max_frame_size: usize) {
+        self.max_frame_size = Some(max_frame_size);
+    }
+    /// Sets whether the connection is secured with TLS. Disabling TLS is only meant for
+    /// connecting to a local TLS proxy such as stunnel, or to a test server
+    pub fn set_tls(&mut self, tls: bool) {
+        self.tls = Some(tls);
+    }
+    /// Sets a recorder that captures the sessions with the registry into a transcript
+    pub fn set_recorder(&mut self, recorder: TranscriptRecorder) {
+        self.recorder = Some(recorder);
+    }
     /// Returns the EPP host and port no as a tuple
     pub fn connection_details(&self) -> (String, u16) {
         (self.host.to_string(), self.port)
@@ -120,52 +383,118 @@ impl EppClientConnection {
     pub fn credentials(&self) -> (String, String) {
         (self.username.to_string(), self.password.to_string())
     }
+    /// Returns the new password the password is changed to on the next login, if any
+    pub fn new_password(&self) -> Option<&str> {
+        self.new_password.as_deref()
+    }
     /// Returns the service extension URIs to be set in the connection to the registry
     pub fn ext_uris(&self) -> Option<&Vec<String>> {
         self.ext_uris.as_ref()
     }
-    /// Returns the parsed client certificate and private key for client TLS auth
-    pub fn tls_files(&self) -> Option<(Vec<Certificate>, PrivateKey)> {
-        let certificates = self.client_certificate();
-        let key = self.key();
-
-        if certificates == None || key == None {
-            None
-        } else {
-            Some((certificates.unwrap(), key.unwrap()))
+    /// Returns whether the objects and extensions requested at login are limited to those the
+    /// registry announces in its greeting
+    pub fn negotiate(&self) -> bool {
+        self.negotiate.unwrap_or(false)
+    }
+    /// Returns the service extension URIs the registry must announce in its greeting
+    pub fn required_ext_uris(&self) -> &[String] {
+        self.required_ext_uris.as_deref().unwrap_or_default()
+    }
+    /// Returns the maximum number of concurrent sessions the registry allows for this account
+    pub fn max_sessions(&self) -> Option<usize> {
+        self.max_sessions
+    }
+    /// Returns the policy for reconnecting when the registry closes the session, if set
+    pub fn reconnect(&self) -> Option<&EppClientReconnectPolicy> {
+        self.reconnect.as_ref()
+    }
+    /// Returns the timeouts for connecting to and transacting with the registry
+    pub fn timeouts(&self) -> EppClientTimeouts {
+        self.timeouts.clone().unwrap_or_default()
+    }
+    /// Returns the codec for reading and writing EPP frames on the connection, limited to the
+    /// configured maximum frame size
+    pub fn codec(&self) -> EppCodec {
+        match self.max_frame_size {
+            Some(max_frame_size) => EppCodec::new(max_frame_size),
+            None => EppCodec::default(),
         }
     }
-    /// Parses the client certificate chain
-    fn client_certificate(&self) -> Option<Vec<Certificate>> {
-        match &self.tls_files {
-            Some(tls) => Some(
-                rustls_pemfile::certs(&mut io::BufReader::new(
-                    fs::File::open(tls.cert_chain.to_string()).unwrap(),
-                ))
-                .unwrap()
-                .iter()
-                .map(|v| Certificate(v.clone()))
-                .collect(),
-            ),
-            None => None,
+    /// Returns the recorder capturing the sessions with the registry, if set
+    pub fn recorder(&self) -> Option<&TranscriptRecorder> {
+        self.recorder.as_ref()
+    }
+    /// Returns whether the connection is secured with TLS, which is the default
+    pub fn tls(&self) -> bool {
+        self.tls.unwrap_or(true)
+    }
+    /// Returns the certificates to trust in addition to the bundled webpki roots, including
+    /// those in the CA file
+    pub fn root_certs(&self) -> Result<Vec<Certificate>, error::Error> {
+        let mut root_certs = self.root_certs.clone();
+
+        if let Some(ca_file) = &self.ca_file {
+            root_certs.extend(Self::read_pem_file(ca_file, certs_from_pem)?);
         }
+
+        Ok(root_certs)
+    }
+    /// Returns whether the bundled webpki roots are trusted, which is the default
+    pub fn bundled_roots(&self) -> bool {
+        self.bundled_roots.unwrap_or(true)
     }
-    /// Parses the client RSA private key
-    fn key(&self) -> Option<PrivateKey> {
-        match &self.tls_files {
-            Some(tls) => Some(rustls::PrivateKey(
-                rustls_pemfile::rsa_private_keys(&mut io::BufReader::new(
-                    fs::File::open(tls.key.to_string()).unwrap(),
-                ))
-                .unwrap()[0]
-                    .clone(),
-            )),
-            None => None,
+    /// Returns the decoded SHA-256 SPKI pins to check the server certificate against
+    pub fn spki_pins(&self) -> Result<Vec<Vec<u8>>, error::Error> {
+        match &self.spki_pins {
+            Some(pins) => pins.iter().map(|pin| decode_spki_pin(pin)).collect(),
+            None => Ok(Vec::new()),
         }
     }
+    /// Returns the name to verify the server certificate against, which defaults to the host
+    pub fn server_name(&self) -> String {
+        match &self.server_name {
+            Some(server_name) => server_name.to_string(),
+            None => self.host.to_string(),
+        }
+    }
+    /// Returns the parsed client certificate and private key for client TLS auth
+    pub fn tls_files(&self) -> Result<Option<(Vec<Certificate>, PrivateKey)>, error::Error> {
+        if let Some(identity) = &self.tls_identity {
+            return Ok(Some(identity.clone()));
+        }
+
+        let tls = match &self.tls_files {
+            Some(tls) => tls,
+            None => return Ok(None),
+        };
+
+        Ok(Some((
+            Self::read_pem_file(&tls.cert_chain, certs_from_pem)?,
+            Self::read_pem_file(&tls.key, private_key_from_pem)?,
+        )))
+    }
+    /// Reads a TLS PEM file and parses it, adding the path to parse errors
+    fn read_pem_file<T>(
+        path: &str,
+        parse: fn(&[u8]) -> Result<T, error::Error>,
+    ) -> Result<T, error::Error> {
+        let pem = fs::read(path)
+            .map_err(|e| error::Error::EppTlsError(format!("Failed to open {}: {}", path, e)))?;
+
+        parse(&pem).map_err(|e| match e {
+            error::Error::EppTlsError(e) => error::Error::EppTlsError(format!("{}: {}", path, e)),
+            e => e,
+        })
+    }
 }
 
 impl EppClientConfig {
+    /// Loads the config from the `$XDG_CONFIG_HOME/epp-client/epp-client.toml` file
+    #[cfg(feature = "config-file")]
+    pub fn load() -> Result<EppClientConfig, error::Error> {
+        confy::load("epp-client").map_err(|e| error::Error::EppConfigError(e.to_string()))
+    }
+
     /// Returns the config for a particular registry
     pub fn registry(&self, registry: &str) -> Option<&EppClientConnection> {
         self.registry.get(registry)
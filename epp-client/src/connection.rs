@@ -1,5 +1,10 @@
 //! Manages registry connections and reading/writing to them
 //! and connects the EppClient instances to them
 
-pub mod registry;
+pub mod builder;
 pub mod client;
+pub mod codec;
+pub mod pool;
+pub mod registry;
+pub mod tls;
+pub mod transcript;
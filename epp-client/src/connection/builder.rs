@@ -0,0 +1,328 @@
+//! Builds registry connection details in code to connect an EppClient without the config file
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use epp_client::EppClient;
+//! use epp_client::epp::{EppDomainCheck, EppDomainCheckResponse};
+//! use epp_client::epp::generate_client_tr_id;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // Credentials can come from anywhere, e.g. a database for multi-tenant setups
+//!     let mut client = match EppClient::builder()
+//!         .host("epp.verisign-grs.com", 700)
+//!         .credentials("username", "password")
+//!         .ext_uris(vec!["urn:ietf:params:xml:ns:rgp-1.0".to_string()])
+//!         .response_timeout(Duration::from_secs(30))
+//!         .connect()
+//!         .await
+//!     {
+//!         Ok(client) => client,
+//!         Err(e) => panic!("Failed to create EppClient: {}",  e)
+//!     };
+//!
+//!     let domain_check = EppDomainCheck::new(vec!["eppdev.com"], generate_client_tr_id(&client).as_str());
+//!     let response = client.transact::<_, EppDomainCheckResponse>(&domain_check).await.unwrap();
+//!     println!("{:?}", response);
+//!
+//!     client.close().await.unwrap();
+//! }
+//! ```
+
+use rustls::{Certificate, PrivateKey};
+use std::time::Duration;
+
+use crate::config::{EppClientConnection, EppClientReconnectPolicy, EppClientTimeouts};
+use crate::connection::client::EppClient;
+use crate::connection::pool::EppPool;
+use crate::connection::registry::ConnectionStream;
+use crate::connection::transcript::TranscriptRecorder;
+use crate::error;
+
+/// Builder for the connection details of a registry, returned by `EppClient::builder()`.
+/// The host and credentials are required, everything else is optional
+#[derive(Debug, Default)]
+pub struct EppClientBuilder {
+    registry: Option<String>,
+    host: Option<(String, u16)>,
+    credentials: Option<(String, String)>,
+    new_password: Option<String>,
+    ext_uris: Option<Vec<String>>,
+    negotiate: Option<bool>,
+    required_ext_uris: Option<Vec<String>>,
+    tls_identity: Option<(Vec<Certificate>, PrivateKey)>,
+    tls_pem: Option<(Vec<u8>, Vec<u8>)>,
+    root_certs: Vec<Certificate>,
+    root_certs_pem: Vec<Vec<u8>>,
+    ca_file: Option<String>,
+    bundled_roots: Option<bool>,
+    spki_pins: Option<Vec<String>>,
+    server_name: Option<String>,
+    timeouts: EppClientTimeouts,
+    reconnect: Option<EppClientReconnectPolicy>,
+    max_frame_size: Option<usize>,
+    max_sessions: Option<usize>,
+    tls: Option<bool>,
+    recorder: Option<TranscriptRecorder>,
+}
+
+/// Converts a duration to the milliseconds used in `EppClientTimeouts`
+fn millis(duration: Duration) -> Option<u64> {
+    Some(duration.as_millis() as u64)
+}
+
+impl EppClientBuilder {
+    /// Sets the name of the registry used in log messages. Defaults to the host
+    pub fn registry(mut self, registry: &str) -> Self {
+        self.registry = Some(registry.to_string());
+        self
+    }
+
+    /// Sets the EPP host and port of the registry
+    pub fn host(mut self, host: &str, port: u16) -> Self {
+        self.host = Some((host.to_string(), port));
+        self
+    }
+
+    /// Sets the EPP username and password
+    pub fn credentials(mut self, username: &str, password: &str) -> Self {
+        self.credentials = Some((username.to_string(), password.to_string()));
+        self
+    }
+
+    /// Sets a new password to change the password to on login, e.g. for periodic password
+    /// rotation. The client uses the new password once the registry accepted it
+    pub fn new_password(mut self, new_password: &str) -> Self {
+        self.new_password = Some(new_password.to_string());
+        self
+    }
+
+    /// Sets the service extension URIs to be set in the connection to the registry
+    pub fn ext_uris(mut self, ext_uris: Vec<String>) -> Self {
+        self.ext_uris = Some(ext_uris);
+        self
+    }
+
+    /// Sets whether the objects and extensions requested at login are limited to those the
+    /// registry announces in its greeting. Disabled by default
+    pub fn negotiate(mut self, negotiate: bool) -> Self {
+        self.negotiate = Some(negotiate);
+        self
+    }
+
+    /// Sets the service extension URIs the registry must announce in its greeting, failing
+    /// the connection before login otherwise
+    pub fn required_ext_uris(mut self, required_ext_uris: Vec<String>) -> Self {
+        self.required_ext_uris = Some(required_ext_uris);
+        self
+    }
+
+    /// Sets the client certificate chain and private key for client TLS auth
+    pub fn tls_identity(mut self, cert_chain: Vec<Certificate>, key: PrivateKey) -> Self {
+        self.tls_identity = Some((cert_chain, key));
+        self
+    }
+
+    /// Sets the PEM encoded client certificate chain and private key for client TLS auth. The
+    /// key can be an RSA, PKCS#8 or SEC1 EC key, and is parsed when the builder connects
+    pub fn tls_identity_pem(mut self, cert_chain: &[u8], key: &[u8]) -> Self {
+        self.tls_pem = Some((cert_chain.to_vec(), key.to_vec()));
+        self
+    }
+
+    /// Adds certificates to trust in addition to the bundled webpki roots
+    pub fn root_certs(mut self, root_certs: Vec<Certificate>) -> Self {
+        self.root_certs.extend(root_certs);
+        self
+    }
+
+    /// Adds PEM encoded certificates to trust in addition to the bundled webpki roots. The
+    /// certificates are parsed when the builder connects
+    pub fn root_certs_pem(mut self, pem: &[u8]) -> Self {
+        self.root_certs_pem.push(pem.to_vec());
+        self
+    }
+
+    /// Sets the path to a PEM file with certificates to trust in addition to the bundled
+    /// webpki roots
+    pub fn ca_file(mut self, ca_file: &str) -> Self {
+        self.ca_file = Some(ca_file.to_string());
+        self
+    }
+
+    /// Sets whether the bundled webpki roots are trusted, which is the default
+    pub fn bundled_roots(mut self, bundled_roots: bool) -> Self {
+        self.bundled_roots = Some(bundled_roots);
+        self
+    }
+
+    /// Sets the base64 encoded SHA-256 hashes of the server public keys (SPKI) to accept
+    pub fn spki_pins(mut self, spki_pins: Vec<String>) -> Self {
+        self.spki_pins = Some(spki_pins);
+        self
+    }
+
+    /// Sets the name to verify the server certificate against, if it differs from the host
+    pub fn server_name(mut self, server_name: &str) -> Self {
+        self.server_name = Some(server_name.to_string());
+        self
+    }
+
+    /// Sets the timeout for resolving the host and establishing the TCP connection
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.connect = millis(timeout);
+        self
+    }
+
+    /// Sets the timeout for the TLS handshake
+    pub fn handshake_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.handshake = millis(timeout);
+        self
+    }
+
+    /// Sets the timeout for receiving the greeting
+    pub fn greeting_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.greeting = millis(timeout);
+        self
+    }
+
+    /// Sets the timeout for receiving the response to each command
+    pub fn response_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.response = millis(timeout);
+        self
+    }
+
+    /// Sets the policy for reconnecting when the registry closes the session
+    pub fn reconnect(mut self, reconnect: EppClientReconnectPolicy) -> Self {
+        self.reconnect = Some(reconnect);
+        self
+    }
+
+    /// Sets whether the connection is secured with TLS, which is the default. Disabling TLS is
+    /// only meant for connecting to a local TLS proxy such as stunnel, or to a test server
+    pub fn tls(mut self, tls: bool) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Sets the maximum size in bytes of a frame received from the registry
+    pub fn max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = Some(max_frame_size);
+        self
+    }
+
+    /// Sets the maximum number of concurrent sessions the registry allows for this account,
+    /// which caps the size of pools
+    pub fn max_sessions(mut self, max_sessions: usize) -> Self {
+        self.max_sessions = Some(max_sessions);
+        self
+    }
+
+    /// Records the sessions with the registry into the recorder's transcript, see
+    /// `connection::transcript`
+    pub fn record(mut self, recorder: TranscriptRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Returns the registry name and the connection details set on the builder
+    pub fn build(self) -> Result<(String, EppClientConnection), error::Error> {
+        let (host, port) = match self.host {
+            Some(host) => host,
+            None => return Err(error::Error::EppConfigError("missing host".to_string())),
+        };
+
+        let (username, password) = match self.credentials {
+            Some(credentials) => credentials,
+            None => {
+                return Err(error::Error::EppConfigError(format!(
+                    "missing credentials for {}",
+                    host
+                )))
+            }
+        };
+
+        let mut registry_creds = EppClientConnection::new(&host, port, &username, &password);
+
+        if let Some(new_password) = self.new_password {
+            registry_creds.set_new_password(&new_password);
+        }
+        if let Some(ext_uris) = self.ext_uris {
+            registry_creds.set_ext_uris(ext_uris);
+        }
+        if let Some(negotiate) = self.negotiate {
+            registry_creds.set_negotiate(negotiate);
+        }
+        if let Some(required_ext_uris) = self.required_ext_uris {
+            registry_creds.set_required_ext_uris(required_ext_uris);
+        }
+        if let Some((cert_chain, key)) = self.tls_identity {
+            registry_creds.set_tls_identity(cert_chain, key);
+        }
+        if let Some((cert_chain, key)) = self.tls_pem {
+            registry_creds.set_tls_pem(&cert_chain, &key)?;
+        }
+        if let Some(reconnect) = self.reconnect {
+            registry_creds.set_reconnect(reconnect);
+        }
+        if let Some(max_frame_size) = self.max_frame_size {
+            registry_creds.set_max_frame_size(max_frame_size);
+        }
+        if let Some(max_sessions) = self.max_sessions {
+            registry_creds.set_max_sessions(max_sessions);
+        }
+        if let Some(tls) = self.tls {
+            registry_creds.set_tls(tls);
+        }
+        if let Some(recorder) = self.recorder {
+            registry_creds.set_recorder(recorder);
+        }
+        registry_creds.set_root_certs(self.root_certs);
+        for pem in self.root_certs_pem {
+            registry_creds.add_root_certs_pem(&pem)?;
+        }
+        if let Some(ca_file) = self.ca_file {
+            registry_creds.set_ca_file(&ca_file);
+        }
+        if let Some(bundled_roots) = self.bundled_roots {
+            registry_creds.set_bundled_roots(bundled_roots);
+        }
+        if let Some(spki_pins) = self.spki_pins {
+            registry_creds.set_spki_pins(spki_pins);
+        }
+        if let Some(server_name) = self.server_name {
+            registry_creds.set_server_name(&server_name);
+        }
+        registry_creds.set_timeouts(self.timeouts);
+
+        Ok((self.registry.unwrap_or(host), registry_creds))
+    }
+
+    /// Connects to the registry and logs in
+    pub async fn connect(self) -> Result<EppClient, error::Error> {
+        let (registry, registry_creds) = self.build()?;
+
+        EppClient::connect(&registry, &registry_creds).await
+    }
+
+    /// Logs in over an already established stream to the registry, see
+    /// `EppClient::connect_with_stream`
+    pub async fn connect_with_stream(
+        self,
+        stream: ConnectionStream,
+    ) -> Result<EppClient, error::Error> {
+        let (registry, registry_creds) = self.build()?;
+
+        EppClient::connect_with_stream(&registry, &registry_creds, stream).await
+    }
+
+    /// Opens a pool of `size` logged-in sessions to the registry
+    pub async fn pool(self, size: usize) -> Result<EppPool, error::Error> {
+        let (registry, registry_creds) = self.build()?;
+
+        EppPool::build(&registry, registry_creds, size).await
+    }
+}
@@ -24,67 +24,54 @@
 //!     let domain_check = EppDomainCheck::new(vec!["eppdev.com", "eppdev.net"], generate_client_tr_id(&client).as_str());
 //!     let response = client.transact::<_, EppDomainCheckResponse>(&domain_check).await.unwrap();
 //!     println!("{:?}", response);
+//!
+//!     // Log out and close the connection
+//!     client.close().await.unwrap();
 //! }
 //! ```
 
-use futures::executor::block_on;
-use std::{error::Error, fmt::Debug};
-use std::time::SystemTime;
-use std::sync::mpsc;
+use std::fmt::Debug;
+use std::time::{Duration, SystemTime};
+use tokio::time::sleep;
 // use std::sync::Arc;
 
-use crate::config::CONFIG;
-use crate::connection::registry::{epp_connect, EppConnection};
-use crate::error;
+#[cfg(feature = "config-file")]
+use crate::config::EppClientConfig;
+use crate::config::EppClientConnection;
+use crate::connection::builder::EppClientBuilder;
+use crate::connection::registry::{epp_connect, ConnectionStream, EppConnection};
+use crate::epp::object::Services;
 use crate::epp::request::{generate_client_tr_id, EppHello, EppLogin, EppLogout};
-use crate::epp::response::{EppGreeting, EppCommandResponse, EppLoginResponse, EppLogoutResponse, EppCommandResponseError};
+use crate::epp::response::{
+    EppCommandResponse, EppGreeting, EppLoginResponse, EppLogoutResponse, Greeting, ResultCode,
+};
 use crate::epp::xml::EppXml;
+use crate::error;
 
 /// Connects to the registry and returns an logged-in instance of EppClient for further transactions
-async fn connect(registry: &'static str) -> Result<EppClient, Box<dyn Error>> {
-    let registry_creds = match CONFIG.registry(registry) {
+#[cfg(feature = "config-file")]
+async fn connect(registry: &str) -> Result<EppClient, error::Error> {
+    let config = EppClientConfig::load()?;
+
+    let registry_creds = match config.registry(registry) {
         Some(creds) => creds,
-        None => return Err(format!("missing credentials for {}", registry).into())
+        None => {
+            return Err(error::Error::EppConfigError(format!(
+                "missing credentials for {}",
+                registry
+            )))
+        }
     };
 
-    let (tx, rx) = mpsc::channel();
-
-    tokio::spawn(async move {
-        let stream = epp_connect(&registry_creds).await.unwrap();
-        let credentials = registry_creds.credentials();
-        let ext_uris = registry_creds.ext_uris();
-
-        let ext_uris = match ext_uris {
-            Some(uris) => Some(
-                uris
-                    .iter()
-                    .map(|u| u.to_string())
-                    .collect::<Vec<String>>()
-            ),
-            None => None,
-        };
-
-        let connection = EppConnection::new(
-            registry.to_string(),
-            stream
-        ).await.unwrap();
-
-        let client = EppClient::build(connection, credentials, ext_uris).await.unwrap();
-
-        tx.send(client).unwrap();
-    });
-
-    let client = rx.recv()?;
-
-    Ok(client)
+    EppClient::connect(registry, registry_creds).await
 }
 
 /// Instances of the EppClient type are used to transact with the registry.
 /// Once initialized, the EppClient instance can serialize EPP requests to XML and send them
 /// to the registry and deserialize the XML responses from the registry to local types
 pub struct EppClient {
-    credentials: (String, String),
-    ext_uris: Option<Vec<String>>,
+    registry: String,
+    registry_creds: EppClientConnection,
     connection: EppConnection,
     // pub client_tr_id_fn: Arc<dyn Fn(&EppClient) -> String + Send + Sync>,
 }
@@ -94,15 +81,51 @@ pub struct EppClient {
 pub fn default_client_tr_id_fn(client: &EppClient) -> String {
     let timestamp = match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
         Ok(time) => time,
-        Err(e) => panic!("Error in client TRID gen function: {}", e)
+        Err(e) => panic!("Error in client TRID gen function: {}", e),
     };
     format!("{}:{}", &client.username(), timestamp.as_secs())
 }
 
+/// Deserializes a command response to type E, or to an `EppCommandError` if the result code
+/// indicates that the command failed
+fn parse_response<E: EppXml + Debug>(response: &str) -> Result<E::Output, error::Error> {
+    let status = EppCommandResponse::deserialize(response)?;
+
+    match status.data.result() {
+        Some(result) if result.code.is_success() => {
+            let response = E::deserialize(response)?;
+            Ok(response)
+        }
+        _ => Err(error::Error::EppCommandError(Box::new(status.into()))),
+    }
+}
+
+/// Reads the greeting on a new stream to the registry, recording the session if a recorder
+/// is set in the connection details
+async fn open_connection(
+    registry: &str,
+    registry_creds: &EppClientConnection,
+    stream: ConnectionStream,
+) -> Result<EppConnection, error::Error> {
+    let mut connection = EppConnection::new(
+        registry.to_string(),
+        stream,
+        registry_creds.timeouts(),
+        registry_creds.codec(),
+    )
+    .await?;
+
+    if let Some(recorder) = registry_creds.recorder() {
+        connection.record(recorder.clone());
+    }
+
+    Ok(connection)
+}
+
 impl EppClient {
     /// Fetches the username used in the registry connection
     pub fn username(&self) -> String {
-        self.credentials.0.to_string()
+        self.registry_creds.credentials().0
     }
 
     // pub fn set_client_tr_id_fn<F>(&mut self, func: F)
@@ -110,66 +133,368 @@ impl EppClient {
     //     self.client_tr_id_fn = Arc::new(func);
     // }
 
-    /// Creates a new EppClient object and does an EPP Login to a given registry to become ready
-    /// for subsequent transactions on this client instance
-    pub async fn new(registry: &'static str) -> Result<EppClient, Box<dyn Error>> {
+    /// Creates a new EppClient object and does an EPP Login to a given registry in the config
+    /// file to become ready for subsequent transactions on this client instance
+    #[cfg(feature = "config-file")]
+    pub async fn new(registry: &str) -> Result<EppClient, error::Error> {
         connect(registry).await
     }
 
+    /// Returns a builder to set up the connection details for a registry in code and connect
+    /// to it, without the config file
+    pub fn builder() -> EppClientBuilder {
+        EppClientBuilder::default()
+    }
+
+    /// Connects to a registry with the given connection details and does an EPP Login, returning
+    /// an EppClient that is ready for subsequent transactions
+    pub async fn connect(
+        registry: &str,
+        registry_creds: &EppClientConnection,
+    ) -> Result<EppClient, error::Error> {
+        let stream = epp_connect(registry_creds).await?;
+        let connection = open_connection(registry, registry_creds, stream).await?;
+
+        EppClient::build(connection, registry.to_string(), registry_creds.clone()).await
+    }
+
+    /// Does an EPP Login over an already established stream to the registry, such as a tunnel
+    /// through a proxy or an in-memory stream to a test server. As the client could only
+    /// reconnect to the host in the connection details rather than over a stream like the given
+    /// one, connection details with a reconnect policy are rejected with an `EppConfigError`
+    pub async fn connect_with_stream(
+        registry: &str,
+        registry_creds: &EppClientConnection,
+        stream: ConnectionStream,
+    ) -> Result<EppClient, error::Error> {
+        if registry_creds.reconnect().is_some() {
+            return Err(error::Error::EppConfigError(format!(
+                "{}: a reconnect policy cannot be used with a client connected over a stream",
+                registry
+            )));
+        }
+
+        let connection = open_connection(registry, registry_creds, stream).await?;
+
+        EppClient::build(connection, registry.to_string(), registry_creds.clone()).await
+    }
+
     /// Makes a login request to the registry and initializes an EppClient instance with it
-    async fn build(connection: EppConnection, credentials: (String, String), ext_uris: Option<Vec<String>>) -> Result<EppClient, Box<dyn Error>> {
+    async fn build(
+        connection: EppConnection,
+        registry: String,
+        registry_creds: EppClientConnection,
+    ) -> Result<EppClient, error::Error> {
         let mut client = EppClient {
-            connection: connection,
-            credentials: credentials,
-            ext_uris: ext_uris,
+            registry,
+            registry_creds,
+            connection,
             // client_tr_id_fn: Arc::new(default_client_tr_id_fn),
         };
 
-        let client_tr_id = generate_client_tr_id(&client.credentials.0)?;
-        let login_request = EppLogin::new(&client.credentials.0, &client.credentials.1, &client.ext_uris, client_tr_id.as_str());
-
-        client.transact::<_, EppLoginResponse>(&login_request).await?;
+        client.login().await?;
 
         Ok(client)
     }
 
+    /// Sends the EPP Login command for the current connection
+    async fn login(&mut self) -> Result<(), error::Error> {
+        let (username, password) = self.registry_creds.credentials();
+        let mut ext_uris = self.registry_creds.ext_uris().cloned();
+
+        let required_ext_uris = self.registry_creds.required_ext_uris();
+        if !required_ext_uris.is_empty() {
+            let ext_uris = ext_uris.get_or_insert_with(Vec::new);
+            for uri in required_ext_uris {
+                if !ext_uris.contains(uri) {
+                    ext_uris.push(uri.clone());
+                }
+            }
+        }
+
+        let client_tr_id = generate_client_tr_id(&username)?;
+        let mut login_request =
+            EppLogin::new(&username, &password, &ext_uris, client_tr_id.as_str());
+
+        if let Some(new_password) = self.registry_creds.new_password() {
+            login_request.new_password(new_password);
+        }
+
+        if self.registry_creds.negotiate() || !required_ext_uris.is_empty() {
+            let greeting = self.greeting()?;
+            self.negotiate(&greeting.data, &mut login_request.data.command.services)?;
+        }
+
+        let login_xml = login_request.serialize()?;
+
+        let response = self.connection.transact(&login_xml).await?;
+
+        match parse_response::<EppLoginResponse>(&response) {
+            Ok(response) => {
+                if self.registry_creds.new_password().is_some() {
+                    match response.data.result().map(|result| result.code) {
+                        Some(ResultCode::CommandCompletedSuccessfully) => {
+                            info!("{}: Password changed", self.registry);
+                            self.registry_creds.password_changed();
+                        }
+                        Some(code) => warn!(
+                            "{}: Password change not confirmed, login returned {}",
+                            self.registry, code
+                        ),
+                        None => warn!(
+                            "{}: Password change not confirmed, login returned no result",
+                            self.registry
+                        ),
+                    }
+                }
+                Ok(())
+            }
+            Err(error::Error::EppCommandError(e)) => {
+                error!("{}: Login rejected: {}", self.registry, e);
+                Err(error::Error::EppLoginError(e))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Checks that the greeting announces the required extensions and, with negotiation
+    /// enabled, drops the objects and extensions it does not announce from the login services
+    fn negotiate(&self, greeting: &Greeting, services: &mut Services) -> Result<(), error::Error> {
+        let missing: Vec<&str> = self
+            .registry_creds
+            .required_ext_uris()
+            .iter()
+            .filter(|uri| !greeting.supports_extension(uri))
+            .map(|uri| uri.as_str())
+            .collect();
+
+        if !missing.is_empty() {
+            error!(
+                "{}: Required extensions missing from the greeting: {}",
+                self.registry,
+                missing.join(", ")
+            );
+            return Err(error::Error::EppNegotiationError(format!(
+                "registry does not offer required extensions {}",
+                missing.join(", ")
+            )));
+        }
+
+        if !self.registry_creds.negotiate() {
+            return Ok(());
+        }
+
+        services.obj_uris.retain(|uri| {
+            let supported = greeting.supports_object(&uri.to_string());
+            if !supported {
+                warn!(
+                    "{}: Not requesting object {} missing from the greeting",
+                    self.registry, uri
+                );
+            }
+            supported
+        });
+
+        let ext_uris = services
+            .svc_ext
+            .as_mut()
+            .and_then(|svc_ext| svc_ext.ext_uris.as_mut());
+        if let Some(ext_uris) = ext_uris {
+            ext_uris.retain(|uri| {
+                let supported = greeting.supports_extension(&uri.to_string());
+                if !supported {
+                    warn!(
+                        "{}: Not requesting extension {} missing from the greeting",
+                        self.registry, uri
+                    );
+                }
+                supported
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Opens a new connection to the registry and logs in again, backing off between failed
+    /// attempts as set in the reconnect policy
+    async fn reconnect(&mut self) -> Result<(), error::Error> {
+        let policy = match self.registry_creds.reconnect() {
+            Some(policy) => policy.clone(),
+            None => return Err(error::Error::EppConnectionClosed),
+        };
+
+        let max_backoff = Duration::from_millis(policy.max_backoff);
+        let mut backoff = Duration::from_millis(policy.backoff).min(max_backoff);
+        let mut attempt = 1;
+
+        loop {
+            info!(
+                "{}: Reconnecting, attempt {} of {}",
+                self.registry, attempt, policy.max_attempts
+            );
+
+            let result = match epp_connect(&self.registry_creds).await {
+                Ok(stream) => {
+                    match open_connection(&self.registry, &self.registry_creds, stream).await {
+                        Ok(connection) => {
+                            self.connection = connection;
+                            self.login().await
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt < policy.max_attempts => {
+                    warn!(
+                        "{}: Reconnect failed: {}, retrying in {:?}",
+                        self.registry, e, backoff
+                    );
+                    sleep(backoff).await;
+                    backoff = (backoff * 2).min(max_backoff);
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Sends an EPP XML request on the current session and returns the response. If the session
+    /// has been closed and a reconnect policy is set, the client first reconnects and logs in again.
+    /// Idempotent requests interrupted by the registry closing the session, or ended with a
+    /// retryable result code, are resent on a new session up to `max_retries` times. A timeout,
+    /// if given, overrides the configured response timeout
+    async fn send(
+        &mut self,
+        epp_xml: &str,
+        idempotent: bool,
+        timeout: Option<Duration>,
+    ) -> Result<String, error::Error> {
+        let max_retries = match self.registry_creds.reconnect() {
+            Some(policy) if idempotent => policy.max_retries,
+            _ => 0,
+        };
+        let mut retries = 0;
+
+        loop {
+            if !self.connection.is_open() && self.registry_creds.reconnect().is_some() {
+                self.reconnect().await?;
+            }
+
+            let result = match timeout {
+                Some(timeout) => {
+                    self.connection
+                        .transact_with_timeout(epp_xml, timeout)
+                        .await
+                }
+                None => self.connection.transact(epp_xml).await,
+            };
+
+            let response = match result {
+                Ok(response) => response,
+                Err(e) if !self.connection.is_open() && retries < max_retries => {
+                    warn!(
+                        "{}: Session closed during request ({}), resending",
+                        self.registry, e
+                    );
+                    retries += 1;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            // 1500 and the 2500 series codes mean the registry is ending the session
+            let code = EppCommandResponse::deserialize(&response)
+                .ok()
+                .and_then(|status| status.data.result().map(|result| result.code));
+
+            if let Some(code) = code {
+                if code.closes_session() {
+                    info!(
+                        "{}: Registry ended the session with code {}",
+                        self.registry, code
+                    );
+                    self.connection.set_closed();
+
+                    if code.is_retryable() && retries < max_retries {
+                        warn!("{}: Resending request on a new session", self.registry);
+                        retries += 1;
+                        continue;
+                    }
+                }
+            }
+
+            return Ok(response);
+        }
+    }
+
     /// Executes an EPP Hello call and returns the response as an `EppGreeting`
-    pub async fn hello(&mut self) -> Result<EppGreeting, Box<dyn Error>> {
+    pub async fn hello(&mut self) -> Result<EppGreeting, error::Error> {
         let hello = EppHello::new();
         let hello_xml = hello.serialize()?;
 
-        let response = self.connection.transact(&hello_xml).await?;
+        let response = self.send(&hello_xml, hello.is_idempotent(), None).await?;
 
-        Ok(EppGreeting::deserialize(&response)?)
+        EppGreeting::deserialize(&response)
     }
 
     /// Accepts an EPP request object to convert to a request to send to the registry. The response from the
     /// registry is deserialized to response type E and returned.
-    pub async fn transact<T: EppXml + Debug, E: EppXml + Debug>(&mut self, request: &T) -> Result<E::Output, error::Error> {
+    pub async fn transact<T: EppXml + Debug, E: EppXml + Debug>(
+        &mut self,
+        request: &T,
+    ) -> Result<E::Output, error::Error> {
         let epp_xml = request.serialize()?;
 
-        let response = self.connection.transact(&epp_xml).await?;
+        let response = self.send(&epp_xml, request.is_idempotent(), None).await?;
 
-        let status = EppCommandResponse::deserialize(&response)?;
+        parse_response::<E>(&response)
+    }
 
-        if status.data.result.code < 2000 {
-            let response = E::deserialize(&response)?;
-            Ok(response)
-        } else {
-            let epp_error = EppCommandResponseError::deserialize(&response)?;
-            Err(error::Error::EppCommandError(epp_error))
-        }
+    /// Same as `transact`, but fails with an `EppTimeoutError` if the response is not received
+    /// within the given timeout instead of the response timeout set for the registry. The
+    /// session cannot be used after a timeout, and is reopened by the next transaction if a
+    /// reconnect policy is set
+    pub async fn transact_with_timeout<T: EppXml + Debug, E: EppXml + Debug>(
+        &mut self,
+        request: &T,
+        timeout: Duration,
+    ) -> Result<E::Output, error::Error> {
+        let epp_xml = request.serialize()?;
+
+        let response = self
+            .send(&epp_xml, request.is_idempotent(), Some(timeout))
+            .await?;
+
+        parse_response::<E>(&response)
+    }
+
+    /// Accepts raw EPP XML and returns the raw EPP XML response to it. Raw requests are never
+    /// resent on a new session. Not recommended for direct use but sometimes can be useful for
+    /// debugging
+    pub async fn transact_xml(&mut self, xml: &str) -> Result<String, error::Error> {
+        self.send(xml, false, None).await
     }
 
-    /// Accepts raw EPP XML and returns the raw EPP XML response to it.
-    /// Not recommended for direct use but sometimes can be useful for debugging
-    pub async fn transact_xml(&mut self, xml: &str) -> Result<String, Box<dyn Error>> {
-        self.connection.transact(&xml).await
+    /// Returns false once the session has been closed, either because the connection failed
+    /// or because the registry ended the session. If a reconnect policy is set, the next
+    /// transaction opens a new session
+    pub fn is_open(&self) -> bool {
+        self.connection.is_open()
+    }
+
+    /// Returns the connection details of the client, with the password updated after a
+    /// password change on login
+    pub fn registry_creds(&self) -> &EppClientConnection {
+        &self.registry_creds
     }
 
     /// Returns the greeting received on establishment of the connection in raw xml form
     pub fn xml_greeting(&self) -> String {
-        return String::from(&self.connection.greeting)
+        String::from(&self.connection.greeting)
     }
 
     /// Returns the greeting received on establishment of the connection as an `EppGreeting`
@@ -177,9 +502,24 @@ impl EppClient {
         EppGreeting::deserialize(&self.connection.greeting)
     }
 
+    /// Logs out of the EPP session and shuts down the connection to the registry. Should be
+    /// called when the client is no longer needed, as dropping an open client does not log out
+    pub async fn close(mut self) -> Result<EppLogoutResponse, error::Error> {
+        let response = match self.is_open() {
+            true => self.logout().await,
+            false => Err(error::Error::EppConnectionClosed),
+        };
+
+        if let Err(e) = self.connection.close().await {
+            debug!("{}: Error shutting down connection: {}", self.registry, e);
+        }
+
+        response
+    }
+
     /// Sends the EPP Logout command to log out of the EPP session
     pub async fn logout(&mut self) -> Result<EppLogoutResponse, error::Error> {
-        let client_tr_id = generate_client_tr_id(&self.credentials.0).unwrap();
+        let client_tr_id = generate_client_tr_id(&self.username())?;
         let epp_logout = EppLogout::new(client_tr_id.as_str());
 
         self.transact::<_, EppLogoutResponse>(&epp_logout).await
@@ -188,6 +528,11 @@ impl EppClient {
 
 impl Drop for EppClient {
     fn drop(&mut self) {
-        block_on(self.logout());
+        if self.is_open() {
+            warn!(
+                "{}: EppClient dropped without calling close(), the session was not logged out",
+                self.registry
+            );
+        }
     }
 }
@@ -0,0 +1,169 @@
+//! Framing of EPP messages over a stream as described in RFC 5734
+//!
+//! Each EPP XML message is preceded by a 4 byte header holding the total length of the
+//! header and the message as a big-endian 32 bit integer. `EppCodec` encodes and decodes
+//! these frames and is used by both sides of a connection, so it can serve a server
+//! implementation as well as the client.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use bytes::BytesMut;
+//! use epp_client::connection::codec::EppCodec;
+//!
+//! let codec = EppCodec::default();
+//!
+//! let mut buf = BytesMut::new();
+//! codec.encode(b"<epp/>", &mut buf).unwrap();
+//! assert_eq!(&buf[..4], &[0, 0, 0, 10]);
+//!
+//! // Frames are only returned once they are complete
+//! let mut partial = buf.split_to(7);
+//! assert_eq!(codec.decode(&mut partial).unwrap(), None);
+//!
+//! partial.unsplit(buf);
+//! assert_eq!(codec.decode(&mut partial).unwrap(), Some(b"<epp/>".to_vec()));
+//! ```
+
+use bytes::{Buf, BufMut, BytesMut};
+use std::convert::TryInto;
+use std::io as stdio;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::error;
+
+/// The length of the frame header
+pub const HEADER_LEN: usize = 4;
+/// The default maximum size of a frame, including the header
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 4 * 1024 * 1024;
+
+/// Encoder and decoder for length-prefixed EPP frames
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EppCodec {
+    max_frame_size: usize,
+}
+
+impl Default for EppCodec {
+    fn default() -> Self {
+        Self {
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+        }
+    }
+}
+
+impl EppCodec {
+    /// Creates a codec that rejects frames larger than `max_frame_size` bytes, including the header
+    pub fn new(max_frame_size: usize) -> EppCodec {
+        EppCodec { max_frame_size }
+    }
+
+    /// Returns the maximum size of a frame, including the header
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    /// Validates a frame header and returns the length of the message that follows it
+    pub fn message_len(&self, header: [u8; HEADER_LEN]) -> Result<usize, error::Error> {
+        let frame_size = u32::from_be_bytes(header) as usize;
+
+        if frame_size < HEADER_LEN {
+            return Err(error::Error::EppFramingError(format!(
+                "Invalid frame length: {}",
+                frame_size
+            )));
+        }
+
+        if frame_size > self.max_frame_size {
+            return Err(error::Error::EppFramingError(format!(
+                "Frame length {} exceeds the maximum of {}",
+                frame_size, self.max_frame_size
+            )));
+        }
+
+        Ok(frame_size - HEADER_LEN)
+    }
+
+    /// Appends the frame for a message to `dst`
+    pub fn encode(&self, message: &[u8], dst: &mut BytesMut) -> Result<(), error::Error> {
+        let frame_size = message.len() + HEADER_LEN;
+
+        if frame_size > self.max_frame_size {
+            return Err(error::Error::EppFramingError(format!(
+                "Frame length {} exceeds the maximum of {}",
+                frame_size, self.max_frame_size
+            )));
+        }
+
+        let frame_size: u32 = frame_size.try_into().map_err(|_| {
+            error::Error::EppFramingError(format!("Message too large: {} bytes", message.len()))
+        })?;
+
+        dst.reserve(frame_size as usize);
+        dst.put_u32(frame_size);
+        dst.put_slice(message);
+
+        Ok(())
+    }
+
+    /// Removes a complete frame from the start of `src` and returns its message. Returns `None`
+    /// and leaves `src` untouched if the frame is not complete yet
+    pub fn decode(&self, src: &mut BytesMut) -> Result<Option<Vec<u8>>, error::Error> {
+        if src.len() < HEADER_LEN {
+            return Ok(None);
+        }
+
+        let mut header = [0u8; HEADER_LEN];
+        header.copy_from_slice(&src[..HEADER_LEN]);
+        let message_len = self.message_len(header)?;
+
+        if src.len() < HEADER_LEN + message_len {
+            src.reserve(HEADER_LEN + message_len - src.len());
+            return Ok(None);
+        }
+
+        src.advance(HEADER_LEN);
+        Ok(Some(src.split_to(message_len).to_vec()))
+    }
+
+    /// Reads a frame from the stream and returns its message. Fails with
+    /// `EppConnectionClosed` if the stream ends before a complete frame is read
+    pub async fn read_frame<R: AsyncRead + Unpin>(
+        &self,
+        reader: &mut R,
+    ) -> Result<Vec<u8>, error::Error> {
+        let mut header = [0u8; HEADER_LEN];
+        reader.read_exact(&mut header).await.map_err(closed)?;
+
+        let mut message = vec![0u8; self.message_len(header)?];
+        reader.read_exact(&mut message).await.map_err(closed)?;
+
+        Ok(message)
+    }
+
+    /// Writes a message to the stream as a single frame. Fails with `EppConnectionClosed` if the
+    /// other end has closed the stream
+    pub async fn write_frame<W: AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+        message: &[u8],
+    ) -> Result<(), error::Error> {
+        let mut buf = BytesMut::new();
+        self.encode(message, &mut buf)?;
+
+        writer.write_all(&buf).await.map_err(closed)?;
+        writer.flush().await.map_err(closed)?;
+
+        Ok(())
+    }
+}
+
+/// Maps the end of the stream, or a stream closed by the other end, to `EppConnectionClosed`
+fn closed(e: stdio::Error) -> error::Error {
+    match e.kind() {
+        stdio::ErrorKind::UnexpectedEof
+        | stdio::ErrorKind::BrokenPipe
+        | stdio::ErrorKind::ConnectionReset
+        | stdio::ErrorKind::ConnectionAborted => error::Error::EppConnectionClosed,
+        _ => e.into(),
+    }
+}
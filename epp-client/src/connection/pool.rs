@@ -0,0 +1,249 @@
+//! Manages a pool of logged-in EPP sessions to a registry that can be shared between tasks
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use epp_client::EppPool;
+//! use epp_client::epp::{EppDomainCheck, EppDomainCheckResponse};
+//! use epp_client::epp::generate_client_tr_id;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // Open as many sessions as the `max_sessions` setting for the registry in the
+//!     // config file allows
+//!     let pool = match EppPool::new("verisign").await {
+//!         Ok(pool) => Arc::new(pool),
+//!         Err(e) => panic!("Failed to create EppPool: {}",  e)
+//!     };
+//!
+//!     let mut handles = Vec::new();
+//!
+//!     for domain in ["eppdev.com", "eppdev.net"] {
+//!         let pool = pool.clone();
+//!
+//!         handles.push(tokio::spawn(async move {
+//!             // Wait for a free session and use it like a regular EppClient
+//!             let mut client = pool.get().await.unwrap();
+//!
+//!             let domain_check = EppDomainCheck::new(
+//!                 vec![domain],
+//!                 generate_client_tr_id(&client).as_str()
+//!             );
+//!             client.transact::<_, EppDomainCheckResponse>(&domain_check).await.unwrap()
+//!         }));
+//!     }
+//!
+//!     for handle in handles {
+//!         println!("{:?}", handle.await.unwrap());
+//!     }
+//!
+//!     // Log out of all sessions
+//!     if let Ok(pool) = Arc::try_unwrap(pool) {
+//!         pool.close().await.unwrap();
+//!     }
+//! }
+//! ```
+
+use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+#[cfg(feature = "config-file")]
+use crate::config::EppClientConfig;
+use crate::config::EppClientConnection;
+use crate::connection::client::EppClient;
+use crate::error;
+
+/// A pool of logged-in `EppClient` sessions to a single registry. The number of sessions
+/// checked out at the same time never exceeds the size of the pool, and sessions whose
+/// connection has failed are replaced with new ones when they are next needed
+pub struct EppPool {
+    registry: String,
+    registry_creds: EppClientConnection,
+    sessions: Mutex<Vec<EppClient>>,
+    permits: Semaphore,
+    size: usize,
+}
+
+impl EppPool {
+    /// Creates a pool for a registry in the config file, opening as many sessions as its
+    /// `max_sessions` setting allows, or a single session if it is not set
+    #[cfg(feature = "config-file")]
+    pub async fn new(registry: &str) -> Result<EppPool, error::Error> {
+        let registry_creds = match EppClientConfig::load()?.registry(registry) {
+            Some(creds) => creds.clone(),
+            None => {
+                return Err(error::Error::EppConfigError(format!(
+                    "missing credentials for {}",
+                    registry
+                )))
+            }
+        };
+        let size = registry_creds.max_sessions().unwrap_or(1);
+
+        EppPool::build(registry, registry_creds, size).await
+    }
+
+    /// Creates a pool with the given connection details and opens `size` sessions to the
+    /// registry. The size is capped at the `max_sessions` setting of the connection, if set
+    pub async fn build(
+        registry: &str,
+        registry_creds: EppClientConnection,
+        size: usize,
+    ) -> Result<EppPool, error::Error> {
+        let size = match registry_creds.max_sessions() {
+            Some(max_sessions) => size.min(max_sessions),
+            None => size,
+        };
+
+        if size == 0 {
+            return Err(error::Error::EppConfigError(format!(
+                "{}: EppPool needs at least one session",
+                registry
+            )));
+        }
+
+        // A password changed by the first login is used for the other sessions
+        let mut registry_creds = registry_creds;
+        let mut sessions: Vec<EppClient> = Vec::with_capacity(size);
+        for _ in 0..size {
+            match EppClient::connect(registry, &registry_creds).await {
+                Ok(client) => {
+                    registry_creds = client.registry_creds().clone();
+                    sessions.push(client);
+                }
+                Err(e) => {
+                    // Log out of the sessions opened so far, so that they do not count
+                    // against the session limit of the registry
+                    warn!(
+                        "{}: Failed to open pooled session, closing {} opened sessions: {}",
+                        registry,
+                        sessions.len(),
+                        e
+                    );
+                    for client in sessions {
+                        if let Err(e) = client.close().await {
+                            warn!("{}: Failed to close pooled session: {}", registry, e);
+                        }
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        info!("{}: Opened {} pooled sessions", registry, size);
+
+        Ok(EppPool {
+            registry: registry.to_string(),
+            registry_creds,
+            sessions: Mutex::new(sessions),
+            permits: Semaphore::new(size),
+            size,
+        })
+    }
+
+    /// Returns the maximum number of sessions in the pool
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Waits for a session to become available and checks it out of the pool. If the idle
+    /// session has been closed, a new session is opened and logged in to take its place
+    pub async fn get(&self) -> Result<PooledEppClient<'_>, error::Error> {
+        let permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|e| error::Error::Other(e.to_string()))?;
+
+        let idle = self
+            .sessions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .pop();
+
+        let client = match idle {
+            Some(client) if client.is_open() => client,
+            _ => {
+                info!("{}: Opening a new pooled session", self.registry);
+                EppClient::connect(&self.registry, &self.registry_creds).await?
+            }
+        };
+
+        Ok(PooledEppClient {
+            pool: self,
+            client: Some(client),
+            _permit: permit,
+        })
+    }
+
+    /// Closes the pool, logging out of each session. As the pool is consumed, all checked out
+    /// sessions have been returned by the time this is called. All sessions are closed even if
+    /// some fail to log out, in which case the first error is returned
+    pub async fn close(self) -> Result<(), error::Error> {
+        let sessions = self
+            .sessions
+            .into_inner()
+            .unwrap_or_else(|e| e.into_inner());
+
+        info!(
+            "{}: Closing {} pooled sessions",
+            self.registry,
+            sessions.len()
+        );
+
+        let mut result = Ok(());
+
+        for client in sessions.into_iter().filter(|client| client.is_open()) {
+            if let Err(e) = client.close().await {
+                warn!("{}: Failed to close pooled session: {}", self.registry, e);
+
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// An `EppClient` session checked out of an `EppPool`. The session is returned to the pool
+/// when this value is dropped
+pub struct PooledEppClient<'a> {
+    pool: &'a EppPool,
+    client: Option<EppClient>,
+    _permit: SemaphorePermit<'a>,
+}
+
+impl Deref for PooledEppClient<'_> {
+    type Target = EppClient;
+
+    fn deref(&self) -> &EppClient {
+        self.client.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PooledEppClient<'_> {
+    fn deref_mut(&mut self) -> &mut EppClient {
+        self.client.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledEppClient<'_> {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            if client.is_open() {
+                self.pool
+                    .sessions
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push(client);
+            } else {
+                warn!("{}: Discarding closed pooled session", self.pool.registry);
+            }
+        }
+    }
+}
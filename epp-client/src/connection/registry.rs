@@ -1,21 +1,40 @@
 //! Manages registry connections and reading/writing to them
 
+use std::future::Future;
 use std::sync::Arc;
-use std::{str, u32};
-use bytes::BytesMut;
-use std::convert::TryInto;
-use futures::executor::block_on;
-use std::{error::Error, net::ToSocketAddrs, io as stdio};
-use tokio_rustls::{TlsConnector, rustls::ClientConfig, webpki::DNSNameRef, client::TlsStream};
-use tokio::{net::TcpStream, io::AsyncWriteExt, io::AsyncReadExt, io::split, io::ReadHalf, io::WriteHalf};
-
-use crate::config::{EppClientConnection};
+use std::time::Duration;
+use tokio::{
+    io::split, io::AsyncRead, io::AsyncWrite, io::AsyncWriteExt, io::ReadHalf, io::WriteHalf,
+    net::lookup_host, net::TcpStream, time,
+};
+use tokio_rustls::{rustls::ClientConfig, webpki::DNSNameRef, TlsConnector};
+
+use crate::config::{EppClientConnection, EppClientTimeouts};
+use crate::connection::codec::{EppCodec, HEADER_LEN};
+use crate::connection::tls::PinnedCertVerifier;
+use crate::connection::transcript::TranscriptRecorder;
 use crate::error;
 
+/// A byte stream that EPP frames can be exchanged over, such as a TLS or plain TCP stream,
+/// a tunnel through a proxy or an in-memory `tokio::io::duplex` stream
+pub trait EppTransport: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> EppTransport for T {}
+
 /// Socket stream for the connection to the registry
 pub struct ConnectionStream {
-    reader: ReadHalf<TlsStream<TcpStream>>,
-    writer: WriteHalf<TlsStream<TcpStream>>,
+    reader: ReadHalf<Box<dyn EppTransport>>,
+    writer: WriteHalf<Box<dyn EppTransport>>,
+}
+
+impl ConnectionStream {
+    /// Creates a ConnectionStream that exchanges EPP frames over the given transport as is
+    pub fn new<T: EppTransport + 'static>(transport: T) -> ConnectionStream {
+        let transport: Box<dyn EppTransport> = Box::new(transport);
+        let (reader, writer) = split(transport);
+
+        ConnectionStream { reader, writer }
+    }
 }
 
 /// EPP Connection struct with some metadata for the connection
@@ -23,158 +42,296 @@ pub struct EppConnection {
     registry: String,
     stream: ConnectionStream,
     pub greeting: String,
+    open: bool,
+    timeouts: EppClientTimeouts,
+    codec: EppCodec,
+    recorder: Option<TranscriptRecorder>,
+}
+
+/// Awaits a future, failing with an `EppTimeoutError` naming the operation if it does not
+/// complete within the timeout
+async fn with_timeout<T, F>(
+    timeout: Option<Duration>,
+    operation: &str,
+    future: F,
+) -> Result<T, error::Error>
+where
+    F: Future<Output = Result<T, error::Error>>,
+{
+    match timeout {
+        Some(duration) => match time::timeout(duration, future).await {
+            Ok(result) => result,
+            Err(_) => Err(error::Error::EppTimeoutError(format!(
+                "{} timed out after {:?}",
+                operation, duration
+            ))),
+        },
+        None => future.await,
+    }
 }
 
 impl EppConnection {
-    /// Create an EppConnection instance with the stream to the registry
+    /// Create an EppConnection instance with the stream to the registry, reading the greeting
+    /// and all subsequent responses with the given codec
     pub async fn new(
         registry: String,
-        mut stream: ConnectionStream) -> Result<EppConnection, Box<dyn Error>> {
-        let mut buf = vec![0u8; 4096];
-        stream.reader.read(&mut buf).await?;
-        let greeting = str::from_utf8(&buf[4..])?.to_string();
+        mut stream: ConnectionStream,
+        timeouts: EppClientTimeouts,
+        codec: EppCodec,
+    ) -> Result<EppConnection, error::Error> {
+        let buf = with_timeout(timeouts.greeting(), "Greeting", async {
+            codec
+                .read_frame(&mut stream.reader)
+                .await
+                .map_err(|e| error::Error::EppGreetingError(e.to_string()))
+        })
+        .await?;
+
+        let greeting =
+            String::from_utf8(buf).map_err(|e| error::Error::EppGreetingError(e.to_string()))?;
 
         debug!("{}: greeting: {}", registry, greeting);
 
         Ok(EppConnection {
-            registry: registry,
-            stream: stream,
-            greeting: greeting
+            registry,
+            stream,
+            greeting,
+            open: true,
+            timeouts,
+            codec,
+            recorder: None,
         })
     }
 
-    /// Writes to the socket
-    async fn write(&mut self, buf: &Vec<u8>) -> Result<(), Box<dyn Error>> {
-        let wrote = self.stream.writer.write(buf).await?;
-
-        debug!("{}: Wrote {} bytes", self.registry, wrote);
-
-        Ok(())
+    /// Records the greeting and all subsequent requests and responses on the connection
+    pub fn record(&mut self, recorder: TranscriptRecorder) {
+        recorder.greeting(&self.greeting);
+        self.recorder = Some(recorder);
     }
 
     /// Constructs an EPP XML request in the required form and sends it to the server
-    async fn send_epp_request(&mut self, content: &str) -> Result<(), Box<dyn Error>> {
-        let len = content.len();
-
-        let buf_size = len + 4;
-        let mut buf: Vec<u8> = vec![0u8; buf_size];
-
-        let len = len + 4;
-        let len_u32: [u8; 4] = u32::to_be_bytes(len.try_into()?);
+    async fn send_epp_request(&mut self, content: &str) -> Result<(), error::Error> {
+        self.codec
+            .write_frame(&mut self.stream.writer, content.as_bytes())
+            .await?;
 
-        buf[..4].clone_from_slice(&len_u32);
-        buf[4..].clone_from_slice(&content.as_bytes());
+        debug!(
+            "{}: Wrote {} bytes",
+            self.registry,
+            content.len() + HEADER_LEN
+        );
 
-        self.write(&buf).await
+        Ok(())
     }
 
     /// Reads response from the socket
-    async fn read_epp_response(&mut self) -> Result<Vec<u8>, Box<dyn Error>> {
-        let mut buf = [0u8; 4];
-        self.stream.reader.read_exact(&mut buf).await?;
-
-        let buf_size :usize = u32::from_be_bytes(buf).try_into()?;
-
-        let message_size = buf_size - 4;
-        debug!("{}: Response buffer size: {}", self.registry, message_size);
-
-        let mut buf = BytesMut::with_capacity(4096);
-        let mut read_buf = vec![0u8; 4096];
-
-        let mut read_size :usize = 0;
-
-        loop {
-            let read = self.stream.reader.read(&mut read_buf).await?;
-            debug!("{}: Read: {} bytes", self.registry, read);
-            buf.extend_from_slice(&read_buf[0..read]);
-
-            read_size = read_size + read;
-            debug!("{}: Total read: {} bytes", self.registry, read_size);
-
-            if read == 0 {
-                panic!("{}: Unexpected eof", self.registry)
-            } else if read_size >= message_size {
-                break;
+    async fn read_epp_response(&mut self) -> Result<Vec<u8>, error::Error> {
+        match self.codec.read_frame(&mut self.stream.reader).await {
+            Ok(data) => {
+                debug!("{}: Read: {} bytes", self.registry, data.len() + HEADER_LEN);
+                Ok(data)
             }
+            Err(error::Error::EppConnectionClosed) => {
+                warn!("{}: Connection closed by registry", self.registry);
+                Err(error::Error::EppConnectionClosed)
+            }
+            Err(e) => Err(e),
         }
-
-        let data = buf.to_vec();
-
-        Ok(data)
     }
 
     /// Receives response from the socket and converts it into an EPP XML string
-    async fn get_epp_response(&mut self) -> Result<String, Box<dyn Error>> {
-        let contents = self.read_epp_response().await?;
+    async fn get_epp_response(&mut self) -> Result<String, error::Error> {
+        let contents = match self.read_epp_response().await {
+            Ok(contents) => contents,
+            Err(e) => {
+                self.open = false;
+                return Err(e);
+            }
+        };
 
-        let response = str::from_utf8(&contents)?.to_string();
+        let response = String::from_utf8(contents)
+            .map_err(|e| error::Error::EppDeserializationError(e.to_string()))?;
 
         Ok(response)
     }
 
     /// Sends an EPP XML request to the registry and return the response
     /// receieved to the request
-    pub async fn transact(&mut self, content: &str) -> Result<String, Box<dyn Error>> {
+    pub async fn transact(&mut self, content: &str) -> Result<String, error::Error> {
+        let timeout = self.timeouts.response();
+        self.transact_until(content, timeout).await
+    }
+
+    /// Sends an EPP XML request to the registry and returns the response, overriding the
+    /// configured response timeout
+    pub async fn transact_with_timeout(
+        &mut self,
+        content: &str,
+        timeout: Duration,
+    ) -> Result<String, error::Error> {
+        self.transact_until(content, Some(timeout)).await
+    }
+
+    /// Sends a request and reads the response within the timeout, if any. As the position
+    /// in the stream is unknown after a timeout, the connection is marked as closed
+    async fn transact_until(
+        &mut self,
+        content: &str,
+        timeout: Option<Duration>,
+    ) -> Result<String, error::Error> {
+        if !self.open {
+            return Err(error::Error::EppConnectionClosed);
+        }
+
         debug!("{}: request: {}", self.registry, content);
-        self.send_epp_request(&content).await?;
 
-        let response = self.get_epp_response().await?;
+        let result = with_timeout(timeout, "Command", async {
+            if let Err(e) = self.send_epp_request(content).await {
+                self.open = false;
+                return Err(e);
+            }
+
+            self.get_epp_response().await
+        })
+        .await;
+
+        let response = match result {
+            Ok(response) => response,
+            Err(e) => {
+                if let error::Error::EppTimeoutError(_) = e {
+                    warn!("{}: {}, closing session", self.registry, e);
+                    self.open = false;
+                }
+                return Err(e);
+            }
+        };
         debug!("{}: response: {}", self.registry, response);
 
+        if let Some(recorder) = &self.recorder {
+            recorder.exchange(content, &response);
+        }
+
         Ok(response)
     }
 
-    /// Closes the socket
-    async fn close(&mut self) -> Result<(), Box<dyn Error>> {
+    /// Returns false if a previous read or write on the connection failed or the session was ended
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Marks the connection as unusable, e.g. when the registry has announced that it is
+    /// ending the session
+    pub(crate) fn set_closed(&mut self) {
+        self.open = false;
+    }
+
+    /// Shuts down the stream and marks the connection as closed
+    pub async fn close(&mut self) -> Result<(), error::Error> {
         info!("{}: Closing connection", self.registry);
 
+        self.open = false;
         self.stream.writer.shutdown().await?;
         Ok(())
     }
 }
 
-impl Drop for EppConnection {
-    fn drop(&mut self) {
-        block_on(self.close());
-    }
-}
-
-/// Establishes a TLS connection to a registry and returns a ConnectionStream instance containing the
-/// socket stream to read/write to the connection
-pub async fn epp_connect(registry_creds: &EppClientConnection) -> Result<ConnectionStream, error::Error> {
+/// Establishes a connection to a registry and returns a ConnectionStream instance containing the
+/// socket stream to read/write to the connection. The connection is secured with TLS unless it
+/// is disabled in the connection details
+pub async fn epp_connect(
+    registry_creds: &EppClientConnection,
+) -> Result<ConnectionStream, error::Error> {
     let (host, port) = registry_creds.connection_details();
+    let timeouts = registry_creds.timeouts();
 
     info!("Connecting: EPP Server: {} Port: {}", host, port);
 
-    let addr = (host.as_str(), port)
-        .to_socket_addrs()?
-        .next()
-        .ok_or_else(|| stdio::ErrorKind::NotFound)?;
+    let stream = with_timeout(timeouts.connect(), "Connect", async {
+        let addr = lookup_host((host.as_str(), port))
+            .await
+            .map_err(|e| error::Error::EppResolutionError(format!("{}: {}", host, e)))?
+            .next()
+            .ok_or_else(|| {
+                error::Error::EppResolutionError(format!("{}: No addresses found", host))
+            })?;
+
+        TcpStream::connect(&addr)
+            .await
+            .map_err(error::Error::EppConnectError)
+    })
+    .await?;
+
+    match registry_creds.tls() {
+        true => tls_connect(registry_creds, stream).await,
+        false => {
+            warn!("{}: Connecting over plain TCP without TLS", host);
+            Ok(ConnectionStream::new(stream))
+        }
+    }
+}
+
+/// Performs the TLS handshake with the registry over an established transport, such as a
+/// tunnel through a proxy, and returns a ConnectionStream for the secured connection
+pub async fn tls_connect<T: EppTransport + 'static>(
+    registry_creds: &EppClientConnection,
+    transport: T,
+) -> Result<ConnectionStream, error::Error> {
+    let server_name = registry_creds.server_name();
+    let timeouts = registry_creds.timeouts();
 
     let mut config = ClientConfig::new();
 
-    config
-        .root_store
-        .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+    if registry_creds.bundled_roots() {
+        config
+            .root_store
+            .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+    }
 
-    if let Some(tls) = registry_creds.tls_files() {
-        if let Err(e) = config.set_single_client_cert(tls.0, tls.1) {
-            return Err(format!("Failed to set client TLS credentials: {}", e).into())
-        }
+    for cert in registry_creds.root_certs()? {
+        config
+            .root_store
+            .add(&cert)
+            .map_err(|e| error::Error::EppTlsError(format!("Invalid root certificate: {}", e)))?;
     }
 
-    let connector = TlsConnector::from(Arc::new(config));
-    let stream = TcpStream::connect(&addr).await?;
+    if config.root_store.is_empty() {
+        return Err(error::Error::EppTlsError(
+            "No root certificates to trust".to_string(),
+        ));
+    }
 
-    let domain = DNSNameRef::try_from_ascii_str(&host)
-        .map_err(|_| stdio::Error::new(stdio::ErrorKind::InvalidInput, format!("Invalid domain: {}", host)))?;
+    let spki_pins = registry_creds.spki_pins()?;
+    if !spki_pins.is_empty() {
+        config
+            .dangerous()
+            .set_certificate_verifier(Arc::new(PinnedCertVerifier::new(spki_pins)));
+    }
 
-    let stream = connector.connect(domain, stream).await?;
+    if let Some(tls) = registry_creds.tls_files()? {
+        if let Err(e) = config.set_single_client_cert(tls.0, tls.1) {
+            return Err(error::Error::EppTlsError(format!(
+                "Failed to set client TLS credentials: {}",
+                e
+            )));
+        }
+    }
 
-    let (reader, writer) = split(stream);
+    let connector = TlsConnector::from(Arc::new(config));
 
-    Ok(ConnectionStream {
-        reader: reader,
-        writer: writer,
+    let domain = DNSNameRef::try_from_ascii_str(&server_name).map_err(|_| {
+        error::Error::EppTlsError(format!(
+            "Invalid server name: {}, set server_name when connecting by IP address",
+            server_name
+        ))
+    })?;
+
+    let stream = with_timeout(timeouts.handshake(), "TLS handshake", async {
+        connector
+            .connect(domain, transport)
+            .await
+            .map_err(|e| error::Error::EppTlsError(format!("TLS handshake failed: {}", e)))
     })
+    .await?;
+
+    Ok(ConnectionStream::new(stream))
 }
@@ -0,0 +1,231 @@
+//! Parses the PEM encoded certificates and private keys used for TLS and verifies pinned
+//! server certificates
+//!
+//! Private keys can be RSA (PKCS#1), PKCS#8 or SEC1 EC keys. As rustls only accepts EC keys
+//! in PKCS#8 form, SEC1 keys on the P-256 and P-384 curves are wrapped in a PKCS#8 structure.
+
+use ring::digest::{digest, SHA256};
+use rustls::{
+    sign, Certificate, PrivateKey, RootCertStore, ServerCertVerified, ServerCertVerifier, TLSError,
+    WebPKIVerifier,
+};
+use rustls_pemfile::Item;
+use std::io;
+use tokio_rustls::webpki::DNSNameRef;
+
+use crate::error;
+
+/// DER encoded OID of the id-ecPublicKey algorithm (1.2.840.10045.2.1)
+const EC_PUBLIC_KEY_OID: &[u8] = &[0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+/// DER encoded OID of the P-256 curve (1.2.840.10045.3.1.7)
+const P256_OID: &[u8] = &[0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+/// DER encoded OID of the P-384 curve (1.3.132.0.34)
+const P384_OID: &[u8] = &[0x06, 0x05, 0x2b, 0x81, 0x04, 0x00, 0x22];
+
+const SEQUENCE: u8 = 0x30;
+const INTEGER: u8 = 0x02;
+const OCTET_STRING: u8 = 0x04;
+const CURVE_PARAMETERS: u8 = 0xa0;
+const EXPLICIT_VERSION: u8 = 0xa0;
+
+/// Verifies the server certificate chain against the trusted roots like the default rustls
+/// verifier, and additionally requires the public key of the server certificate to match one
+/// of the pinned SHA-256 SPKI hashes
+pub struct PinnedCertVerifier {
+    pins: Vec<Vec<u8>>,
+    verifier: WebPKIVerifier,
+}
+
+impl PinnedCertVerifier {
+    /// Creates a verifier accepting the given SHA-256 hashes of SubjectPublicKeyInfo structures
+    pub fn new(pins: Vec<Vec<u8>>) -> PinnedCertVerifier {
+        PinnedCertVerifier {
+            pins,
+            verifier: WebPKIVerifier::new(),
+        }
+    }
+}
+
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        roots: &RootCertStore,
+        presented_certs: &[Certificate],
+        dns_name: DNSNameRef,
+        ocsp_response: &[u8],
+    ) -> Result<ServerCertVerified, TLSError> {
+        let verified =
+            self.verifier
+                .verify_server_cert(roots, presented_certs, dns_name, ocsp_response)?;
+
+        let spki_hash = presented_certs
+            .first()
+            .and_then(spki_sha256)
+            .ok_or_else(|| TLSError::General("Invalid server certificate".to_string()))?;
+
+        match self.pins.contains(&spki_hash) {
+            true => Ok(verified),
+            false => Err(TLSError::General(format!(
+                "Server certificate public key {} does not match any pin",
+                base64::encode(&spki_hash)
+            ))),
+        }
+    }
+}
+
+/// Decodes a base64 SHA-256 SPKI pin, as printed by e.g.
+/// `openssl x509 -pubkey -noout | openssl pkey -pubin -outform der | openssl dgst -sha256 -binary | base64`
+pub fn decode_spki_pin(pin: &str) -> Result<Vec<u8>, error::Error> {
+    match base64::decode(pin.trim()) {
+        Ok(hash) if hash.len() == SHA256.output_len => Ok(hash),
+        _ => Err(error::Error::EppTlsError(format!(
+            "Invalid SHA-256 SPKI pin: {}",
+            pin
+        ))),
+    }
+}
+
+/// Returns the SHA-256 hash of the SubjectPublicKeyInfo of a certificate
+pub fn spki_sha256(cert: &Certificate) -> Option<Vec<u8>> {
+    let (_, cert, _) = der_element(&cert.0)?;
+    let (_, mut tbs, _) = der_element(cert)?;
+
+    if tbs.first() == Some(&EXPLICIT_VERSION) {
+        tbs = der_element(tbs)?.2;
+    }
+
+    // The serial number, signature algorithm, issuer, validity and subject precede the SPKI
+    for _ in 0..5 {
+        tbs = der_element(tbs)?.2;
+    }
+
+    let (_, _, rest) = der_element(tbs)?;
+    let spki = &tbs[..tbs.len() - rest.len()];
+
+    Some(digest(&SHA256, spki).as_ref().to_vec())
+}
+
+/// Parses all certificates in PEM encoded data, failing if there are none
+pub fn certs_from_pem(pem: &[u8]) -> Result<Vec<Certificate>, error::Error> {
+    let certs = rustls_pemfile::certs(&mut io::BufReader::new(pem))
+        .map_err(|e| error::Error::EppTlsError(format!("Failed to parse certificates: {}", e)))?;
+
+    if certs.is_empty() {
+        return Err(error::Error::EppTlsError(
+            "No certificates found".to_string(),
+        ));
+    }
+
+    Ok(certs.into_iter().map(Certificate).collect())
+}
+
+/// Parses the first RSA, PKCS#8 or SEC1 EC private key in PEM encoded data and checks that
+/// it can be used for signing
+pub fn private_key_from_pem(pem: &[u8]) -> Result<PrivateKey, error::Error> {
+    let mut reader = io::BufReader::new(pem);
+
+    let key = loop {
+        let item = rustls_pemfile::read_one(&mut reader).map_err(|e| {
+            error::Error::EppTlsError(format!("Failed to parse private key: {}", e))
+        })?;
+
+        match item {
+            Some(Item::RSAKey(key)) | Some(Item::PKCS8Key(key)) => break PrivateKey(key),
+            Some(Item::ECKey(key)) => break PrivateKey(sec1_to_pkcs8(&key)?),
+            Some(_) => continue,
+            None => {
+                return Err(error::Error::EppTlsError(
+                    "No RSA, PKCS#8 or EC private key found".to_string(),
+                ))
+            }
+        }
+    };
+
+    if sign::any_supported_type(&key).is_err() {
+        return Err(error::Error::EppTlsError(
+            "Unsupported private key type".to_string(),
+        ));
+    }
+
+    Ok(key)
+}
+
+/// Wraps a SEC1 EC private key in a PKCS#8 structure, using the curve named in its parameters
+fn sec1_to_pkcs8(sec1: &[u8]) -> Result<Vec<u8>, error::Error> {
+    let curve = sec1_curve(sec1).ok_or_else(|| {
+        error::Error::EppTlsError("Invalid EC private key or unsupported curve".to_string())
+    })?;
+
+    let mut algorithm = EC_PUBLIC_KEY_OID.to_vec();
+    algorithm.extend_from_slice(curve);
+
+    let mut pkcs8 = vec![INTEGER, 0x01, 0x00];
+    pkcs8.extend(der(SEQUENCE, &algorithm));
+    pkcs8.extend(der(OCTET_STRING, sec1));
+
+    Ok(der(SEQUENCE, &pkcs8))
+}
+
+/// Returns the OID of the curve of a SEC1 EC private key, if it is P-256 or P-384
+fn sec1_curve(sec1: &[u8]) -> Option<&'static [u8]> {
+    let (tag, mut key, _) = der_element(sec1)?;
+    if tag != SEQUENCE {
+        return None;
+    }
+
+    // The version and private key precede the optional curve parameters
+    for expected in &[INTEGER, OCTET_STRING] {
+        let (tag, _, rest) = der_element(key)?;
+        if tag != *expected {
+            return None;
+        }
+        key = rest;
+    }
+
+    let (tag, curve, _) = der_element(key)?;
+    if tag != CURVE_PARAMETERS {
+        return None;
+    }
+
+    [P256_OID, P384_OID]
+        .iter()
+        .find(|oid| **oid == curve)
+        .copied()
+}
+
+/// Splits a DER element off the start of the input, returning its tag, contents and the
+/// remaining input
+fn der_element(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, input) = input.split_first()?;
+    let (&first, input) = input.split_first()?;
+
+    let (len, input) = match first {
+        len if len < 0x80 => (len as usize, input),
+        0x81 => (*input.first()? as usize, &input[1..]),
+        0x82 => (
+            u16::from_be_bytes([*input.first()?, *input.get(1)?]) as usize,
+            &input[2..],
+        ),
+        _ => return None,
+    };
+
+    if input.len() < len {
+        return None;
+    }
+
+    Some((tag, &input[..len], &input[len..]))
+}
+
+/// Encodes a DER element with the given tag and contents
+fn der(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+
+    match contents.len() {
+        len if len < 0x80 => out.push(len as u8),
+        len if len <= 0xff => out.extend(&[0x81, len as u8]),
+        len => out.extend(&[0x82, (len >> 8) as u8, len as u8]),
+    }
+
+    out.extend_from_slice(contents);
+    out
+}
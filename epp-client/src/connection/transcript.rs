@@ -0,0 +1,462 @@
+//! Records EPP sessions into transcripts and replays them against an EppClient
+//!
+//! A [`TranscriptRecorder`] set on the connection details captures the greeting and every
+//! request and response of the session, with passwords redacted. The transcript can be saved
+//! to a file and later served by a [`ReplayTransport`], which answers each request with the
+//! recorded response and fails the write if the client sends a different request than the
+//! one recorded. Client transaction ids and passwords are ignored when comparing requests.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use epp_client::EppClient;
+//! use epp_client::connection::registry::ConnectionStream;
+//! use epp_client::connection::transcript::{ReplayTransport, Transcript, TranscriptRecorder};
+//! use epp_client::epp::{EppDomainCheck, EppDomainCheckResponse};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // Record a session with the registry
+//!     let recorder = TranscriptRecorder::new();
+//!     let mut client = EppClient::builder()
+//!         .host("epp.verisign-grs.com", 700)
+//!         .credentials("username", "password")
+//!         .record(recorder.clone())
+//!         .connect()
+//!         .await
+//!         .unwrap();
+//!
+//!     let domain_check = EppDomainCheck::new(vec!["eppdev.com"], "cltrid:1");
+//!     client.transact::<_, EppDomainCheckResponse>(&domain_check).await.unwrap();
+//!     client.close().await.unwrap();
+//!
+//!     recorder.transcript().save("session.epp").unwrap();
+//!
+//!     // Replay it later without a registry
+//!     let replay = ReplayTransport::new(Transcript::load("session.epp").unwrap());
+//!     let mut client = EppClient::builder()
+//!         .host("epp.verisign-grs.com", 700)
+//!         .credentials("username", "password")
+//!         .connect_with_stream(ConnectionStream::new(replay))
+//!         .await
+//!         .unwrap();
+//!
+//!     let domain_check = EppDomainCheck::new(vec!["eppdev.com"], "cltrid:2");
+//!     client.transact::<_, EppDomainCheckResponse>(&domain_check).await.unwrap();
+//!     client.close().await.unwrap();
+//! }
+//! ```
+//!
+//! ## Format
+//!
+//! Transcripts are text files holding the EPP messages of the session in order, each
+//! preceded by a line with `S:` for messages from the registry or `C:` for messages from the
+//! client and the length of the message in bytes. The first message is the greeting.
+
+use bytes::BytesMut;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io as stdio;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::connection::codec::EppCodec;
+use crate::error;
+
+/// The text that redacted passwords are replaced with
+pub const REDACTED: &str = "REDACTED";
+
+/// Elements holding passwords, which are redacted in transcripts
+const PASSWORD_ELEMENTS: &[&str] = &["pw", "newPW"];
+
+/// A request and the response the registry sent to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exchange {
+    pub request: String,
+    pub response: String,
+}
+
+/// The greeting and the exchanges of an EPP session
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Transcript {
+    pub greeting: String,
+    pub exchanges: Vec<Exchange>,
+}
+
+impl Transcript {
+    /// Creates a transcript of a session that started with the greeting
+    pub fn new(greeting: &str) -> Transcript {
+        Transcript {
+            greeting: greeting.to_string(),
+            exchanges: Vec::new(),
+        }
+    }
+
+    /// Appends a request and its response to the transcript, redacting passwords
+    pub fn push(&mut self, request: &str, response: &str) {
+        self.exchanges.push(Exchange {
+            request: redact(request),
+            response: redact(response),
+        });
+    }
+
+    /// Parses a transcript in the text format written by `to_string()`
+    pub fn parse(text: &str) -> Result<Transcript, error::Error> {
+        let mut rest = text;
+        let mut messages = Vec::new();
+
+        while !rest.trim().is_empty() {
+            let (message, remainder) = next_message(rest)?;
+            messages.push(message);
+            rest = remainder;
+        }
+
+        let mut messages = messages.into_iter();
+        let greeting = match messages.next() {
+            Some(('S', greeting)) => greeting,
+            _ => {
+                return Err(error::Error::EppTranscriptError(
+                    "Transcript does not start with a greeting".to_string(),
+                ))
+            }
+        };
+
+        let mut transcript = Transcript::new(greeting);
+        while let Some(message) = messages.next() {
+            match (message, messages.next()) {
+                (('C', request), Some(('S', response))) => transcript.exchanges.push(Exchange {
+                    request: request.to_string(),
+                    response: response.to_string(),
+                }),
+                _ => {
+                    return Err(error::Error::EppTranscriptError(format!(
+                        "Request {} is not followed by a response",
+                        transcript.exchanges.len() + 1
+                    )))
+                }
+            }
+        }
+
+        Ok(transcript)
+    }
+
+    /// Reads a transcript from a file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Transcript, error::Error> {
+        let text = std::fs::read_to_string(path.as_ref()).map_err(|e| {
+            error::Error::EppTranscriptError(format!("{}: {}", path.as_ref().display(), e))
+        })?;
+
+        Transcript::parse(&text)
+    }
+
+    /// Writes the transcript to a file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), error::Error> {
+        std::fs::write(path.as_ref(), self.to_string()).map_err(|e| {
+            error::Error::EppTranscriptError(format!("{}: {}", path.as_ref().display(), e))
+        })
+    }
+}
+
+impl fmt::Display for Transcript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "S: {}\n{}", self.greeting.len(), self.greeting)?;
+
+        for exchange in &self.exchanges {
+            writeln!(f, "C: {}\n{}", exchange.request.len(), exchange.request)?;
+            writeln!(f, "S: {}\n{}", exchange.response.len(), exchange.response)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits the next message, with the side that sent it, off the text of a transcript
+fn next_message(text: &str) -> Result<((char, &str), &str), error::Error> {
+    let invalid = |reason: &str| error::Error::EppTranscriptError(reason.to_string());
+
+    let text = text.trim_start();
+    let (header, rest) = text
+        .split_once('\n')
+        .ok_or_else(|| invalid("Missing message after header"))?;
+
+    let (side, len) = match header.trim_end().split_once(": ") {
+        Some(("S", len)) => ('S', len),
+        Some(("C", len)) => ('C', len),
+        _ => return Err(invalid(&format!("Invalid message header: {}", header))),
+    };
+    let len: usize = len
+        .parse()
+        .map_err(|_| invalid(&format!("Invalid message length: {}", header)))?;
+
+    match rest.get(..len) {
+        Some(message) => Ok(((side, message), &rest[len..])),
+        None => Err(invalid(&format!("Truncated message: {}", header))),
+    }
+}
+
+/// Replaces the contents of the elements with the given local names, such as `pw` for
+/// &lt;pw&gt; and &lt;domain:pw&gt;, with the replacement. The elements are found with the
+/// quick-xml reader, so comments, CDATA sections and attribute values are not mistaken for
+/// them, and everything after invalid XML is replaced, as it may hold a password the reader
+/// could not find
+fn mask(xml: &str, names: &[&str], replacement: &str) -> String {
+    let mut reader = Reader::from_str(xml);
+    reader.check_end_names(false);
+
+    let mut buf = Vec::new();
+    let mut masked = String::with_capacity(xml.len());
+    let mut copied = 0;
+    // The offset right after the start tag of the element being masked, if any, and the depth
+    // of the elements in it
+    let mut text_start = None;
+    let mut depth = 0;
+
+    loop {
+        let offset = reader.buffer_position();
+        let event = match reader.read_event(&mut buf) {
+            Ok(Event::Eof) => break,
+            // The attributes are only parsed on demand, so tags with invalid attributes, such
+            // as an unclosed quote swallowing the rest of the message, are checked here
+            Ok(Event::Start(e)) | Ok(Event::Empty(e))
+                if e.attributes().any(|attribute| attribute.is_err()) =>
+            {
+                text_start = text_start.or(Some(offset));
+                break;
+            }
+            Ok(event) => event,
+            Err(_) => {
+                text_start = text_start.or(Some(offset));
+                break;
+            }
+        };
+
+        match (event, text_start) {
+            (Event::Start(_), Some(_)) => depth += 1,
+            (Event::End(_), Some(_)) if depth > 0 => depth -= 1,
+            (Event::End(_), Some(start)) => {
+                masked.push_str(&xml[copied..start]);
+                if offset > start {
+                    masked.push_str(replacement);
+                }
+                copied = offset;
+                text_start = None;
+            }
+            (_, Some(_)) => {}
+            (Event::Start(e), None)
+                if names.iter().any(|name| e.local_name() == name.as_bytes()) =>
+            {
+                text_start = Some(reader.buffer_position());
+            }
+            _ => {}
+        }
+
+        buf.clear();
+    }
+
+    // An element that is not closed, or the rest after invalid XML, is replaced to the end
+    match text_start {
+        Some(start) => {
+            masked.push_str(&xml[copied..start]);
+            if xml.len() > start {
+                masked.push_str(replacement);
+            }
+        }
+        None => masked.push_str(&xml[copied..]),
+    }
+    masked
+}
+
+/// Replaces the passwords in an EPP XML message, such as the login password and the
+/// authInfo of objects, with `REDACTED`
+pub fn redact(xml: &str) -> String {
+    mask(xml, PASSWORD_ELEMENTS, REDACTED)
+}
+
+/// Returns a request with the parts that differ between sessions, the passwords and the
+/// client transaction id, masked for comparing it to a recorded request
+fn comparable(request: &str) -> String {
+    let names: Vec<&str> = PASSWORD_ELEMENTS
+        .iter()
+        .copied()
+        .chain(["clTRID"])
+        .collect();
+
+    mask(request, &names, REDACTED).trim().to_string()
+}
+
+/// Records the session of a connection into a transcript. Clones share the same transcript,
+/// so a recorder can be set on the connection details and read after the session
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptRecorder {
+    transcript: Arc<Mutex<Transcript>>,
+}
+
+impl TranscriptRecorder {
+    /// Creates a recorder with an empty transcript
+    pub fn new() -> TranscriptRecorder {
+        TranscriptRecorder::default()
+    }
+
+    /// Returns the transcript recorded so far
+    pub fn transcript(&self) -> Transcript {
+        self.transcript.lock().unwrap().clone()
+    }
+
+    /// Records the greeting of the session. Sessions opened on a reconnect are recorded into
+    /// the same transcript, keeping the first greeting
+    pub(crate) fn greeting(&self, greeting: &str) {
+        let mut transcript = self.transcript.lock().unwrap();
+        if transcript.greeting.is_empty() {
+            transcript.greeting = redact(greeting);
+        }
+    }
+
+    /// Records a request and its response
+    pub(crate) fn exchange(&self, request: &str, response: &str) {
+        self.transcript.lock().unwrap().push(request, response);
+    }
+}
+
+/// Tracks how far a `ReplayTransport` has replayed its transcript
+#[derive(Debug, Clone)]
+pub struct ReplayProgress {
+    remaining: Arc<AtomicUsize>,
+}
+
+impl ReplayProgress {
+    /// Returns the number of recorded requests the client has not sent yet
+    pub fn remaining(&self) -> usize {
+        self.remaining.load(Ordering::SeqCst)
+    }
+
+    /// Returns whether the client has sent all recorded requests
+    pub fn is_finished(&self) -> bool {
+        self.remaining() == 0
+    }
+}
+
+/// An in-memory transport that plays the registry side of a recorded session. It sends the
+/// recorded greeting, answers each request with the recorded response and fails the write with
+/// an `InvalidData` error if a request differs from the recorded one
+pub struct ReplayTransport {
+    codec: EppCodec,
+    exchanges: VecDeque<Exchange>,
+    position: usize,
+    input: BytesMut,
+    output: BytesMut,
+    remaining: Arc<AtomicUsize>,
+}
+
+impl ReplayTransport {
+    /// Creates a transport that replays the transcript
+    pub fn new(transcript: Transcript) -> ReplayTransport {
+        let codec = EppCodec::default();
+
+        let mut output = BytesMut::new();
+        // The greeting fits in a frame, as it was read from one when recording
+        let _ = codec.encode(transcript.greeting.as_bytes(), &mut output);
+
+        ReplayTransport {
+            codec,
+            remaining: Arc::new(AtomicUsize::new(transcript.exchanges.len())),
+            exchanges: transcript.exchanges.into(),
+            position: 0,
+            input: BytesMut::new(),
+            output,
+        }
+    }
+
+    /// Returns a handle to check the progress of the replay after the transport has been
+    /// handed to a client
+    pub fn progress(&self) -> ReplayProgress {
+        ReplayProgress {
+            remaining: self.remaining.clone(),
+        }
+    }
+
+    /// Checks a request from the client against the transcript and queues the recorded response
+    fn replay(&mut self, request: &[u8]) -> stdio::Result<()> {
+        let invalid = |message: String| stdio::Error::new(stdio::ErrorKind::InvalidData, message);
+
+        self.position += 1;
+        let request = String::from_utf8_lossy(request);
+
+        let exchange = self.exchanges.pop_front().ok_or_else(|| {
+            invalid(format!(
+                "Unexpected request {}, the transcript has ended: {}",
+                self.position, request
+            ))
+        })?;
+
+        if comparable(&request) != comparable(&exchange.request) {
+            return Err(invalid(format!(
+                "Request {} differs from the transcript, expected: {} got: {}",
+                self.position, exchange.request, request
+            )));
+        }
+
+        self.codec
+            .encode(exchange.response.as_bytes(), &mut self.output)
+            .map_err(|e| invalid(e.to_string()))?;
+        self.remaining.store(self.exchanges.len(), Ordering::SeqCst);
+
+        Ok(())
+    }
+}
+
+impl AsyncRead for ReplayTransport {
+    /// Reads the queued greeting or response. Once the client has to send a request first,
+    /// reads return end of file
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<stdio::Result<()>> {
+        let this = self.get_mut();
+
+        let len = buf.remaining().min(this.output.len());
+        buf.put_slice(&this.output.split_to(len));
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for ReplayTransport {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<stdio::Result<usize>> {
+        let this = self.get_mut();
+        this.input.extend_from_slice(buf);
+
+        loop {
+            match this.codec.decode(&mut this.input) {
+                Ok(Some(request)) => this.replay(&request)?,
+                Ok(None) => break,
+                Err(e) => {
+                    return Poll::Ready(Err(stdio::Error::new(
+                        stdio::ErrorKind::InvalidData,
+                        e.to_string(),
+                    )))
+                }
+            }
+        }
+
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<stdio::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<stdio::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
@@ -9,6 +9,7 @@ pub use request::contact::check::*;
 pub use request::contact::create::*;
 pub use request::contact::delete::*;
 pub use request::contact::info::*;
+pub use request::contact::transfer::*;
 pub use request::contact::update::*;
 pub use request::domain::check::*;
 pub use request::domain::create::*;
@@ -31,6 +32,7 @@ pub use response::contact::check::*;
 pub use response::contact::create::*;
 pub use response::contact::delete::*;
 pub use response::contact::info::*;
+pub use response::contact::transfer::*;
 pub use response::contact::update::*;
 pub use response::domain::check::*;
 pub use response::domain::create::*;
@@ -1,6 +1,7 @@
 //! Data types common to EPP Requests and Responses
 
 pub mod data;
+pub mod datetime;
 
 use epp_client_macros::*;
 use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
@@ -25,6 +26,14 @@ impl Display for StringValue {
     }
 }
 
+/// Serializes a StringValue as the inner text of its parent tag, for fields renamed to `$value`
+pub(crate) fn serialize_text<S: Serializer>(
+    value: &StringValue,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.0)
+}
+
 /// Trait for StringValue type to add easier conversion from str and String
 pub trait StringValueTrait {
     fn to_string_value(&self) -> StringValue;
@@ -45,9 +54,15 @@ impl StringValueTrait for String {
 /// Trait to set correct value for xml tags when tags are being generated from generic types
 pub trait ElementName {
     fn element_name(&self) -> &'static str;
+
+    /// Returns true for requests that do not change anything at the registry, which the
+    /// client can safely resend on a new session if the registry ends the session first
+    fn is_idempotent(&self) -> bool {
+        false
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, ElementName)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, ElementName)]
 #[element_name(name = "empty")]
 /// An empty placeholder tag. To be refactored to something more compliant later.
 pub struct EmptyTag;
@@ -66,7 +81,7 @@ pub struct EppObject<T: ElementName> {
     #[serde(rename = "xsi:schemaLocation")]
     pub xsi_schema_location: String,
     /// the request or response object that is set or received in the EPP XML document
-    #[serde(alias = "greeting", alias = "response")]
+    #[serde(alias = "greeting", alias = "response", alias = "command")]
     pub data: T,
     // TODO: save serialized xml in the instance for debugging or client logging purposes
     // #[serde(skip)]
@@ -132,7 +147,7 @@ impl<E: ElementName + Serialize> Serialize for Extension<E> {
 }
 
 /// The <svcExtension> type in EPP XML
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 #[serde(rename = "svcExtension")]
 pub struct ServiceExtension {
     /// The service extension URIs being represented by <extURI> in EPP XML
@@ -1,48 +1,86 @@
 //! Common data types included in EPP Requests and Responses
 
-use crate::epp::object::{StringValue, StringValueTrait};
-use serde::{Deserialize, Serialize};
-
-/// The &lt;status&gt; attribute on EPP XML for domain transactions
-pub type DomainStatus = ContactStatus;
-/// The &lt;status&gt; attribute on EPP XML for host transactions
-pub type HostStatus = ContactStatus;
+use crate::epp::object::{serialize_text, EmptyTag, StringValue, StringValueTrait};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
 /// The &lt;hostAddr&gt; types domain or host transactions
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct HostAddr {
+    /// The IP address, serialized with an 'ip' attr of "v4" or "v6" matching its family
+    pub address: IpAddr,
+}
+
+/// The &lt;addr&gt; tag as it appears in EPP XML, with the address as text
+#[derive(Serialize, Deserialize)]
+struct RawHostAddr {
     #[serde(rename = "ip")]
-    pub ip_version: Option<String>,
+    ip_version: Option<String>,
     #[serde(rename = "$value")]
-    pub address: String,
+    address: String,
 }
 
-impl HostAddr {
-    /// Creates a 'v4' type HostAddr (mostly useful when you don't want to include an 'ip' attr in the XML)
-    pub fn new(ip_version: &str, address: &str) -> HostAddr {
-        HostAddr {
-            ip_version: Some(ip_version.to_string()),
-            address: address.to_string(),
+impl Serialize for HostAddr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawHostAddr {
+            ip_version: Some(self.ip_version().to_string()),
+            address: self.address.to_string(),
         }
+        .serialize(serializer)
     }
+}
 
-    /// Creates a 'v4' type HostAddr
-    pub fn new_v4(address: &str) -> HostAddr {
-        HostAddr {
-            ip_version: Some("v4".to_string()),
-            address: address.to_string(),
+impl<'de> Deserialize<'de> for HostAddr {
+    /// Parses the address and checks it against the 'ip' attr, which defaults to "v4"
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawHostAddr::deserialize(deserializer)?;
+        let address = raw.address.trim().parse::<IpAddr>().map_err(|e| {
+            de::Error::custom(format!("invalid host address {}: {}", raw.address, e))
+        })?;
+
+        let ip_version = raw.ip_version.as_deref().unwrap_or("v4");
+        match (ip_version, address) {
+            ("v4", IpAddr::V4(_)) | ("v6", IpAddr::V6(_)) => Ok(HostAddr { address }),
+            _ => Err(de::Error::custom(format!(
+                "host address {} does not match ip type {}",
+                address, ip_version
+            ))),
         }
     }
+}
+
+impl HostAddr {
+    /// Creates a HostAddr for an IPv4 or IPv6 address
+    pub fn new(address: IpAddr) -> HostAddr {
+        HostAddr { address }
+    }
+
+    /// Creates a 'v4' type HostAddr
+    pub fn new_v4(address: Ipv4Addr) -> HostAddr {
+        HostAddr::new(IpAddr::V4(address))
+    }
 
     /// Creates a 'v6' type HostAddr
-    pub fn new_v6(address: &str) -> HostAddr {
-        HostAddr {
-            ip_version: Some("v6".to_string()),
-            address: address.to_string(),
+    pub fn new_v6(address: Ipv6Addr) -> HostAddr {
+        HostAddr::new(IpAddr::V6(address))
+    }
+
+    /// Returns the 'ip' attr for the address, "v4" or "v6"
+    pub fn ip_version(&self) -> &'static str {
+        match self.address {
+            IpAddr::V4(_) => "v4",
+            IpAddr::V6(_) => "v6",
         }
     }
 }
 
+impl From<IpAddr> for HostAddr {
+    fn from(address: IpAddr) -> HostAddr {
+        HostAddr::new(address)
+    }
+}
+
 /// The &lt;host&gt; type for host transactions
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Host {
@@ -95,10 +133,10 @@ pub struct DomainContact {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Period {
     /// The interval (usually 'y' indicating years)
-    unit: String,
+    pub unit: String,
     /// The length of the registration, renewal or transfer period (usually in years)
     #[serde(rename = "$value")]
-    length: u16,
+    pub length: u16,
 }
 
 impl Period {
@@ -116,12 +154,149 @@ impl Period {
     }
 }
 
-/// The &lt;status&gt; type on contact transactions
-#[derive(Serialize, Deserialize, Debug)]
-pub struct ContactStatus {
-    /// The status name, represented by the 's' attr on &lt;status&gt; tags
+/// The &lt;status&gt; type on domain, contact and host transactions, holding one of the
+/// `DomainStatus`, `ContactStatus` or `HostStatus` values
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Status<T> {
+    /// The status value, represented by the 's' attr on &lt;status&gt; tags
     #[serde(rename = "s")]
-    pub status: String,
+    pub status: T,
+    /// The language of the reason, represented by the 'lang' attr on &lt;status&gt; tags
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    /// The free-text reason for the status, the inner text of &lt;status&gt; tags
+    #[serde(rename = "$value", default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl<T> Status<T> {
+    /// Creates a status without a reason
+    pub fn new(status: T) -> Status<T> {
+        Status {
+            status,
+            lang: None,
+            reason: None,
+        }
+    }
+
+    /// Sets the free-text reason for the status
+    pub fn set_reason(&mut self, reason: &str) {
+        self.reason = Some(reason.to_string());
+    }
+
+    /// Sets the language of the reason, e.g. 'en'
+    pub fn set_lang(&mut self, lang: &str) {
+        self.lang = Some(lang.to_string());
+    }
+}
+
+/// Defines an enum of status values that is (de)serialized from its name in EPP XML, with an
+/// `Other` variant holding values the enum does not know, such as registry specific statuses
+macro_rules! status_values {
+    ($(#[$meta:meta])* $name:ident { $($(#[$variant_meta:meta])* $variant:ident => $value:expr,)* }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)*
+            /// A status value not defined in the RFC
+            Other(String),
+        }
+
+        impl $name {
+            /// Returns the status value as it appears in EPP XML
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)*
+                    $name::Other(value) => value,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    _ => $name::Other(value.to_string()),
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                Ok($name::from(value.as_str()))
+            }
+        }
+    };
+}
+
+status_values! {
+    /// Domain status values as defined in RFC 5731
+    DomainStatus {
+        ClientDeleteProhibited => "clientDeleteProhibited",
+        ClientHold => "clientHold",
+        ClientRenewProhibited => "clientRenewProhibited",
+        ClientTransferProhibited => "clientTransferProhibited",
+        ClientUpdateProhibited => "clientUpdateProhibited",
+        Inactive => "inactive",
+        Ok => "ok",
+        PendingCreate => "pendingCreate",
+        PendingDelete => "pendingDelete",
+        PendingRenew => "pendingRenew",
+        PendingTransfer => "pendingTransfer",
+        PendingUpdate => "pendingUpdate",
+        ServerDeleteProhibited => "serverDeleteProhibited",
+        ServerHold => "serverHold",
+        ServerRenewProhibited => "serverRenewProhibited",
+        ServerTransferProhibited => "serverTransferProhibited",
+        ServerUpdateProhibited => "serverUpdateProhibited",
+    }
+}
+
+status_values! {
+    /// Contact status values as defined in RFC 5733
+    ContactStatus {
+        ClientDeleteProhibited => "clientDeleteProhibited",
+        ClientTransferProhibited => "clientTransferProhibited",
+        ClientUpdateProhibited => "clientUpdateProhibited",
+        Linked => "linked",
+        Ok => "ok",
+        PendingCreate => "pendingCreate",
+        PendingDelete => "pendingDelete",
+        PendingTransfer => "pendingTransfer",
+        PendingUpdate => "pendingUpdate",
+        ServerDeleteProhibited => "serverDeleteProhibited",
+        ServerTransferProhibited => "serverTransferProhibited",
+        ServerUpdateProhibited => "serverUpdateProhibited",
+    }
+}
+
+status_values! {
+    /// Host status values as defined in RFC 5732
+    HostStatus {
+        ClientDeleteProhibited => "clientDeleteProhibited",
+        ClientUpdateProhibited => "clientUpdateProhibited",
+        Linked => "linked",
+        Ok => "ok",
+        PendingCreate => "pendingCreate",
+        PendingDelete => "pendingDelete",
+        PendingTransfer => "pendingTransfer",
+        PendingUpdate => "pendingUpdate",
+        ServerDeleteProhibited => "serverDeleteProhibited",
+        ServerUpdateProhibited => "serverUpdateProhibited",
+    }
 }
 
 /// The data for &lt;voice&gt; and &lt;fax&gt; types on domain transactions
@@ -144,17 +319,18 @@ pub struct Address {
     pub city: StringValue,
     /// The &lt;sp&gt; tag under &lt;addr&gt;
     #[serde(rename = "sp")]
-    pub province: StringValue,
+    pub province: Option<StringValue>,
     /// The &lt;pc&gt; tag under &lt;addr&gt;
     #[serde(rename = "pc")]
-    pub postal_code: StringValue,
+    pub postal_code: Option<StringValue>,
     /// The &lt;cc&gt; tag under &lt;addr&gt;
     #[serde(rename = "cc")]
     pub country_code: StringValue,
 }
 
-/// The &lt;postalInfo&gt; type on contact transactions
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// The &lt;postalInfo&gt; type on contact transactions. A contact carries at most one
+/// internationalized ("int") and one localized ("loc") postal info
+#[derive(Deserialize, Debug, Clone)]
 pub struct PostalInfo {
     /// The 'type' attr on &lt;postalInfo&gt;
     #[serde(rename = "type")]
@@ -163,18 +339,193 @@ pub struct PostalInfo {
     pub name: StringValue,
     /// The &lt;org&gt; tag under &lt;postalInfo&gt;
     #[serde(rename = "org")]
-    pub organization: StringValue,
+    pub organization: Option<StringValue>,
     /// The &lt;addr&gt; tag under &lt;postalInfo&gt;
     #[serde(rename = "addr")]
     pub address: Address,
 }
 
+/// Borrowed form of PostalInfo that is serialized once the postal info is validated
+#[derive(Serialize)]
+struct ValidatedPostalInfo<'a> {
+    #[serde(rename = "type")]
+    info_type: &'a str,
+    name: &'a StringValue,
+    #[serde(rename = "org")]
+    organization: &'a Option<StringValue>,
+    #[serde(rename = "addr")]
+    address: &'a Address,
+}
+
+impl Serialize for PostalInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.validate().map_err(ser::Error::custom)?;
+
+        ValidatedPostalInfo {
+            info_type: &self.info_type,
+            name: &self.name,
+            organization: &self.organization,
+            address: &self.address,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// The &lt;name&gt;, &lt;org&gt; and &lt;addr&gt; tags under &lt;disclose&gt;, for the
+/// internationalized or localized form of the element
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DiscloseInfoType {
+    /// The 'type' attr, either "int" or "loc"
+    #[serde(rename = "type")]
+    pub info_type: String,
+}
+
+/// The &lt;disclose&gt; tag on contact transactions, listing the contact fields the registry
+/// should or should not publish, e.g. in WHOIS and RDAP
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Disclose {
+    /// The 'flag' attr on &lt;disclose&gt;, true if the listed fields may be published and
+    /// false if they may not
+    #[serde(
+        serialize_with = "serialize_flag",
+        deserialize_with = "deserialize_flag"
+    )]
+    pub flag: bool,
+    /// The &lt;name&gt; tags under &lt;disclose&gt;
+    #[serde(default)]
+    pub name: Vec<DiscloseInfoType>,
+    /// The &lt;org&gt; tags under &lt;disclose&gt;
+    #[serde(rename = "org", default)]
+    pub organization: Vec<DiscloseInfoType>,
+    /// The &lt;addr&gt; tags under &lt;disclose&gt;
+    #[serde(rename = "addr", default)]
+    pub address: Vec<DiscloseInfoType>,
+    /// The &lt;voice&gt; tag under &lt;disclose&gt;
+    pub voice: Option<EmptyTag>,
+    /// The &lt;fax&gt; tag under &lt;disclose&gt;
+    pub fax: Option<EmptyTag>,
+    /// The &lt;email&gt; tag under &lt;disclose&gt;
+    pub email: Option<EmptyTag>,
+}
+
+/// Serializes the 'flag' attr on &lt;disclose&gt; as "1" or "0"
+fn serialize_flag<S: Serializer>(flag: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(if *flag { "1" } else { "0" })
+}
+
+/// Deserializes the 'flag' attr on &lt;disclose&gt;, which is an XML schema boolean
+fn deserialize_flag<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    match String::deserialize(deserializer)?.as_str() {
+        "1" | "true" => Ok(true),
+        "0" | "false" => Ok(false),
+        flag => Err(de::Error::custom(format!(
+            "invalid disclose flag: {}",
+            flag
+        ))),
+    }
+}
+
 /// The &lt;authInfo&gt; tag for domain and contact transactions
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct AuthInfo {
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuthInfo {
     /// The &lt;pw&gt; tag under &lt;authInfo&gt;
+    Pw(Password),
+    /// The &lt;null&gt; tag under &lt;authInfo&gt;, used in place of &lt;pw&gt; to remove the
+    /// authInfo in update requests
+    Null,
+}
+
+/// The &lt;authInfo&gt; tag as it appears in EPP XML, with either a &lt;pw&gt; or a
+/// &lt;null&gt; tag
+#[derive(Serialize, Deserialize)]
+struct RawAuthInfo {
     #[serde(rename = "pw")]
-    pub password: StringValue,
+    password: Option<Password>,
+    null: Option<EmptyTag>,
+}
+
+impl Serialize for AuthInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let raw = match self {
+            AuthInfo::Pw(password) => RawAuthInfo {
+                password: Some(password.clone()),
+                null: None,
+            },
+            AuthInfo::Null => RawAuthInfo {
+                password: None,
+                null: Some(EmptyTag),
+            },
+        };
+        raw.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for AuthInfo {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match RawAuthInfo::deserialize(deserializer)? {
+            RawAuthInfo {
+                password: Some(password),
+                null: None,
+            } => Ok(AuthInfo::Pw(password)),
+            RawAuthInfo {
+                password: None,
+                null: Some(_),
+            } => Ok(AuthInfo::Null),
+            _ => Err(de::Error::custom(
+                "authInfo must have exactly one of pw or null",
+            )),
+        }
+    }
+}
+
+/// The &lt;pw&gt; tag under &lt;authInfo&gt;
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Password {
+    /// The 'roid' attr on &lt;pw&gt;, identifying the contact the authInfo belongs to when a
+    /// contact's authInfo is used to query a domain
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roid: Option<String>,
+    /// The password text
+    #[serde(rename = "$value", default, serialize_with = "serialize_text")]
+    pub value: StringValue,
+}
+
+impl DiscloseInfoType {
+    /// Creates a new DiscloseInfoType for the given type, "int" or "loc"
+    pub fn new(info_type: &str) -> DiscloseInfoType {
+        DiscloseInfoType {
+            info_type: info_type.to_string(),
+        }
+    }
+}
+
+impl Disclose {
+    /// Creates a new Disclose instance with the given flag and no fields listed
+    pub fn new(flag: bool) -> Disclose {
+        Disclose {
+            flag,
+            name: Vec::new(),
+            organization: Vec::new(),
+            address: Vec::new(),
+            voice: None,
+            fax: None,
+            email: None,
+        }
+    }
+
+    /// Lists the name, organization and address in the given form, "int" or "loc"
+    pub fn postal_info(&mut self, info_type: &str) {
+        self.name.push(DiscloseInfoType::new(info_type));
+        self.organization.push(DiscloseInfoType::new(info_type));
+        self.address.push(DiscloseInfoType::new(info_type));
+    }
+
+    /// Lists the voice, fax and email fields
+    pub fn contact_details(&mut self) {
+        self.voice = Some(EmptyTag);
+        self.fax = Some(EmptyTag);
+        self.email = Some(EmptyTag);
+    }
 }
 
 impl Phone {
@@ -195,8 +546,31 @@ impl Phone {
 impl AuthInfo {
     /// Creates an AuthInfo instance with the given password
     pub fn new(password: &str) -> AuthInfo {
-        AuthInfo {
-            password: password.to_string_value(),
+        AuthInfo::Pw(Password {
+            roid: None,
+            value: password.to_string_value(),
+        })
+    }
+
+    /// Creates an AuthInfo instance with the password of the contact with the given ROID
+    pub fn with_roid(password: &str, roid: &str) -> AuthInfo {
+        AuthInfo::Pw(Password {
+            roid: Some(roid.to_string()),
+            value: password.to_string_value(),
+        })
+    }
+
+    /// Creates an AuthInfo instance with a &lt;null&gt; tag, which removes the authInfo
+    /// when sent in an update request
+    pub fn null() -> AuthInfo {
+        AuthInfo::Null
+    }
+
+    /// Returns the password, if the authInfo has one
+    pub fn password(&self) -> Option<&StringValue> {
+        match self {
+            AuthInfo::Pw(password) => Some(&password.value),
+            AuthInfo::Null => None,
         }
     }
 }
@@ -206,8 +580,8 @@ impl Address {
     pub fn new(
         street: Vec<&str>,
         city: &str,
-        province: &str,
-        postal_code: &str,
+        province: Option<&str>,
+        postal_code: Option<&str>,
         country_code: &str,
     ) -> Address {
         let street = street
@@ -218,8 +592,8 @@ impl Address {
         Address {
             street: street,
             city: city.to_string_value(),
-            province: province.to_string_value(),
-            postal_code: postal_code.to_string_value(),
+            province: province.map(|p| p.to_string_value()),
+            postal_code: postal_code.map(|p| p.to_string_value()),
             country_code: country_code.to_string_value(),
         }
     }
@@ -227,12 +601,46 @@ impl Address {
 
 impl PostalInfo {
     /// Creates a new PostalInfo instance
-    pub fn new(info_type: &str, name: &str, organization: &str, address: Address) -> PostalInfo {
+    pub fn new(
+        info_type: &str,
+        name: &str,
+        organization: Option<&str>,
+        address: Address,
+    ) -> PostalInfo {
         PostalInfo {
             info_type: info_type.to_string(),
             name: name.to_string_value(),
-            organization: organization.to_string_value(),
+            organization: organization.map(|o| o.to_string_value()),
             address: address,
         }
     }
+
+    /// Checks that the postal info is either "int" or "loc", and that "int" postal info only
+    /// contains 7-bit ASCII characters as required by RFC 5733. Requests with invalid postal
+    /// info fail to serialize
+    pub fn validate(&self) -> Result<(), String> {
+        match self.info_type.as_str() {
+            "int" => (),
+            "loc" => return Ok(()),
+            info_type => return Err(format!("invalid postalInfo type: {}", info_type)),
+        }
+
+        let address = &self.address;
+        let mut fields = vec![&self.name, &address.city, &address.country_code];
+        fields.extend(self.organization.iter());
+        fields.extend(address.street.iter());
+        fields.extend(address.province.iter());
+        fields.extend(address.postal_code.iter());
+
+        for field in fields {
+            if !field.0.is_ascii() {
+                return Err(format!(
+                    "int postalInfo contains non-ASCII characters: {}",
+                    field
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
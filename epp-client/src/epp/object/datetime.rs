@@ -0,0 +1,78 @@
+//! (De)serialization of EPP dates into `chrono::DateTime<Utc>`, for use with
+//! `#[serde(with = "datetime")]` on required and `#[serde(default, with = "datetime::optional")]`
+//! on optional fields
+//!
+//! Dates are parsed as RFC 3339 timestamps, with or without fractional seconds and in any
+//! offset, which is converted to UTC. Timestamps without an offset and plain dates, as sent by
+//! some registries, are taken to be in UTC.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::epp::object::StringValue;
+
+/// Parses an EPP date, returning an error message naming the date if it is malformed
+pub fn parse(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Ok(datetime.with_timezone(&Utc));
+    }
+
+    if let Ok(datetime) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f") {
+        return Ok(Utc.from_utc_datetime(&datetime));
+    }
+
+    match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Ok(date) => Ok(Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap_or_default())),
+        Err(_) => Err(format!("invalid date: {}", value)),
+    }
+}
+
+/// Formats a date as an RFC 3339 timestamp in UTC, with fractional seconds only if needed
+pub fn format(datetime: &DateTime<Utc>) -> String {
+    datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+/// Serializes a date as the inner text of its tag
+pub fn serialize<S: Serializer>(
+    datetime: &DateTime<Utc>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    StringValue(format(datetime)).serialize(serializer)
+}
+
+/// Deserializes a date from the inner text of its tag
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    parse(&value).map_err(de::Error::custom)
+}
+
+/// (De)serialization of optional EPP dates
+pub mod optional {
+    use chrono::{DateTime, Utc};
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::epp::object::StringValue;
+
+    /// Serializes a date, if any, as the inner text of its tag
+    pub fn serialize<S: Serializer>(
+        datetime: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        datetime
+            .as_ref()
+            .map(|datetime| StringValue(super::format(datetime)))
+            .serialize(serializer)
+    }
+
+    /// Deserializes a date, if present, from the inner text of its tag
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(value) => super::parse(&value).map(Some).map_err(de::Error::custom),
+            None => Ok(None),
+        }
+    }
+}
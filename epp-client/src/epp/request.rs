@@ -27,12 +27,23 @@ pub type EppLogin = EppObject<Command<Login>>;
 /// The EPP Logout request
 pub type EppLogout = EppObject<Command<Logout>>;
 
-#[derive(Deserialize, Debug, PartialEq, ElementName)]
-#[element_name(name = "command")]
+#[derive(Deserialize, Debug, PartialEq)]
 /// Type corresponding to the &lt;command&gt; tag in an EPP XML request
 /// with an &lt;extension&gt; tag
 pub struct CommandWithExtension<T: ElementName, E: ElementName> {
     /// The instance that will be used to populate the &lt;command&gt; tag
+    #[serde(
+        alias = "check",
+        alias = "create",
+        alias = "delete",
+        alias = "info",
+        alias = "login",
+        alias = "logout",
+        alias = "poll",
+        alias = "renew",
+        alias = "transfer",
+        alias = "update"
+    )]
     pub command: T,
     /// The client TRID
     pub extension: Option<Extension<E>>,
@@ -40,6 +51,17 @@ pub struct CommandWithExtension<T: ElementName, E: ElementName> {
     pub client_tr_id: StringValue,
 }
 
+impl<T: ElementName, E: ElementName> ElementName for CommandWithExtension<T, E> {
+    fn element_name(&self) -> &'static str {
+        "command"
+    }
+
+    /// A command is idempotent if the object command it wraps is
+    fn is_idempotent(&self) -> bool {
+        self.command.is_idempotent()
+    }
+}
+
 impl<T: ElementName + Serialize, E: ElementName + Serialize> Serialize
     for CommandWithExtension<T, E>
 {
@@ -85,11 +107,21 @@ pub fn generate_client_tr_id(username: &str) -> Result<String, Box<dyn Error>> {
     Ok(format!("{}:{}", username, timestamp.as_secs()))
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, ElementName)]
-#[element_name(name = "hello")]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 /// Type corresponding to the <hello> tag in an EPP XML hello request
 pub struct Hello;
 
+impl ElementName for Hello {
+    fn element_name(&self) -> &'static str {
+        "hello"
+    }
+
+    /// A hello only asks for the greeting
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+}
+
 impl EppHello {
     /// Creates a new Epp Hello request
     pub fn new() -> EppHello {
@@ -103,15 +135,18 @@ impl EppHello {
 pub struct Login {
     /// The username to use for the login
     #[serde(rename(serialize = "clID", deserialize = "clID"))]
-    username: StringValue,
+    pub username: StringValue,
     /// The password to use for the login
     #[serde(rename = "pw", default)]
-    password: StringValue,
+    pub password: StringValue,
+    /// The new password to change the password to on login
+    #[serde(rename = "newPW", skip_serializing_if = "Option::is_none")]
+    pub new_password: Option<StringValue>,
     /// Data under the <options> tag
-    options: Options,
+    pub options: Options,
     /// Data under the <svcs> tag
     #[serde(rename = "svcs")]
-    services: Services,
+    pub services: Services,
 }
 
 impl EppLogin {
@@ -134,6 +169,7 @@ impl EppLogin {
         let login = Login {
             username: username.to_string_value(),
             password: password.to_string_value(),
+            new_password: None,
             options: Options {
                 version: EPP_VERSION.to_string_value(),
                 lang: EPP_LANG.to_string_value(),
@@ -155,6 +191,11 @@ impl EppLogin {
         })
     }
 
+    /// Sets the password to change the password to on successful login
+    pub fn new_password(&mut self, new_password: &str) {
+        self.data.command.new_password = Some(new_password.to_string_value());
+    }
+
     /// Sets the <options> tag data
     pub fn options(&mut self, options: Options) {
         self.data.command.options = options;
@@ -1,7 +1,5 @@
 //! Types for EPP contact check request
 
-use epp_client_macros::*;
-
 use crate::epp::object::{ElementName, EppObject, StringValue, StringValueTrait};
 use crate::epp::request::Command;
 use crate::epp::xml::EPP_CONTACT_XMLNS;
@@ -43,19 +41,29 @@ pub type EppContactCheck = EppObject<Command<ContactCheck>>;
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ContactList {
     /// The XML namespace for the contact &lt;check&gt;
-    xmlns: String,
+    pub xmlns: String,
     /// The list of contact ids to check for availability
     #[serde(rename = "id")]
     pub contact_ids: Vec<StringValue>,
 }
 
-#[derive(Serialize, Deserialize, Debug, ElementName)]
-#[element_name(name = "check")]
+#[derive(Serialize, Deserialize, Debug)]
 /// The &lt;command&gt; type for contact check command
 pub struct ContactCheck {
     /// The &lt;check&gt; tag for the contact check command
     #[serde(rename = "check")]
-    list: ContactList,
+    pub list: ContactList,
+}
+
+impl ElementName for ContactCheck {
+    fn element_name(&self) -> &'static str {
+        "check"
+    }
+
+    /// Check commands do not change anything at the registry
+    fn is_idempotent(&self) -> bool {
+        true
+    }
 }
 
 impl EppContactCheck {
@@ -29,8 +29,8 @@ use serde::{Deserialize, Serialize};
 ///
 ///     // Create the address, postal_info, voice instances
 ///     let street = vec!["58", "Orchid Road"];
-///     let address = Address::new(street, "New York", "New York", "392374", "US");
-///     let postal_info = PostalInfo::new("int", "John Doe", "Acme Widgets", address);
+///     let address = Address::new(street, "New York", Some("New York"), Some("392374"), "US");
+///     let postal_info = PostalInfo::new("int", "John Doe", Some("Acme Widgets"), address);
 ///     let mut voice = Phone::new("+1.47237942");
 ///     voice.set_extension("123");
 ///     let mut fax = Phone::new("+1.86698799");
@@ -59,21 +59,23 @@ pub type EppContactCreate = EppObject<Command<ContactCreate>>;
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Contact {
     /// XML namespace for contact commands
-    xmlns: String,
+    pub xmlns: String,
     /// Contact &lt;id&gt; tag
-    id: StringValue,
-    /// Contact &lt;postalInfo&gt; tag
+    pub id: StringValue,
+    /// Contact &lt;postalInfo&gt; tags, one each for the "int" and "loc" forms
     #[serde(rename = "postalInfo")]
-    postal_info: data::PostalInfo,
+    pub postal_info: Vec<data::PostalInfo>,
     /// Contact &lt;voice&gt; tag
-    voice: data::Phone,
+    pub voice: data::Phone,
     /// Contact &lt;fax&gt; tag,
-    fax: Option<data::Phone>,
+    pub fax: Option<data::Phone>,
     /// Contact &lt;email&gt; tag
-    email: StringValue,
+    pub email: StringValue,
     /// Contact &lt;authInfo&gt; tag
     #[serde(rename = "authInfo")]
-    auth_info: data::AuthInfo,
+    pub auth_info: data::AuthInfo,
+    /// Contact &lt;disclose&gt; tag
+    pub disclose: Option<data::Disclose>,
 }
 
 #[derive(Serialize, Deserialize, Debug, ElementName)]
@@ -99,19 +101,32 @@ impl EppContactCreate {
             contact: Contact {
                 xmlns: EPP_CONTACT_XMLNS.to_string(),
                 id: id.to_string_value(),
-                postal_info: postal_info,
+                postal_info: vec![postal_info],
                 voice: voice,
                 fax: None,
                 email: email.to_string_value(),
                 auth_info: data::AuthInfo::new(auth_password),
+                disclose: None,
             },
         };
 
         EppObject::build(Command::<ContactCreate>::new(contact_create, client_tr_id))
     }
 
+    /// Adds &lt;postalInfo&gt; data to the request, replacing any postal info of the same type
+    pub fn add_postal_info(&mut self, postal_info: data::PostalInfo) {
+        let postal_infos = &mut self.data.command.contact.postal_info;
+        postal_infos.retain(|p| p.info_type != postal_info.info_type);
+        postal_infos.push(postal_info);
+    }
+
     /// Sets the &lt;fax&gt; data for the request
     pub fn set_fax(&mut self, fax: data::Phone) {
         self.data.command.contact.fax = Some(fax);
     }
+
+    /// Sets the &lt;disclose&gt; data for the request
+    pub fn set_disclose(&mut self, disclose: data::Disclose) {
+        self.data.command.contact.disclose = Some(disclose);
+    }
 }
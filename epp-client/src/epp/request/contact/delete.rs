@@ -43,9 +43,9 @@ pub type EppContactDelete = EppObject<Command<ContactDelete>>;
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ContactDeleteData {
     /// XML namespace for the &lt;delete&gt; command for contacts
-    xmlns: String,
+    pub xmlns: String,
     /// The id of the contact to be deleted
-    id: StringValue,
+    pub id: StringValue,
 }
 
 #[derive(Serialize, Deserialize, Debug, ElementName)]
@@ -54,7 +54,7 @@ pub struct ContactDeleteData {
 pub struct ContactDelete {
     #[serde(rename = "delete")]
     /// The data for the &lt;delete&gt; tag for a contact delete command
-    contact: ContactDeleteData,
+    pub contact: ContactDeleteData,
 }
 
 impl EppContactDelete {
@@ -1,7 +1,5 @@
 //! Types for EPP contact info request
 
-use epp_client_macros::*;
-
 use crate::epp::object::data::AuthInfo;
 use crate::epp::object::{ElementName, EppObject, StringValue, StringValueTrait};
 use crate::epp::request::Command;
@@ -45,21 +43,31 @@ pub type EppContactInfo = EppObject<Command<ContactInfo>>;
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ContactInfoData {
     /// XML namespace for contact commands
-    xmlns: String,
+    pub xmlns: String,
     /// The contact id for the info command
-    id: StringValue,
+    pub id: StringValue,
     /// The &lt;authInfo&gt; data
     #[serde(rename = "authInfo")]
-    auth_info: AuthInfo,
+    pub auth_info: AuthInfo,
 }
 
-#[derive(Serialize, Deserialize, Debug, ElementName)]
-#[element_name(name = "info")]
+#[derive(Serialize, Deserialize, Debug)]
 /// Type for EPP XML &lt;info&gt; command for contacts
 pub struct ContactInfo {
     /// Data for &lt;info&gt; command for contact
     #[serde(rename = "info")]
-    info: ContactInfoData,
+    pub info: ContactInfoData,
+}
+
+impl ElementName for ContactInfo {
+    fn element_name(&self) -> &'static str {
+        "info"
+    }
+
+    /// Info commands do not change anything at the registry
+    fn is_idempotent(&self) -> bool {
+        true
+    }
 }
 
 impl EppContactInfo {
@@ -0,0 +1,283 @@
+//! Types for EPP contact transfer request
+
+use crate::epp::object::data::AuthInfo;
+use crate::epp::object::{ElementName, EppObject, StringValue, StringValueTrait};
+use crate::epp::request::Command;
+use crate::epp::xml::EPP_CONTACT_XMLNS;
+use serde::{Deserialize, Serialize};
+
+/// Type that represents the &lt;epp&gt; request for transfer request for contact
+///
+/// ## Usage
+///
+/// ```ignore
+/// use epp_client::EppClient;
+/// use epp_client::epp::{EppContactTransferRequest, EppContactTransferRequestResponse};
+/// use epp_client::epp::generate_client_tr_id;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     // Create an instance of EppClient, specifying the name of the registry as in
+///     // the config file
+///     let mut client = match EppClient::new("verisign").await {
+///         Ok(client) => client,
+///         Err(e) => panic!("Failed to create EppClient: {}",  e)
+///     };
+///
+///     // Create an EppContactTransferRequest instance
+///     let contact_transfer_request = EppContactTransferRequest::request(
+///         "eppdev-contact-100", "epP4uthd#v", generate_client_tr_id(&client).as_str()
+///     );
+///
+///     // send it to the registry and receive a response of type EppContactTransferRequestResponse
+///     let response = client.transact::<_, EppContactTransferRequestResponse>(&contact_transfer_request).await.unwrap();
+///
+///     println!("{:?}", response);
+/// }
+/// ```
+pub type EppContactTransferRequest = EppObject<Command<ContactTransfer>>;
+
+/// Type that represents the &lt;epp&gt; request for transfer approval for contacts
+///
+/// ## Usage
+///
+/// ```ignore
+/// use epp_client::EppClient;
+/// use epp_client::epp::{EppContactTransferApprove, EppContactTransferApproveResponse};
+/// use epp_client::epp::generate_client_tr_id;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     // Create an instance of EppClient, specifying the name of the registry as in
+///     // the config file
+///     let mut client = match EppClient::new("verisign").await {
+///         Ok(client) => client,
+///         Err(e) => panic!("Failed to create EppClient: {}",  e)
+///     };
+///
+///     // Create an EppContactTransferApprove instance
+///     let contact_transfer_approve = EppContactTransferApprove::approve(
+///         "eppdev-contact-100", generate_client_tr_id(&client).as_str()
+///     );
+///
+///     // send it to the registry and receive a response of type EppContactTransferApproveResponse
+///     let response = client.transact::<_, EppContactTransferApproveResponse>(&contact_transfer_approve).await.unwrap();
+///
+///     println!("{:?}", response);
+/// }
+/// ```
+pub type EppContactTransferApprove = EppObject<Command<ContactTransfer>>;
+
+/// Type that represents the &lt;epp&gt; request for transfer rejection for contacts
+///
+/// ## Usage
+///
+/// ```ignore
+/// use epp_client::EppClient;
+/// use epp_client::epp::{EppContactTransferReject, EppContactTransferRejectResponse};
+/// use epp_client::epp::generate_client_tr_id;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     // Create an instance of EppClient, specifying the name of the registry as in
+///     // the config file
+///     let mut client = match EppClient::new("verisign").await {
+///         Ok(client) => client,
+///         Err(e) => panic!("Failed to create EppClient: {}",  e)
+///     };
+///
+///     // Create an EppContactTransferReject instance
+///     let contact_transfer_reject = EppContactTransferReject::reject(
+///         "eppdev-contact-100", generate_client_tr_id(&client).as_str()
+///     );
+///
+///     // send it to the registry and receive a response of type EppContactTransferRejectResponse
+///     let response = client.transact::<_, EppContactTransferRejectResponse>(&contact_transfer_reject).await.unwrap();
+///
+///     println!("{:?}", response);
+/// }
+/// ```
+pub type EppContactTransferReject = EppObject<Command<ContactTransfer>>;
+
+/// Type that represents the &lt;epp&gt; request for transfer request cancellation for contacts
+///
+/// ## Usage
+///
+/// ```ignore
+/// use epp_client::EppClient;
+/// use epp_client::epp::{EppContactTransferCancel, EppContactTransferCancelResponse};
+/// use epp_client::epp::generate_client_tr_id;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     // Create an instance of EppClient, specifying the name of the registry as in
+///     // the config file
+///     let mut client = match EppClient::new("verisign").await {
+///         Ok(client) => client,
+///         Err(e) => panic!("Failed to create EppClient: {}",  e)
+///     };
+///
+///     // Create an EppContactTransferCancel instance
+///     let contact_transfer_cancel = EppContactTransferCancel::cancel(
+///         "eppdev-contact-100", generate_client_tr_id(&client).as_str()
+///     );
+///
+///     // send it to the registry and receive a response of type EppContactTransferCancelResponse
+///     let response = client.transact::<_, EppContactTransferCancelResponse>(&contact_transfer_cancel).await.unwrap();
+///
+///     println!("{:?}", response);
+/// }
+/// ```
+pub type EppContactTransferCancel = EppObject<Command<ContactTransfer>>;
+
+/// Type that represents the &lt;epp&gt; request for transfer request query for contacts
+///
+/// ## Usage
+///
+/// ```ignore
+/// use epp_client::EppClient;
+/// use epp_client::epp::{EppContactTransferQuery, EppContactTransferQueryResponse};
+/// use epp_client::epp::generate_client_tr_id;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     // Create an instance of EppClient, specifying the name of the registry as in
+///     // the config file
+///     let mut client = match EppClient::new("verisign").await {
+///         Ok(client) => client,
+///         Err(e) => panic!("Failed to create EppClient: {}",  e)
+///     };
+///
+///     // Create an EppContactTransferQuery instance
+///     let contact_transfer_query = EppContactTransferQuery::query(
+///         "eppdev-contact-100", "epP4uthd#v", generate_client_tr_id(&client).as_str()
+///     );
+///
+///     // send it to the registry and receive a response of type EppContactTransferQueryResponse
+///     let response = client.transact::<_, EppContactTransferQueryResponse>(&contact_transfer_query).await.unwrap();
+///
+///     println!("{:?}", response);
+/// }
+/// ```
+pub type EppContactTransferQuery = EppObject<Command<ContactTransfer>>;
+
+/// Type for elements under the contact &lt;transfer&gt; tag
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ContactTransferInfo {
+    /// XML namespace for contact commands
+    pub xmlns: String,
+    /// The id of the contact under transfer
+    pub id: StringValue,
+    /// The authInfo for the contact under transfer
+    /// Only applicable to contact transfer and contact transfer query requests
+    #[serde(rename = "authInfo")]
+    pub auth_info: Option<AuthInfo>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+/// Type for EPP XML &lt;transfer&gt; command for contacts
+pub struct ContactTransfer {
+    /// The transfer operation to perform indicated by the 'op' attr
+    /// The values are one of transfer, approve, reject, cancel, or query
+    #[serde(rename = "op")]
+    pub operation: String,
+    /// The data under the &lt;transfer&gt; tag in the transfer request
+    #[serde(rename = "transfer")]
+    pub contact: ContactTransferInfo,
+}
+
+impl ElementName for ContactTransfer {
+    fn element_name(&self) -> &'static str {
+        "transfer"
+    }
+
+    /// Only transfer queries leave the transfer status unchanged
+    fn is_idempotent(&self) -> bool {
+        self.operation == "query"
+    }
+}
+
+impl EppContactTransferRequest {
+    /// Creates a new EppObject for contact transfer request corresponding to the &lt;epp&gt; tag in EPP XML
+    pub fn request(id: &str, auth_password: &str, client_tr_id: &str) -> EppContactTransferRequest {
+        EppObject::build(Command::<ContactTransfer>::new(
+            ContactTransfer {
+                operation: "request".to_string(),
+                contact: ContactTransferInfo {
+                    xmlns: EPP_CONTACT_XMLNS.to_string(),
+                    id: id.to_string_value(),
+                    auth_info: Some(AuthInfo::new(auth_password)),
+                },
+            },
+            client_tr_id,
+        ))
+    }
+}
+
+impl EppContactTransferApprove {
+    /// Creates a new EppObject for contact transfer approval corresponding to the &lt;epp&gt; tag in EPP XML
+    pub fn approve(id: &str, client_tr_id: &str) -> EppContactTransferApprove {
+        EppObject::build(Command::<ContactTransfer>::new(
+            ContactTransfer {
+                operation: "approve".to_string(),
+                contact: ContactTransferInfo {
+                    xmlns: EPP_CONTACT_XMLNS.to_string(),
+                    id: id.to_string_value(),
+                    auth_info: None,
+                },
+            },
+            client_tr_id,
+        ))
+    }
+}
+
+impl EppContactTransferCancel {
+    /// Creates a new EppObject for contact transfer request cancellation corresponding to the &lt;epp&gt; tag in EPP XML
+    pub fn cancel(id: &str, client_tr_id: &str) -> EppContactTransferCancel {
+        EppObject::build(Command::<ContactTransfer>::new(
+            ContactTransfer {
+                operation: "cancel".to_string(),
+                contact: ContactTransferInfo {
+                    xmlns: EPP_CONTACT_XMLNS.to_string(),
+                    id: id.to_string_value(),
+                    auth_info: None,
+                },
+            },
+            client_tr_id,
+        ))
+    }
+}
+
+impl EppContactTransferReject {
+    /// Creates a new EppObject for contact transfer rejection corresponding to the &lt;epp&gt; tag in EPP XML
+    pub fn reject(id: &str, client_tr_id: &str) -> EppContactTransferReject {
+        EppObject::build(Command::<ContactTransfer>::new(
+            ContactTransfer {
+                operation: "reject".to_string(),
+                contact: ContactTransferInfo {
+                    xmlns: EPP_CONTACT_XMLNS.to_string(),
+                    id: id.to_string_value(),
+                    auth_info: None,
+                },
+            },
+            client_tr_id,
+        ))
+    }
+}
+
+impl EppContactTransferQuery {
+    /// Creates a new EppObject for contact transfer request query corresponding to the &lt;epp&gt; tag in EPP XML
+    pub fn query(id: &str, auth_password: &str, client_tr_id: &str) -> EppContactTransferQuery {
+        EppObject::build(Command::<ContactTransfer>::new(
+            ContactTransfer {
+                operation: "query".to_string(),
+                contact: ContactTransferInfo {
+                    xmlns: EPP_CONTACT_XMLNS.to_string(),
+                    id: id.to_string_value(),
+                    auth_info: Some(AuthInfo::new(auth_password)),
+                },
+            },
+            client_tr_id,
+        ))
+    }
+}
@@ -2,7 +2,7 @@
 
 use epp_client_macros::*;
 
-use crate::epp::object::data::{AuthInfo, ContactStatus, Phone, PostalInfo};
+use crate::epp::object::data::{AuthInfo, ContactStatus, Disclose, Phone, PostalInfo, Status};
 use crate::epp::object::{ElementName, EppObject, StringValue, StringValueTrait};
 use crate::epp::request::Command;
 use crate::epp::response::contact::info::EppContactInfoResponse;
@@ -16,6 +16,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// ```ignore
 /// use epp_client::EppClient;
+/// use epp_client::epp::object::data::{ContactStatus, Status};
 /// use epp_client::epp::{EppContactUpdate, EppContactUpdateResponse};
 /// use epp_client::epp::generate_client_tr_id;
 ///
@@ -35,9 +36,7 @@ use serde::{Deserialize, Serialize};
 ///     );
 ///
 ///     let add_statuses = vec![
-///         ContactStatus {
-///             status: "clientTransferProhibited".to_string()
-///         }
+///         Status::new(ContactStatus::ClientTransferProhibited)
 ///     ];
 ///
 ///     contact_update.add(add_statuses);
@@ -51,34 +50,35 @@ use serde::{Deserialize, Serialize};
 pub type EppContactUpdate = EppObject<Command<ContactUpdate>>;
 
 /// Type for elements under the &lt;chg&gt; tag for contact update request
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct ContactChangeInfo {
-    #[serde(rename = "postalInfo")]
-    postal_info: Option<PostalInfo>,
-    voice: Option<Phone>,
-    fax: Option<Phone>,
-    email: Option<StringValue>,
+    #[serde(rename = "postalInfo", default, skip_serializing_if = "Vec::is_empty")]
+    pub postal_info: Vec<PostalInfo>,
+    pub voice: Option<Phone>,
+    pub fax: Option<Phone>,
+    pub email: Option<StringValue>,
     #[serde(rename = "authInfo")]
-    auth_info: Option<AuthInfo>,
+    pub auth_info: Option<AuthInfo>,
+    pub disclose: Option<Disclose>,
 }
 
 /// Type for list of elements of the &lt;status&gt; tag for contact update request
 #[derive(Serialize, Deserialize, Debug)]
 pub struct StatusList {
-    status: Vec<ContactStatus>,
+    pub status: Vec<Status<ContactStatus>>,
 }
 
 /// Type for elements under the contact &lt;update&gt; tag
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ContactUpdateData {
-    xmlns: String,
-    id: StringValue,
+    pub xmlns: String,
+    pub id: StringValue,
     #[serde(rename = "add")]
-    add_statuses: Option<StatusList>,
+    pub add_statuses: Option<StatusList>,
     #[serde(rename = "rem")]
-    remove_statuses: Option<StatusList>,
+    pub remove_statuses: Option<StatusList>,
     #[serde(rename = "chg")]
-    change_info: Option<ContactChangeInfo>,
+    pub change_info: Option<ContactChangeInfo>,
 }
 
 #[derive(Serialize, Deserialize, Debug, ElementName)]
@@ -87,7 +87,7 @@ pub struct ContactUpdateData {
 pub struct ContactUpdate {
     /// The data under the &lt;update&gt; tag for the contact update
     #[serde(rename = "update")]
-    contact: ContactUpdateData,
+    pub contact: ContactUpdateData,
 }
 
 impl EppContactUpdate {
@@ -115,10 +115,11 @@ impl EppContactUpdate {
     ) {
         self.data.command.contact.change_info = Some(ContactChangeInfo {
             email: Some(email.to_string_value()),
-            postal_info: Some(postal_info),
+            postal_info: vec![postal_info],
             voice: Some(voice),
             auth_info: Some(AuthInfo::new(auth_password)),
             fax: None,
+            disclose: None,
         });
     }
 
@@ -130,13 +131,38 @@ impl EppContactUpdate {
         }
     }
 
+    /// Adds data for a &lt;postalInfo&gt; tag under &lt;chg&gt; for the contact update request,
+    /// replacing any postal info of the same type
+    pub fn add_postal_info(&mut self, postal_info: PostalInfo) {
+        let postal_infos = &mut self
+            .data
+            .command
+            .contact
+            .change_info
+            .get_or_insert_with(ContactChangeInfo::default)
+            .postal_info;
+        postal_infos.retain(|p| p.info_type != postal_info.info_type);
+        postal_infos.push(postal_info);
+    }
+
+    /// Sets the data for the &lt;disclose&gt; tag under &lt;chg&gt; for the contact update
+    /// request, which can be changed on its own
+    pub fn set_disclose(&mut self, disclose: Disclose) {
+        self.data
+            .command
+            .contact
+            .change_info
+            .get_or_insert_with(ContactChangeInfo::default)
+            .disclose = Some(disclose);
+    }
+
     /// Sets the data for the &lt;add&gt; tag for the contact update request
-    pub fn add(&mut self, statuses: Vec<ContactStatus>) {
+    pub fn add(&mut self, statuses: Vec<Status<ContactStatus>>) {
         self.data.command.contact.add_statuses = Some(StatusList { status: statuses });
     }
 
     /// Sets the data for the &lt;rem&gt; tag for the contact update request
-    pub fn remove(&mut self, statuses: Vec<ContactStatus>) {
+    pub fn remove(&mut self, statuses: Vec<Status<ContactStatus>>) {
         self.data.command.contact.remove_statuses = Some(StatusList { status: statuses });
     }
 
@@ -149,10 +175,11 @@ impl EppContactUpdate {
             Some(res_data) => {
                 self.data.command.contact.change_info = Some(ContactChangeInfo {
                     email: Some(res_data.info_data.email.clone()),
-                    postal_info: Some(res_data.info_data.postal_info.clone()),
+                    postal_info: res_data.info_data.postal_info.clone(),
                     voice: Some(res_data.info_data.voice.clone()),
                     fax: res_data.info_data.fax.clone(),
                     auth_info: None,
+                    disclose: res_data.info_data.disclose.clone(),
                 });
                 Ok(())
             }
@@ -1,7 +1,5 @@
 //! Types for EPP domain check request
 
-use epp_client_macros::*;
-
 use crate::epp::object::{ElementName, EppObject, StringValue, StringValueTrait};
 use crate::epp::request::Command;
 use crate::epp::xml::EPP_DOMAIN_XMLNS;
@@ -49,13 +47,23 @@ pub struct DomainList {
     pub domains: Vec<StringValue>,
 }
 
-#[derive(Serialize, Deserialize, Debug, ElementName)]
-#[element_name(name = "check")]
+#[derive(Serialize, Deserialize, Debug)]
 /// Type for EPP XML &lt;check&gt; command for domains
 pub struct DomainCheck {
     /// The object holding the list of domains to be checked
     #[serde(rename = "check")]
-    list: DomainList,
+    pub list: DomainList,
+}
+
+impl ElementName for DomainCheck {
+    fn element_name(&self) -> &'static str {
+        "check"
+    }
+
+    /// Check commands do not change anything at the registry
+    fn is_idempotent(&self) -> bool {
+        true
+    }
 }
 
 impl EppDomainCheck {
@@ -66,22 +66,22 @@ pub type EppDomainCreateWithHostAttr = EppObject<Command<DomainCreate<HostAttrLi
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DomainCreateData<T> {
     /// XML namespace for domain commands
-    xmlns: String,
+    pub xmlns: String,
     /// The domain name
-    name: StringValue,
+    pub name: StringValue,
     /// The period of registration
-    period: Period,
+    pub period: Period,
     /// The list of nameserver hosts
     /// either of type `HostObjList` or `HostAttrList`
-    ns: Option<T>,
+    pub ns: Option<T>,
     /// The domain registrant
-    registrant: Option<StringValue>,
+    pub registrant: Option<StringValue>,
     /// The list of contacts for the domain
     #[serde(rename = "contact")]
-    contacts: Option<Vec<DomainContact>>,
+    pub contacts: Option<Vec<DomainContact>>,
     /// The auth info for the domain
     #[serde(rename = "authInfo")]
-    auth_info: AuthInfo,
+    pub auth_info: AuthInfo,
 }
 
 #[derive(Serialize, Deserialize, Debug, ElementName)]
@@ -92,7 +92,7 @@ pub struct DomainCreate<T> {
     /// T being the type of nameserver list (`HostObjList` or `HostAttrList`)
     /// to be supplied
     #[serde(rename = "create")]
-    domain: DomainCreateData<T>,
+    pub domain: DomainCreateData<T>,
 }
 
 impl EppDomainCreate {
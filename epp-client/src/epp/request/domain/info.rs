@@ -1,11 +1,11 @@
 //! Types for EPP domain info request
 
-use epp_client_macros::*;
-
+use crate::epp::object::data::AuthInfo;
 use crate::epp::object::{ElementName, EppObject};
 use crate::epp::request::Command;
 use crate::epp::xml::EPP_DOMAIN_XMLNS;
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
 
 /// Type that represents the &lt;epp&gt; request for domain &lt;info&gt; command
 ///
@@ -36,33 +36,98 @@ use serde::{Deserialize, Serialize};
 /// ```
 pub type EppDomainInfo = EppObject<Command<DomainInfo>>;
 
+/// The hosts attribute of the domain &lt;info&gt; request, which filters the hosts in the response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DomainInfoHosts {
+    /// Delegated and subordinate hosts
+    #[default]
+    All,
+    /// Delegated hosts only
+    Del,
+    /// Subordinate hosts only
+    Sub,
+    /// No hosts
+    None,
+}
+
+impl DomainInfoHosts {
+    /// Returns the hosts value as it appears in EPP XML
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DomainInfoHosts::All => "all",
+            DomainInfoHosts::Del => "del",
+            DomainInfoHosts::Sub => "sub",
+            DomainInfoHosts::None => "none",
+        }
+    }
+}
+
+impl fmt::Display for DomainInfoHosts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for DomainInfoHosts {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for DomainInfoHosts {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        match value.as_str() {
+            "all" => Ok(DomainInfoHosts::All),
+            "del" => Ok(DomainInfoHosts::Del),
+            "sub" => Ok(DomainInfoHosts::Sub),
+            "none" => Ok(DomainInfoHosts::None),
+            _ => Err(de::Error::custom(format!("invalid hosts value {}", value))),
+        }
+    }
+}
+
 /// Type for data under the &lt;name&gt; element tag for the domain &lt;info&gt; tag
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Domain {
     /// The hosts attribute. Default value is "all"
-    hosts: String,
+    pub hosts: DomainInfoHosts,
     /// The name of the domain
     #[serde(rename = "$value")]
-    name: String,
+    pub name: String,
 }
 
 /// Type for &lt;name&gt; element under the domain &lt;info&gt; tag
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DomainInfoData {
     /// XML namespace for domain commands
-    xmlns: String,
+    pub xmlns: String,
     /// The data for the domain to be queried
     #[serde(rename = "name")]
-    domain: Domain,
+    pub domain: Domain,
+    /// The authInfo of the domain or one of its contacts, which lets clients other than
+    /// the sponsor see the full domain data
+    #[serde(rename = "authInfo")]
+    pub auth_info: Option<AuthInfo>,
 }
 
-#[derive(Serialize, Deserialize, Debug, ElementName)]
-#[element_name(name = "info")]
+#[derive(Serialize, Deserialize, Debug)]
 /// Type for EPP XML &lt;info&gt; command for domains
 pub struct DomainInfo {
     /// The data under the &lt;info&gt; tag for domain info
     #[serde(rename = "info")]
-    info: DomainInfoData,
+    pub info: DomainInfoData,
+}
+
+impl ElementName for DomainInfo {
+    fn element_name(&self) -> &'static str {
+        "info"
+    }
+
+    /// Info commands do not change anything at the registry
+    fn is_idempotent(&self) -> bool {
+        true
+    }
 }
 
 impl EppDomainInfo {
@@ -73,12 +138,23 @@ impl EppDomainInfo {
                 info: DomainInfoData {
                     xmlns: EPP_DOMAIN_XMLNS.to_string(),
                     domain: Domain {
-                        hosts: "all".to_string(),
+                        hosts: DomainInfoHosts::default(),
                         name: name.to_string(),
                     },
+                    auth_info: None,
                 },
             },
             client_tr_id,
         ))
     }
+
+    /// Sets the hosts attribute, which filters the hosts in the response
+    pub fn set_hosts(&mut self, hosts: DomainInfoHosts) {
+        self.data.command.info.domain.hosts = hosts;
+    }
+
+    /// Sets the &lt;authInfo&gt; data for the request
+    pub fn set_auth_info(&mut self, auth_info: AuthInfo) {
+        self.data.command.info.auth_info = Some(auth_info);
+    }
 }
@@ -72,33 +72,33 @@ pub type EppDomainRgpRestoreReport =
 pub struct RgpRestoreReportData {
     /// The pre-delete registration date
     #[serde(rename = "preData")]
-    pre_data: StringValue,
+    pub pre_data: StringValue,
     /// The post-delete registration date
     #[serde(rename = "postData")]
-    post_data: StringValue,
+    pub post_data: StringValue,
     /// The domain deletion date
     #[serde(rename = "delTime")]
-    deleted_at: StringValue,
+    pub deleted_at: StringValue,
     /// The domain restore request date
     #[serde(rename = "resTime")]
-    restored_at: StringValue,
+    pub restored_at: StringValue,
     /// The reason for domain restoration
     #[serde(rename = "resReason")]
-    restore_reason: StringValue,
+    pub restore_reason: StringValue,
     /// The registrar's statements on the domain restoration
     #[serde(rename = "statement")]
-    statements: Vec<StringValue>,
+    pub statements: Vec<StringValue>,
     /// Other remarks for domain restoration
-    other: StringValue,
+    pub other: StringValue,
 }
 
 /// Type corresponding to the &lt;restore&gt; section in the rgp restore extension
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RgpRestoreReportSection {
     /// The value of the op attribute for the &lt;restore&gt; tag
-    op: String,
+    pub op: String,
     /// Data for the &lt;report&gt; tag
-    report: RgpRestoreReportData,
+    pub report: RgpRestoreReportData,
 }
 
 #[derive(Serialize, Deserialize, Debug, ElementName)]
@@ -106,12 +106,12 @@ pub struct RgpRestoreReportSection {
 /// Type for EPP XML &lt;check&gt; command for domains
 pub struct RgpRestoreReport {
     /// XML namespace for the RGP restore extension
-    xmlns: String,
+    pub xmlns: String,
     /// XML schema location for the RGP restore extension
     #[serde(rename = "xsi:schemaLocation")]
-    schema_location: String,
+    pub schema_location: String,
     /// The object holding the list of domains to be checked
-    restore: RgpRestoreReportSection,
+    pub restore: RgpRestoreReportSection,
 }
 
 impl EppDomainRgpRestoreReport {
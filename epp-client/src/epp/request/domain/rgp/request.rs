@@ -56,12 +56,12 @@ pub struct RgpRestoreRequestData {
 /// Type for EPP XML &lt;check&gt; command for domains
 pub struct RgpRestoreRequest {
     /// XML namespace for the RGP restore extension
-    xmlns: String,
+    pub xmlns: String,
     /// XML schema location for the RGP restore extension
     #[serde(rename = "xsi:schemaLocation")]
-    schema_location: String,
+    pub schema_location: String,
     /// The object holding the list of domains to be checked
-    restore: RgpRestoreRequestData,
+    pub restore: RgpRestoreRequestData,
 }
 
 impl EppDomainRgpRestoreRequest {
@@ -1,7 +1,5 @@
 //! Types for EPP domain transfer request
 
-use epp_client_macros::*;
-
 use crate::epp::object::data::{AuthInfo, Period};
 use crate::epp::object::{ElementName, EppObject, StringValue, StringValueTrait};
 use crate::epp::request::Command;
@@ -167,29 +165,39 @@ pub type EppDomainTransferQuery = EppObject<Command<DomainTransfer>>;
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DomainTransferData {
     /// XML namespace for domain commands
-    xmlns: String,
+    pub xmlns: String,
     /// The name of the domain under transfer
-    name: StringValue,
+    pub name: StringValue,
     /// The period of renewal upon a successful transfer
     /// Only applicable in case of a transfer request
-    period: Option<Period>,
+    pub period: Option<Period>,
     /// The authInfo for the domain under transfer
     /// Only applicable to domain transfer and domain transfer query requests
     #[serde(rename = "authInfo")]
-    auth_info: Option<AuthInfo>,
+    pub auth_info: Option<AuthInfo>,
 }
 
-#[derive(Serialize, Deserialize, Debug, ElementName)]
-#[element_name(name = "transfer")]
+#[derive(Serialize, Deserialize, Debug)]
 /// Type for EPP XML &lt;transfer&gt; command for domains
 pub struct DomainTransfer {
     /// The transfer operation to perform indicated by the 'op' attr
     /// The values are one of transfer, approve, reject, cancel, or query
     #[serde(rename = "op")]
-    operation: String,
+    pub operation: String,
     /// The data under the &lt;transfer&gt; tag in the transfer request
     #[serde(rename = "transfer")]
-    domain: DomainTransferData,
+    pub domain: DomainTransferData,
+}
+
+impl ElementName for DomainTransfer {
+    fn element_name(&self) -> &'static str {
+        "transfer"
+    }
+
+    /// Only transfer queries leave the transfer status unchanged
+    fn is_idempotent(&self) -> bool {
+        self.operation == "query"
+    }
 }
 
 impl EppDomainTransferRequest {
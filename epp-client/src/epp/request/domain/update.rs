@@ -2,7 +2,9 @@
 
 use epp_client_macros::*;
 
-use crate::epp::object::data::{AuthInfo, DomainContact, DomainStatus, HostAttrList, HostObjList};
+use crate::epp::object::data::{
+    AuthInfo, DomainContact, DomainStatus, HostAttrList, HostObjList, Status,
+};
 use crate::epp::object::{ElementName, EppObject, StringValue, StringValueTrait};
 use crate::epp::request::Command;
 use crate::epp::xml::EPP_DOMAIN_XMLNS;
@@ -15,7 +17,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// ```ignore
 /// use epp_client::EppClient;
-/// use epp_client::epp::object::data::{DomainStatus, DomainContact};
+/// use epp_client::epp::object::data::{DomainStatus, DomainContact, Status};
 /// use epp_client::epp::{EppDomainUpdate, EppDomainUpdateResponse, DomainAddRemove};
 /// use epp_client::epp::generate_client_tr_id;
 ///
@@ -35,9 +37,7 @@ use serde::{Deserialize, Serialize};
 ///         ns: None,
 ///         contacts: None,
 ///         statuses: Some(vec![
-///             DomainStatus {
-///                 status: "clientUpdateProhibited".to_string()
-///             }
+///             Status::new(DomainStatus::ClientUpdateProhibited)
 ///         ])
 ///     };
 ///
@@ -88,7 +88,7 @@ pub struct DomainAddRemove<T> {
     pub contacts: Option<Vec<DomainContact>>,
     /// The list of statuses to add to or remove from the domain
     #[serde(rename = "status")]
-    pub statuses: Option<Vec<DomainStatus>>,
+    pub statuses: Option<Vec<Status<DomainStatus>>>,
 }
 
 /// Type for elements under the &lt;update&gt; tag for domain update
@@ -1,7 +1,5 @@
 //! Types for EPP host check request
 
-use epp_client_macros::*;
-
 use crate::epp::object::{ElementName, EppObject, StringValue, StringValueTrait};
 use crate::epp::request::Command;
 use crate::epp::xml::EPP_HOST_XMLNS;
@@ -43,19 +41,29 @@ pub type EppHostCheck = EppObject<Command<HostCheck>>;
 #[derive(Serialize, Deserialize, Debug)]
 pub struct HostList {
     /// XML namespace for host commands
-    xmlns: String,
+    pub xmlns: String,
     /// List of hosts to be checked for availability
     #[serde(rename = "name")]
     pub hosts: Vec<StringValue>,
 }
 
-#[derive(Serialize, Deserialize, Debug, ElementName)]
-#[element_name(name = "check")]
+#[derive(Serialize, Deserialize, Debug)]
 /// Type for EPP XML &lt;check&gt; command for hosts
 pub struct HostCheck {
     /// The instance holding the list of hosts to be checked
     #[serde(rename = "check")]
-    list: HostList,
+    pub list: HostList,
+}
+
+impl ElementName for HostCheck {
+    fn element_name(&self) -> &'static str {
+        "check"
+    }
+
+    /// Check commands do not change anything at the registry
+    fn is_idempotent(&self) -> bool {
+        true
+    }
 }
 
 impl EppHostCheck {
@@ -29,8 +29,8 @@ use serde::{Deserialize, Serialize};
 ///
 ///     // Create a vector of IP addresses to assign to the host
 ///     let addresses = vec![
-///         HostAddr::new("v4", "29.245.122.14"),
-///         HostAddr::new("v6", "2404:6800:4001:801::200e"),
+///         HostAddr::new_v4("29.245.122.14".parse().unwrap()),
+///         HostAddr::new_v6("2404:6800:4001:801::200e".parse().unwrap()),
 ///     ];
 ///
 ///     // Create an EppHostCreate instance
@@ -48,7 +48,7 @@ pub type EppHostCreate = EppObject<Command<HostCreate>>;
 #[derive(Serialize, Deserialize, Debug)]
 pub struct HostCreateData {
     /// XML namespace for host commands
-    xmlns: String,
+    pub xmlns: String,
     /// The name of the host to be created
     pub name: StringValue,
     /// The list of IP addresses for the host
@@ -62,7 +62,7 @@ pub struct HostCreateData {
 pub struct HostCreate {
     /// The instance holding the data for the host to be created
     #[serde(rename = "create")]
-    host: HostCreateData,
+    pub host: HostCreateData,
 }
 
 impl EppHostCreate {
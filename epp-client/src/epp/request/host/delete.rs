@@ -40,9 +40,9 @@ pub type EppHostDelete = EppObject<Command<HostDelete>>;
 #[derive(Serialize, Deserialize, Debug)]
 pub struct HostDeleteData {
     /// XML namespace for host commands
-    xmlns: String,
+    pub xmlns: String,
     /// The host to be deleted
-    name: StringValue,
+    pub name: StringValue,
 }
 
 #[derive(Serialize, Deserialize, Debug, ElementName)]
@@ -51,7 +51,7 @@ pub struct HostDeleteData {
 pub struct HostDelete {
     /// The instance holding the data for the host to be deleted
     #[serde(rename = "delete")]
-    host: HostDeleteData,
+    pub host: HostDeleteData,
 }
 
 impl EppHostDelete {
@@ -1,7 +1,5 @@
 //! Types for EPP host info request
 
-use epp_client_macros::*;
-
 use crate::epp::object::{ElementName, EppObject, StringValue, StringValueTrait};
 use crate::epp::request::Command;
 use crate::epp::xml::EPP_HOST_XMLNS;
@@ -40,18 +38,28 @@ pub type EppHostInfo = EppObject<Command<HostInfo>>;
 #[derive(Serialize, Deserialize, Debug)]
 pub struct HostInfoData {
     /// XML namespace for host commands
-    xmlns: String,
+    pub xmlns: String,
     /// The name of the host to be queried
-    name: StringValue,
+    pub name: StringValue,
 }
 
-#[derive(Serialize, Deserialize, Debug, ElementName)]
-#[element_name(name = "info")]
+#[derive(Serialize, Deserialize, Debug)]
 /// Type for EPP XML &lt;info&gt; command for hosts
 pub struct HostInfo {
     /// The instance holding the data for the host query
     #[serde(rename = "info")]
-    info: HostInfoData,
+    pub info: HostInfoData,
+}
+
+impl ElementName for HostInfo {
+    fn element_name(&self) -> &'static str {
+        "info"
+    }
+
+    /// Info commands do not change anything at the registry
+    fn is_idempotent(&self) -> bool {
+        true
+    }
 }
 
 impl EppHostInfo {
@@ -2,7 +2,7 @@
 
 use epp_client_macros::*;
 
-use crate::epp::object::data::{HostAddr, HostStatus};
+use crate::epp::object::data::{HostAddr, HostStatus, Status};
 use crate::epp::object::{ElementName, EppObject, StringValue, StringValueTrait};
 use crate::epp::request::Command;
 use crate::epp::xml::EPP_HOST_XMLNS;
@@ -15,7 +15,7 @@ use serde::{Deserialize, Serialize};
 /// ```ignore
 /// use epp_client::EppClient;
 /// use epp_client::epp::object::StringValueTrait;
-/// use epp_client::epp::object::data::{HostAddr, HostStatus};
+/// use epp_client::epp::object::data::{HostAddr, HostStatus, Status};
 /// use epp_client::epp::{EppHostUpdate, EppHostUpdateResponse, HostAddRemove, HostChangeInfo};
 /// use epp_client::epp::generate_client_tr_id;
 ///
@@ -34,14 +34,16 @@ use serde::{Deserialize, Serialize};
 ///     /// Prepare the add and remove sections for the update
 ///     let add = HostAddRemove {
 ///         addresses: Some(vec![
-///             HostAddr::new("v4", "177.34.126.17")
+///             HostAddr::new_v4("177.34.126.17".parse().unwrap())
 ///         ]),
-///         statuses: None
+///         statuses: Some(vec![
+///             Status::new(HostStatus::ClientDeleteProhibited)
+///         ])
 ///     };
 ///
 ///     let remove = HostAddRemove {
 ///         addresses: Some(vec![
-///             HostAddr::new("v6", "2404:6800:4001:801::200e")
+///             HostAddr::new_v6("2404:6800:4001:801::200e".parse().unwrap())
 ///         ]),
 ///         statuses: None,
 ///     };
@@ -75,24 +77,24 @@ pub struct HostAddRemove {
     pub addresses: Option<Vec<HostAddr>>,
     /// The statuses to be added to or removed from the host
     #[serde(rename = "status")]
-    pub statuses: Option<Vec<HostStatus>>,
+    pub statuses: Option<Vec<Status<HostStatus>>>,
 }
 
 /// Type for data under the host &lt;update&gt; tag
 #[derive(Serialize, Deserialize, Debug)]
 pub struct HostUpdateData {
     /// XML namespace for host commands
-    xmlns: String,
+    pub xmlns: String,
     /// The name of the host
-    name: StringValue,
+    pub name: StringValue,
     /// The IP addresses and statuses to be added to the host
-    add: Option<HostAddRemove>,
+    pub add: Option<HostAddRemove>,
     /// The IP addresses and statuses to be removed from the host
     #[serde(rename = "rem")]
-    remove: Option<HostAddRemove>,
+    pub remove: Option<HostAddRemove>,
     /// The host details that need to be updated
     #[serde(rename = "chg")]
-    change_info: Option<HostChangeInfo>,
+    pub change_info: Option<HostChangeInfo>,
 }
 
 #[derive(Serialize, Deserialize, Debug, ElementName)]
@@ -101,7 +103,7 @@ pub struct HostUpdateData {
 pub struct HostUpdate {
     /// The instance holding the data for the host to be updated
     #[serde(rename = "update")]
-    host: HostUpdateData,
+    pub host: HostUpdateData,
 }
 
 impl EppHostUpdate {
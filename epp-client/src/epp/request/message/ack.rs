@@ -40,10 +40,10 @@ pub type EppMessageAck = EppObject<Command<MessageAck>>;
 pub struct MessageAck {
     /// The type of operation to perform
     /// The value is "ack" for message acknowledgement
-    op: String,
+    pub op: String,
     /// The ID of the message to be acknowledged
     #[serde(rename = "msgID")]
-    message_id: String,
+    pub message_id: String,
 }
 
 impl EppMessageAck {
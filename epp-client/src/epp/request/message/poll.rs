@@ -1,7 +1,5 @@
 //! Types for EPP message poll request
 
-use epp_client_macros::*;
-
 use crate::epp::object::{ElementName, EppObject};
 use crate::epp::request::Command;
 use serde::{Deserialize, Serialize};
@@ -35,13 +33,23 @@ use serde::{Deserialize, Serialize};
 /// ```
 pub type EppMessagePoll = EppObject<Command<MessagePoll>>;
 
-#[derive(Serialize, Deserialize, Debug, ElementName)]
-#[element_name(name = "poll")]
+#[derive(Serialize, Deserialize, Debug)]
 /// Type for EPP XML &lt;poll&gt; command for message poll
 pub struct MessagePoll {
     /// The type of operation to perform
     /// The value is "req" for message polling
-    op: String,
+    pub op: String,
+}
+
+impl ElementName for MessagePoll {
+    fn element_name(&self) -> &'static str {
+        "poll"
+    }
+
+    /// Polling for a message does not change anything at the registry, unlike acking one
+    fn is_idempotent(&self) -> bool {
+        self.op == "req"
+    }
 }
 
 impl EppMessagePoll {
@@ -1,16 +1,21 @@
 //! Types for EPP responses
 
+pub mod code;
 pub mod contact;
 pub mod domain;
 pub mod host;
 pub mod message;
 
+pub use code::ResultCode;
+
+use chrono::{DateTime, Utc};
 use epp_client_macros::*;
-use serde::{Deserialize, Deserializer, Serialize};
-use std::fmt::Debug;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{self, Debug};
 
 use crate::epp::object::{
-    ElementName, EmptyTag, EppObject, Extension, Options, ServiceExtension, Services, StringValue,
+    datetime, ElementName, EmptyTag, EppObject, Extension, Options, ServiceExtension, Services,
+    StringValue,
 };
 
 /// Type corresponding to the &lt;response&gt; tag in an EPP response without an &lt;extension&gt; section
@@ -28,23 +33,44 @@ pub type EppLoginResponse = EppCommandResponse;
 pub type EppLogoutResponse = EppCommandResponse;
 
 /// Type for data within the <svcMenu> section of an EPP greeting
-#[derive(Serialize, Debug, PartialEq)]
+#[derive(Debug, PartialEq)]
 pub struct ServiceMenu {
-    pub options: Options,
+    /// All protocol versions the registry offers
+    pub versions: Vec<StringValue>,
+    /// All languages the registry offers
+    pub languages: Vec<StringValue>,
     pub services: Services,
 }
 
 /// Simplified service menu type for deserialization to `ServiceMenu` type from EPP greeting XML
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 struct FlattenedServiceMenu {
-    pub version: StringValue,
-    pub lang: StringValue,
+    #[serde(rename = "version")]
+    pub versions: Vec<StringValue>,
+    #[serde(rename = "lang")]
+    pub languages: Vec<StringValue>,
     #[serde(rename = "objURI")]
     pub obj_uris: Vec<StringValue>,
     #[serde(rename = "svcExtension")]
     pub svc_ext: Option<ServiceExtension>,
 }
 
+impl Serialize for ServiceMenu {
+    /// Serializes the `ServiceMenu` type to flat <svcMenu> data
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        FlattenedServiceMenu {
+            versions: self.versions.clone(),
+            languages: self.languages.clone(),
+            obj_uris: self.services.obj_uris.clone(),
+            svc_ext: self.services.svc_ext.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
 impl<'de> Deserialize<'de> for ServiceMenu {
     /// Deserializes the <svcMenu> data to the `ServiceMenu` type
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -53,11 +79,13 @@ impl<'de> Deserialize<'de> for ServiceMenu {
     {
         let flattened_svc_menu = FlattenedServiceMenu::deserialize(deserializer)?;
 
+        if flattened_svc_menu.versions.is_empty() || flattened_svc_menu.languages.is_empty() {
+            return Err(de::Error::custom("<svcMenu> has no <version> or <lang>"));
+        }
+
         let svc_menu = ServiceMenu {
-            options: Options {
-                version: flattened_svc_menu.version,
-                lang: flattened_svc_menu.lang,
-            },
+            versions: flattened_svc_menu.versions,
+            languages: flattened_svc_menu.languages,
             services: Services {
                 obj_uris: flattened_svc_menu.obj_uris,
                 svc_ext: flattened_svc_menu.svc_ext,
@@ -68,63 +96,142 @@ impl<'de> Deserialize<'de> for ServiceMenu {
     }
 }
 
-/// Type corresponding to <all> in the EPP greeting XML (pending more compliant implementation)
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
-pub struct All;
-
-/// Type corresponding to <access> in the EPP greeting XML (pending more compliant implementation)
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
-pub struct Access {
-    /// Data for the <all> tag
-    pub all: All,
+impl ServiceMenu {
+    /// Returns the first protocol version and language the registry offers, which every
+    /// greeting has
+    pub fn options(&self) -> Option<Options> {
+        match (self.versions.first(), self.languages.first()) {
+            (Some(version), Some(lang)) => Some(Options {
+                version: version.clone(),
+                lang: lang.clone(),
+            }),
+            _ => None,
+        }
+    }
 }
 
-/// Type corresponding to <admin> in the EPP greeting XML (pending more compliant implementation)
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
-pub struct Admin;
+/// (De)serialization of tags holding one of several child elements into enums, for use with
+/// `#[serde(with = "choice")]`
+mod choice {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-/// Type corresponding to <prov> in the EPP greeting XML (pending more compliant implementation)
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
-pub struct Prov;
+    /// The content of a tag, the variant being given by the name of the child element
+    #[derive(Serialize, Deserialize)]
+    struct Choice<T> {
+        #[serde(rename = "$value")]
+        value: T,
+    }
 
-/// Type corresponding to <purpose> in the EPP greeting XML (pending more compliant implementation)
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
-pub struct Purpose {
-    /// Data for the <admin> tag
-    pub admin: Admin,
-    /// Data for the <prov> tag
-    pub prov: Prov,
+    pub fn serialize<T: Serialize, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Choice { value }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        Ok(Choice::deserialize(deserializer)?.value)
+    }
+
+    pub mod optional {
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        pub fn serialize<T: Serialize, S: Serializer>(
+            value: &Option<T>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            value
+                .as_ref()
+                .map(|value| super::Choice { value })
+                .serialize(serializer)
+        }
+
+        pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<T>, D::Error> {
+            Ok(Option::<super::Choice<T>>::deserialize(deserializer)?.map(|choice| choice.value))
+        }
+    }
 }
 
-/// Type corresponding to <ours> in the EPP greeting XML (pending more compliant implementation)
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
-pub struct Ours;
+/// The access the registry provides to the data it collects, the content of <access> in the
+/// EPP greeting XML
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum Access {
+    /// Access to all data
+    All,
+    /// No access
+    None,
+    /// Data is not persistent, so no access is possible
+    Null,
+    /// Access to other data of a non-personal nature
+    Other,
+    /// Access to personal data
+    Personal,
+    /// Access to personal and other data
+    PersonalAndOther,
+}
 
-/// Type corresponding to <public> in the EPP greeting XML (pending more compliant implementation)
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
-pub struct Public;
+/// Type corresponding to <purpose> in the EPP greeting XML, with the purposes the registry
+/// collects data for
+#[derive(Serialize, Deserialize, Debug, PartialEq, Default)]
+pub struct Purpose {
+    /// Data is collected for administrative purposes
+    pub admin: Option<EmptyTag>,
+    /// Data is collected for contacting individuals about matters other than provisioning
+    pub contact: Option<EmptyTag>,
+    /// Data is collected for other purposes
+    pub other: Option<EmptyTag>,
+    /// Data is collected to provision the objects of the registry
+    pub prov: Option<EmptyTag>,
+}
 
-/// Type corresponding to <recipeint> in the EPP greeting XML (pending more compliant implementation)
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
-pub struct Recipient {
-    /// Data for the <ours> tag
-    pub ours: Ours,
-    /// Data for the <public> tag
-    pub public: Public,
+/// Type corresponding to <ours> in the EPP greeting XML
+#[derive(Serialize, Deserialize, Debug, PartialEq, Default)]
+pub struct Ours {
+    /// Description of the recipient
+    #[serde(rename = "recDesc")]
+    pub recipient_description: Option<StringValue>,
 }
 
-/// Type corresponding to <stated> in the EPP greeting XML (pending more compliant implementation)
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
-pub struct Stated;
+/// Type corresponding to <recipient> in the EPP greeting XML, with the recipients of the data
+/// the registry collects
+#[derive(Serialize, Deserialize, Debug, PartialEq, Default)]
+pub struct Recipient {
+    /// Other entities following other practices
+    pub other: Option<EmptyTag>,
+    /// The registry operator and its agents
+    #[serde(default)]
+    pub ours: Vec<Ours>,
+    /// Public forums
+    pub public: Option<EmptyTag>,
+    /// Other entities following the same practices
+    pub same: Option<EmptyTag>,
+    /// Unrelated third parties
+    pub unrelated: Option<EmptyTag>,
+}
 
-/// Type corresponding to <retention> in the EPP greeting XML (pending more compliant implementation)
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
-pub struct Retention {
-    /// Data for the <stated> tag
-    pub stated: Stated,
+/// How long the registry retains the data it collects, the content of <retention> in the EPP
+/// greeting XML
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum Retention {
+    /// Until business practices no longer require it
+    Business,
+    /// Indefinitely
+    Indefinite,
+    /// As required by law
+    Legal,
+    /// Data is not retained
+    None,
+    /// As stated in the registry's policy
+    Stated,
 }
 
-/// Type corresponding to <statement> in the EPP greeting XML (pending more compliant implementation)
+/// Type corresponding to <statement> in the EPP greeting XML
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Statement {
     /// Data for the <purpose> tag
@@ -132,29 +239,50 @@ pub struct Statement {
     /// Data for the <recipient> tag
     pub recipient: Recipient,
     /// Data for the <retention> tag
+    #[serde(with = "choice")]
     pub retention: Retention,
 }
 
-/// Type corresponding to <dcp> in the EPP greeting XML (pending more compliant implementation)
+/// When the data collection policy expires, the content of <expiry> in the EPP greeting XML
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum Expiry {
+    /// The policy expires at the given date
+    #[serde(rename = "absolute")]
+    Absolute(#[serde(with = "datetime")] DateTime<Utc>),
+    /// The policy expires the given XML Schema duration after the greeting, e.g. "P1Y"
+    #[serde(rename = "relative")]
+    Relative(String),
+}
+
+/// Type corresponding to <dcp> in the EPP greeting XML, the data collection policy of the
+/// registry
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Dcp {
     /// Data for the <access> tag
+    #[serde(with = "choice")]
     pub access: Access,
-    /// Data for the <statement> tag
-    pub statement: Statement,
+    /// Data for the <statement> tags
+    #[serde(rename = "statement")]
+    pub statements: Vec<Statement>,
+    /// Data for the <expiry> tag
+    #[serde(
+        default,
+        with = "choice::optional",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub expiry: Option<Expiry>,
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, ElementName)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(rename_all = "lowercase")]
-#[element_name(name = "greeting")]
 /// Type corresponding to the <greeting> tag in the EPP greeting XML
 pub struct Greeting {
     /// The service ID
     #[serde(rename = "svID")]
     pub service_id: String,
     /// The date from the EPP server
-    #[serde(rename = "svDate")]
-    pub service_date: String,
+    #[serde(rename = "svDate", with = "datetime")]
+    pub service_date: DateTime<Utc>,
     /// Data under the <svcMenu> element
     #[serde(rename = "svcMenu")]
     pub svc_menu: ServiceMenu,
@@ -162,40 +290,118 @@ pub struct Greeting {
     pub dcp: Dcp,
 }
 
-/// Type corresponding to the <undef> tag an EPP response XML
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
-pub struct Undef;
+impl ElementName for Greeting {
+    fn element_name(&self) -> &'static str {
+        "greeting"
+    }
+}
 
-/// Type corresponding to the <value> tag under <extValue> in an EPP response XML
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
-pub struct ResultValue {
-    /// The XML namespace for the <value> tag
-    #[serde(rename = "xmlns:epp")]
-    xmlns: String,
-    /// The <undef> element
-    pub undef: Undef,
+impl Greeting {
+    /// Returns whether the registry offers the object with the given namespace URI, for
+    /// instance "urn:ietf:params:xml:ns:domain-1.0"
+    pub fn supports_object(&self, uri: &str) -> bool {
+        self.objects().any(|obj_uri| obj_uri == uri)
+    }
+
+    /// Returns whether the registry offers the extension with the given namespace URI, for
+    /// instance "urn:ietf:params:xml:ns:rgp-1.0"
+    pub fn supports_extension(&self, uri: &str) -> bool {
+        self.extensions().any(|ext_uri| ext_uri == uri)
+    }
+
+    /// Returns whether the registry offers the given language
+    pub fn supports_language(&self, lang: &str) -> bool {
+        self.svc_menu
+            .languages
+            .iter()
+            .any(|language| language.to_string() == lang)
+    }
+
+    /// Returns the namespace URIs of the objects the registry offers
+    pub fn objects(&self) -> impl Iterator<Item = String> + '_ {
+        self.svc_menu
+            .services
+            .obj_uris
+            .iter()
+            .map(|uri| uri.to_string())
+    }
+
+    /// Returns the namespace URIs of the extensions the registry offers
+    pub fn extensions(&self) -> impl Iterator<Item = String> + '_ {
+        self.svc_menu
+            .services
+            .svc_ext
+            .iter()
+            .flat_map(|svc_ext| svc_ext.ext_uris.iter().flatten())
+            .map(|uri| uri.to_string())
+    }
+}
+
+/// Type corresponding to the <msg> and <reason> tags in an EPP response XML, the text of which
+/// may be in a language other than English
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ResultText {
+    /// The language of the text, if other than English
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    /// The text
+    #[serde(rename = "$value", default)]
+    pub text: String,
+}
+
+impl ResultText {
+    /// Creates a new ResultText in the default language
+    pub fn new(text: &str) -> ResultText {
+        ResultText {
+            lang: None,
+            text: text.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ResultText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
 }
 
 /// Type corresponding to the <extValue> tag in an EPP response XML
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct ExtValue {
-    /// Data under the <value> tag
-    pub value: ResultValue,
+    /// The raw XML under the <value> tag, usually the client-provided element that caused
+    /// the error
+    pub value: StringValue,
     /// Data under the <reason> tag
-    pub reason: StringValue,
+    pub reason: ResultText,
 }
 
 /// Type corresponding to the <result> tag in an EPP response XML
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct EppResult {
     /// The result code
-    pub code: u16,
+    pub code: ResultCode,
     /// The result message
     #[serde(rename = "msg")]
-    pub message: StringValue,
-    /// Data under the <extValue> tag
-    #[serde(rename = "extValue")]
-    pub ext_value: Option<ExtValue>,
+    pub message: ResultText,
+    /// The raw XML under the <value> tags, usually client-provided elements that caused the
+    /// error
+    #[serde(rename = "value", default)]
+    pub values: Vec<StringValue>,
+    /// Data under the <extValue> tags
+    #[serde(rename = "extValue", default)]
+    pub ext_values: Vec<ExtValue>,
+}
+
+impl EppResult {
+    /// Creates a new EppResult with the standard message for the code
+    pub fn new(code: ResultCode) -> EppResult {
+        EppResult {
+            code,
+            message: ResultText::new(code.message()),
+            values: Vec::new(),
+            ext_values: Vec::new(),
+        }
+    }
 }
 
 /// Type corresponding to the <trID> tag in an EPP response XML
@@ -217,21 +423,21 @@ pub struct MessageQueue {
     /// The message ID
     pub id: String,
     /// The message date
-    #[serde(rename = "qDate")]
-    pub date: Option<StringValue>,
+    #[serde(rename = "qDate", default, with = "datetime::optional")]
+    pub date: Option<DateTime<Utc>>,
     /// The message text
     #[serde(rename = "msg")]
     pub message: Option<StringValue>,
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, ElementName)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(rename_all = "lowercase")]
-#[element_name(name = "response")]
 /// Type corresponding to the &lt;response&gt; tag in an EPP response XML
 /// containing an &lt;extension&gt; tag
 pub struct CommandResponseWithExtension<T, E: ElementName> {
-    /// Data under the <result> tag
-    pub result: EppResult,
+    /// Data under the <result> tags
+    #[serde(rename = "result", deserialize_with = "deserialize_results")]
+    pub results: Vec<EppResult>,
     /// Data under the <msgQ> tag
     #[serde(rename = "msgQ")]
     pub message_queue: Option<MessageQueue>,
@@ -245,19 +451,43 @@ pub struct CommandResponseWithExtension<T, E: ElementName> {
     pub tr_ids: ResponseTRID,
 }
 
+impl<T, E: ElementName> ElementName for CommandResponseWithExtension<T, E> {
+    fn element_name(&self) -> &'static str {
+        "response"
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, ElementName)]
 #[element_name(name = "response")]
 /// Type corresponding to the &lt;response&gt; tag in an EPP response XML
 /// without <msgQ> or &lt;resData&gt; sections. Generally used for error handling
 pub struct CommandResponseStatus {
-    /// Data under the <result> tag
-    pub result: EppResult,
+    /// Data under the <result> tags
+    #[serde(rename = "result", deserialize_with = "deserialize_results")]
+    pub results: Vec<EppResult>,
     #[serde(rename = "trID")]
     /// Data under the <trID> tag
     pub tr_ids: ResponseTRID,
 }
 
+/// Deserializes the <result> tags of a response, of which there must be at least one
+fn deserialize_results<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<EppResult>, D::Error> {
+    let results = Vec::<EppResult>::deserialize(deserializer)?;
+    if results.is_empty() {
+        return Err(de::Error::custom("response has no <result> tags"));
+    }
+    Ok(results)
+}
+
 impl<T, E: ElementName> CommandResponseWithExtension<T, E> {
+    /// Returns the first result of the response, the one with the code determining the
+    /// outcome of the command
+    pub fn result(&self) -> Option<&EppResult> {
+        self.results.first()
+    }
+
     /// Returns the data under the corresponding &lt;resData&gt; from the EPP XML
     pub fn res_data(&self) -> Option<&T> {
         match &self.res_data {
@@ -273,3 +503,11 @@ impl<T, E: ElementName> CommandResponseWithExtension<T, E> {
         }
     }
 }
+
+impl CommandResponseStatus {
+    /// Returns the first result of the response, the one with the code determining the
+    /// outcome of the command
+    pub fn result(&self) -> Option<&EppResult> {
+        self.results.first()
+    }
+}
@@ -0,0 +1,141 @@
+//! Result codes of EPP responses as defined in RFC 5730
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Defines the `ResultCode` enum with the numeric value and standard message of each code
+macro_rules! result_codes {
+    ($($(#[$meta:meta])* $variant:ident = $code:expr => $message:expr,)*) => {
+        /// An EPP result code, the 'code' attr on &lt;result&gt; tags. Codes not defined in
+        /// RFC 5730 are kept as `Unknown`
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ResultCode {
+            $($(#[$meta])* $variant,)*
+            /// A result code not defined in RFC 5730
+            Unknown(u16),
+        }
+
+        impl ResultCode {
+            /// Returns the numeric value of the result code
+            pub fn code(&self) -> u16 {
+                match self {
+                    $(ResultCode::$variant => $code,)*
+                    ResultCode::Unknown(code) => *code,
+                }
+            }
+
+            /// Returns the standard message for the result code as given in RFC 5730
+            pub fn message(&self) -> &'static str {
+                match self {
+                    $(ResultCode::$variant => $message,)*
+                    ResultCode::Unknown(_) => "Unknown result code",
+                }
+            }
+        }
+
+        impl From<u16> for ResultCode {
+            fn from(code: u16) -> Self {
+                match code {
+                    $($code => ResultCode::$variant,)*
+                    _ => ResultCode::Unknown(code),
+                }
+            }
+        }
+    };
+}
+
+result_codes! {
+    CommandCompletedSuccessfully = 1000 => "Command completed successfully",
+    CommandCompletedSuccessfullyActionPending = 1001 => "Command completed successfully; action pending",
+    CommandCompletedSuccessfullyNoMessages = 1300 => "Command completed successfully; no messages",
+    CommandCompletedSuccessfullyAckToDequeue = 1301 => "Command completed successfully; ack to dequeue",
+    CommandCompletedSuccessfullyEndingSession = 1500 => "Command completed successfully; ending session",
+    UnknownCommand = 2000 => "Unknown command",
+    CommandSyntaxError = 2001 => "Command syntax error",
+    CommandUseError = 2002 => "Command use error",
+    RequiredParameterMissing = 2003 => "Required parameter missing",
+    ParameterValueRangeError = 2004 => "Parameter value range error",
+    ParameterValueSyntaxError = 2005 => "Parameter value syntax error",
+    UnimplementedProtocolVersion = 2100 => "Unimplemented protocol version",
+    UnimplementedCommand = 2101 => "Unimplemented command",
+    UnimplementedOption = 2102 => "Unimplemented option",
+    UnimplementedExtension = 2103 => "Unimplemented extension",
+    BillingFailure = 2104 => "Billing failure",
+    ObjectNotEligibleForRenewal = 2105 => "Object is not eligible for renewal",
+    ObjectNotEligibleForTransfer = 2106 => "Object is not eligible for transfer",
+    AuthenticationError = 2200 => "Authentication error",
+    AuthorizationError = 2201 => "Authorization error",
+    InvalidAuthorizationInformation = 2202 => "Invalid authorization information",
+    ObjectPendingTransfer = 2300 => "Object pending transfer",
+    ObjectNotPendingTransfer = 2301 => "Object not pending transfer",
+    ObjectExists = 2302 => "Object exists",
+    ObjectDoesNotExist = 2303 => "Object does not exist",
+    ObjectStatusProhibitsOperation = 2304 => "Object status prohibits operation",
+    ObjectAssociationProhibitsOperation = 2305 => "Object association prohibits operation",
+    ParameterValuePolicyError = 2306 => "Parameter value policy error",
+    UnimplementedObjectService = 2307 => "Unimplemented object service",
+    DataManagementPolicyViolation = 2308 => "Data management policy violation",
+    CommandFailed = 2400 => "Command failed",
+    CommandFailedServerClosingConnection = 2500 => "Command failed; server closing connection",
+    AuthenticationErrorServerClosingConnection = 2501 => "Authentication error; server closing connection",
+    SessionLimitExceededServerClosingConnection = 2502 => "Session limit exceeded; server closing connection",
+}
+
+impl ResultCode {
+    /// Returns whether the command succeeded, which is the case for all 1xxx codes
+    pub fn is_success(&self) -> bool {
+        (1000..2000).contains(&self.code())
+    }
+
+    /// Returns whether the command succeeded but the action is pending, to be completed by the
+    /// registry later
+    pub fn is_pending(&self) -> bool {
+        *self == ResultCode::CommandCompletedSuccessfullyActionPending
+    }
+
+    /// Returns whether the registry ends the session after the response
+    pub fn closes_session(&self) -> bool {
+        matches!(
+            self,
+            ResultCode::CommandCompletedSuccessfullyEndingSession
+                | ResultCode::CommandFailedServerClosingConnection
+                | ResultCode::AuthenticationErrorServerClosingConnection
+                | ResultCode::SessionLimitExceededServerClosingConnection
+        )
+    }
+
+    /// Returns whether the command failed for reasons on the registry side, so that sending
+    /// it again later, possibly on a new session, may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ResultCode::CommandFailed
+                | ResultCode::CommandFailedServerClosingConnection
+                | ResultCode::SessionLimitExceededServerClosingConnection
+        )
+    }
+}
+
+impl PartialEq<u16> for ResultCode {
+    fn eq(&self, other: &u16) -> bool {
+        self.code() == *other
+    }
+}
+
+impl fmt::Display for ResultCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+impl Serialize for ResultCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for ResultCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(ResultCode::from(u16::deserialize(deserializer)?))
+    }
+}
@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::epp::object::{EppObject, StringValue};
+use crate::epp::object::{serialize_text, EppObject, StringValue};
 use crate::epp::response::CommandResponse;
 
 /// Type that represents the &lt;epp&gt; tag for the EPP XML contact check response
@@ -12,7 +12,7 @@ pub type EppContactCheckResponse = EppObject<CommandResponse<ContactCheckResult>
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ContactCheck {
     /// The text of the &lt;id&gt; tag
-    #[serde(rename = "$value")]
+    #[serde(rename = "$value", serialize_with = "serialize_text")]
     pub id: StringValue,
     /// The avail attr on the &lt;id&gt; tag
     #[serde(rename = "avail")]
@@ -34,10 +34,10 @@ pub struct ContactCheckDataItem {
 pub struct ContactCheckData {
     /// XML namespace for contact response data
     #[serde(rename = "xmlns:contact")]
-    xmlns: String,
+    pub xmlns: String,
     /// XML schema location for contact response data
     #[serde(rename = "xsi:schemaLocation")]
-    schema_location: String,
+    pub schema_location: String,
     /// Data under the &lt;cd&gt; tag
     #[serde(rename = "cd")]
     pub contact_list: Vec<ContactCheckDataItem>,
@@ -1,8 +1,9 @@
 //! Types for EPP contact create response
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::epp::object::{EppObject, StringValue};
+use crate::epp::object::{datetime, EppObject, StringValue};
 use crate::epp::response::CommandResponse;
 
 /// Type that represents the &lt;epp&gt; tag for the EPP XML contact create response
@@ -13,15 +14,15 @@ pub type EppContactCreateResponse = EppObject<CommandResponse<ContactCreateResul
 pub struct ContactCreateData {
     /// XML namespace for contact response data
     #[serde(rename = "xmlns:contact")]
-    xmlns: String,
+    pub xmlns: String,
     /// XML schema location for contact response data
     #[serde(rename = "xsi:schemaLocation")]
-    schema_location: String,
+    pub schema_location: String,
     /// The contact id
     pub id: StringValue,
-    #[serde(rename = "crDate")]
+    #[serde(rename = "crDate", with = "datetime")]
     /// The contact creation date
-    pub created_at: StringValue,
+    pub created_at: DateTime<Utc>,
 }
 
 /// Type that represents the &lt;resData&gt; tag for contact create response
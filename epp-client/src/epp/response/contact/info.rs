@@ -1,9 +1,10 @@
 //! Types for EPP contact info response
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::epp::object::data::{AuthInfo, ContactStatus, Phone, PostalInfo};
-use crate::epp::object::{EppObject, StringValue};
+use crate::epp::object::data::{AuthInfo, ContactStatus, Disclose, Phone, PostalInfo, Status};
+use crate::epp::object::{datetime, EppObject, StringValue};
 use crate::epp::response::CommandResponse;
 
 /// Type that represents the &lt;epp&gt; tag for the EPP XML contact info response
@@ -14,20 +15,20 @@ pub type EppContactInfoResponse = EppObject<CommandResponse<ContactInfoResult>>;
 pub struct ContactInfoData {
     /// XML namespace for contact response data
     #[serde(rename = "xmlns:contact")]
-    xmlns: String,
+    pub xmlns: String,
     /// XML schema location for contact response data
     #[serde(rename = "xsi:schemaLocation")]
-    schema_location: String,
+    pub schema_location: String,
     /// The contact id
     pub id: StringValue,
     /// The contact ROID
     pub roid: StringValue,
     /// The list of contact statuses
     #[serde(rename = "status")]
-    pub statuses: Vec<ContactStatus>,
-    /// The postal info for the contact
+    pub statuses: Vec<Status<ContactStatus>>,
+    /// The postal info for the contact, in "int" and/or "loc" form
     #[serde(rename = "postalInfo")]
-    pub postal_info: PostalInfo,
+    pub postal_info: Vec<PostalInfo>,
     /// The voice data for the contact
     pub voice: Phone,
     /// The fax data for the contact
@@ -41,20 +42,29 @@ pub struct ContactInfoData {
     #[serde(rename = "crID")]
     pub creator_id: StringValue,
     /// The creation date
-    #[serde(rename = "crDate")]
-    pub created_at: StringValue,
+    #[serde(rename = "crDate", with = "datetime")]
+    pub created_at: DateTime<Utc>,
     /// The epp user who last updated the contact
     #[serde(rename = "upID")]
     pub updater_id: Option<StringValue>,
     /// The last update date
-    #[serde(rename = "upDate")]
-    pub updated_at: Option<StringValue>,
+    #[serde(rename = "upDate", default, with = "datetime::optional")]
+    pub updated_at: Option<DateTime<Utc>>,
     /// The contact transfer date
-    #[serde(rename = "trDate")]
-    pub transferred_at: Option<StringValue>,
+    #[serde(rename = "trDate", default, with = "datetime::optional")]
+    pub transferred_at: Option<DateTime<Utc>>,
     /// The contact auth info
     #[serde(rename = "authInfo")]
     pub auth_info: Option<AuthInfo>,
+    /// The contact fields the registry does or does not publish
+    pub disclose: Option<Disclose>,
+}
+
+impl ContactInfoData {
+    /// Returns the postal info of the given type, "int" or "loc", if the registry returned it
+    pub fn postal_info(&self, info_type: &str) -> Option<&PostalInfo> {
+        self.postal_info.iter().find(|p| p.info_type == info_type)
+    }
 }
 
 /// Type that represents the &lt;resData&gt; tag for contact info response
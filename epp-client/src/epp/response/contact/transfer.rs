@@ -0,0 +1,54 @@
+//! Types for EPP contact transfer response
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::epp::object::{datetime, EppObject, StringValue};
+use crate::epp::response::CommandResponse;
+use crate::epp::response::EppCommandResponse;
+
+/// Type that represents the &lt;epp&gt; tag for the EPP XML contact transfer request response
+pub type EppContactTransferRequestResponse = EppObject<CommandResponse<ContactTransferResult>>;
+/// Type that represents the &lt;epp&gt; tag for the EPP XML contact transfer approval response
+pub type EppContactTransferApproveResponse = EppCommandResponse;
+/// Type that represents the &lt;epp&gt; tag for the EPP XML contact transfer rejection response
+pub type EppContactTransferRejectResponse = EppCommandResponse;
+/// Type that represents the &lt;epp&gt; tag for the EPP XML contact transfer cancellation response
+pub type EppContactTransferCancelResponse = EppCommandResponse;
+/// Type that represents the &lt;epp&gt; tag for the EPP XML contact transfer query response
+pub type EppContactTransferQueryResponse = EppObject<CommandResponse<ContactTransferResult>>;
+
+/// Type that represents the &lt;trnData&gt; tag for contact transfer response
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ContactTransferData {
+    /// XML namespace for contact response data
+    #[serde(rename = "xmlns:contact")]
+    pub xmlns: String,
+    /// XML schema location for contact response data
+    #[serde(rename = "xsi:schemaLocation")]
+    pub schema_location: String,
+    /// The contact id
+    pub id: StringValue,
+    /// The contact transfer status
+    #[serde(rename = "trStatus")]
+    pub transfer_status: StringValue,
+    /// The epp user who requested the transfer
+    #[serde(rename = "reID")]
+    pub requester_id: StringValue,
+    /// The transfer request date
+    #[serde(rename = "reDate", with = "datetime")]
+    pub requested_at: DateTime<Utc>,
+    /// The epp user who should acknowledge the transfer request
+    #[serde(rename = "acID")]
+    pub ack_id: StringValue,
+    /// The date by which the acknowledgment should be made
+    #[serde(rename = "acDate", with = "datetime")]
+    pub ack_by: DateTime<Utc>,
+}
+
+/// Type that represents the &lt;resData&gt; tag for contact transfer response
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ContactTransferResult {
+    /// Data under the &lt;trnData&gt; tag
+    #[serde(rename = "trnData")]
+    pub transfer_data: ContactTransferData,
+}
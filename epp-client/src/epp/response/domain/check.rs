@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::epp::object::{EppObject, StringValue};
+use crate::epp::object::{serialize_text, EppObject, StringValue};
 use crate::epp::response::CommandResponse;
 
 /// Type that represents the &lt;epp&gt; tag for the EPP XML domain check response
@@ -12,7 +12,7 @@ pub type EppDomainCheckResponse = EppObject<CommandResponse<DomainCheckResult>>;
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DomainCheck {
     /// The domain name
-    #[serde(rename = "$value")]
+    #[serde(rename = "$value", serialize_with = "serialize_text")]
     pub name: StringValue,
     /// The domain (un)availability
     #[serde(rename = "avail")]
@@ -34,10 +34,10 @@ pub struct DomainCheckDataItem {
 pub struct DomainCheckData {
     /// XML namespace for domain response data
     #[serde(rename = "xmlns:domain")]
-    xmlns: String,
+    pub xmlns: String,
     /// XML schema location for domain response data
     #[serde(rename = "xsi:schemaLocation")]
-    schema_location: String,
+    pub schema_location: String,
     /// Data under the &lt;cd&gt; tag
     #[serde(rename = "cd")]
     pub domain_list: Vec<DomainCheckDataItem>,
@@ -1,8 +1,9 @@
 //! Types for EPP domain create response
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::epp::object::{EppObject, StringValue};
+use crate::epp::object::{datetime, EppObject, StringValue};
 use crate::epp::response::CommandResponse;
 
 /// Type that represents the &lt;epp&gt; tag for the EPP XML domain create response
@@ -13,18 +14,18 @@ pub type EppDomainCreateResponse = EppObject<CommandResponse<DomainCreateResult>
 pub struct DomainCreateData {
     /// XML namespace for domain response data
     #[serde(rename = "xmlns:domain")]
-    xmlns: String,
+    pub xmlns: String,
     /// XML schema location for domain response data
     #[serde(rename = "xsi:schemaLocation")]
-    schema_location: String,
+    pub schema_location: String,
     /// The domain name
     pub name: StringValue,
     /// The creation date
-    #[serde(rename = "crDate")]
-    pub created_at: StringValue,
+    #[serde(rename = "crDate", with = "datetime")]
+    pub created_at: DateTime<Utc>,
     /// The expiry date
-    #[serde(rename = "exDate")]
-    pub expiring_at: StringValue,
+    #[serde(rename = "exDate", with = "datetime")]
+    pub expiring_at: DateTime<Utc>,
 }
 
 /// Type that represents the &lt;resData&gt; tag for domain create response
@@ -1,9 +1,10 @@
 //! Types for EPP domain info response
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::epp::object::data::{AuthInfo, DomainContact, DomainStatus, HostAttr};
-use crate::epp::object::{EppObject, StringValue};
+use crate::epp::object::data::{AuthInfo, DomainContact, DomainStatus, HostAttr, Status};
+use crate::epp::object::{datetime, EppObject, StringValue};
 use crate::epp::response::domain::rgp::request::RgpRequestResult;
 use crate::epp::response::CommandResponseWithExtension;
 
@@ -27,17 +28,17 @@ pub struct DomainNsList {
 pub struct DomainInfoData {
     /// XML namespace for domain response data
     #[serde(rename = "xmlns:domain")]
-    xmlns: String,
+    pub xmlns: String,
     /// XML schema location for domain response data
     #[serde(rename = "xsi:schemaLocation")]
-    schema_location: String,
+    pub schema_location: String,
     /// The domain name
     pub name: StringValue,
     /// The domain ROID
     pub roid: StringValue,
     /// The list of domain statuses
     #[serde(rename = "status")]
-    pub statuses: Vec<DomainStatus>,
+    pub statuses: Vec<Status<DomainStatus>>,
     /// The domain registrant
     pub registrant: StringValue,
     /// The list of domain contacts
@@ -56,20 +57,20 @@ pub struct DomainInfoData {
     #[serde(rename = "crID")]
     pub creator_id: StringValue,
     /// The domain creation date
-    #[serde(rename = "crDate")]
-    pub created_at: StringValue,
+    #[serde(rename = "crDate", with = "datetime")]
+    pub created_at: DateTime<Utc>,
     /// The epp user who last updated the domain
     #[serde(rename = "upID")]
     pub updater_id: StringValue,
     /// The domain last updated date
-    #[serde(rename = "upDate")]
-    pub updated_at: StringValue,
+    #[serde(rename = "upDate", with = "datetime")]
+    pub updated_at: DateTime<Utc>,
     /// The domain expiry date
-    #[serde(rename = "exDate")]
-    pub expiring_at: StringValue,
+    #[serde(rename = "exDate", with = "datetime")]
+    pub expiring_at: DateTime<Utc>,
     /// The domain transfer date
-    #[serde(rename = "trDate")]
-    pub transferred_at: Option<StringValue>,
+    #[serde(rename = "trDate", default, with = "datetime::optional")]
+    pub transferred_at: Option<DateTime<Utc>>,
     /// The domain auth info
     #[serde(rename = "authInfo")]
     pub auth_info: Option<AuthInfo>,
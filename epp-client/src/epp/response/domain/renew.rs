@@ -1,7 +1,8 @@
 //! Types for EPP domain renew response
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::epp::object::{EppObject, StringValue};
+use crate::epp::object::{datetime, EppObject, StringValue};
 use crate::epp::response::CommandResponse;
 
 /// Type that represents the &lt;epp&gt; tag for the EPP XML domain renew response
@@ -12,15 +13,15 @@ pub type EppDomainRenewResponse = EppObject<CommandResponse<DomainRenewResult>>;
 pub struct DomainRenewData {
     /// XML namespace for domain response data
     #[serde(rename = "xmlns:domain")]
-    xmlns: String,
+    pub xmlns: String,
     /// XML schema location for domain response data
     #[serde(rename = "xsi:schemaLocation")]
-    schema_location: String,
+    pub schema_location: String,
     /// The name of the domain
     pub name: StringValue,
     /// The new expiry date after renewal
-    #[serde(rename = "exDate")]
-    pub expiring_at: StringValue,
+    #[serde(rename = "exDate", with = "datetime")]
+    pub expiring_at: DateTime<Utc>,
 }
 
 /// Type that represents the &lt;resData&gt; tag for domain renew response
@@ -22,10 +22,10 @@ pub struct RgpStatus {
 /// Type that represents the &lt;resData&gt; tag for domain transfer response
 pub struct RgpRequestResult {
     #[serde(rename = "xmlns:rgp")]
-    xmlns: String,
+    pub xmlns: String,
     /// XML schema location for domain response data
     #[serde(rename = "xsi:schemaLocation")]
-    schema_location: String,
+    pub schema_location: String,
     /// Data under the &lt;rgpStatus&gt; tag
     #[serde(rename = "rgpStatus")]
     pub rgp_status: RgpStatus,
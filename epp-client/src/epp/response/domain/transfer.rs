@@ -1,7 +1,8 @@
 //! Types for EPP domain transfer response
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::epp::object::{EppObject, StringValue};
+use crate::epp::object::{datetime, EppObject, StringValue};
 use crate::epp::response::CommandResponse;
 use crate::epp::response::EppCommandResponse;
 
@@ -21,10 +22,10 @@ pub type EppDomainTransferQueryResponse = EppObject<CommandResponse<DomainTransf
 pub struct DomainTransferData {
     /// XML namespace for domain response data
     #[serde(rename = "xmlns:domain")]
-    xmlns: String,
+    pub xmlns: String,
     /// XML schema location for domain response data
     #[serde(rename = "xsi:schemaLocation")]
-    schema_location: String,
+    pub schema_location: String,
     /// The domain name
     pub name: StringValue,
     /// The domain transfer status
@@ -34,17 +35,17 @@ pub struct DomainTransferData {
     #[serde(rename = "reID")]
     pub requester_id: StringValue,
     /// The transfer rquest date
-    #[serde(rename = "reDate")]
-    pub requested_at: StringValue,
+    #[serde(rename = "reDate", with = "datetime")]
+    pub requested_at: DateTime<Utc>,
     /// The epp user who should acknowledge the transfer request
     #[serde(rename = "acID")]
     pub ack_id: StringValue,
     /// THe date by which the acknowledgment should be made
-    #[serde(rename = "acDate")]
-    pub ack_by: StringValue,
+    #[serde(rename = "acDate", with = "datetime")]
+    pub ack_by: DateTime<Utc>,
     /// The domain expiry date
-    #[serde(rename = "exDate")]
-    pub expiring_at: StringValue,
+    #[serde(rename = "exDate", with = "datetime")]
+    pub expiring_at: DateTime<Utc>,
 }
 
 /// Type that represents the &lt;resData&gt; tag for domain transfer response
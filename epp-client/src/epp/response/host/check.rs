@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::epp::object::{EppObject, StringValue};
+use crate::epp::object::{serialize_text, EppObject, StringValue};
 use crate::epp::response::CommandResponse;
 
 /// Type that represents the &lt;epp&gt; tag for the EPP XML host check response
@@ -12,7 +12,7 @@ pub type EppHostCheckResponse = EppObject<CommandResponse<HostCheckResult>>;
 #[derive(Serialize, Deserialize, Debug)]
 pub struct HostCheck {
     /// The host name
-    #[serde(rename = "$value")]
+    #[serde(rename = "$value", serialize_with = "serialize_text")]
     pub name: StringValue,
     /// The host (un)availability
     #[serde(rename = "avail")]
@@ -34,10 +34,10 @@ pub struct HostCheckDataItem {
 pub struct HostCheckData {
     /// XML namespace for host response data
     #[serde(rename = "xmlns:host")]
-    xmlns: String,
+    pub xmlns: String,
     /// XML schema location for host response data
     #[serde(rename = "xsi:schemaLocation")]
-    schema_location: String,
+    pub schema_location: String,
     /// Data under the &lt;cd&gt; tag
     #[serde(rename = "cd")]
     pub host_list: Vec<HostCheckDataItem>,
@@ -1,8 +1,9 @@
 //! Types for EPP host create response
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::epp::object::{EppObject, StringValue};
+use crate::epp::object::{datetime, EppObject, StringValue};
 use crate::epp::response::CommandResponse;
 
 /// Type that represents the &lt;epp&gt; tag for the EPP XML host create response
@@ -13,15 +14,15 @@ pub type EppHostCreateResponse = EppObject<CommandResponse<HostCreateResult>>;
 pub struct HostCreateData {
     /// XML namespace for host response data
     #[serde(rename = "xmlns:host")]
-    xmlns: String,
+    pub xmlns: String,
     /// XML schema location for host response data
     #[serde(rename = "xsi:schemaLocation")]
-    schema_location: String,
+    pub schema_location: String,
     /// The host name
     pub name: StringValue,
     /// The host creation date
-    #[serde(rename = "crDate")]
-    pub created_at: StringValue,
+    #[serde(rename = "crDate", with = "datetime")]
+    pub created_at: DateTime<Utc>,
 }
 
 /// Type that represents the &lt;resData&gt; tag for host check response
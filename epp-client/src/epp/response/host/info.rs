@@ -1,9 +1,10 @@
 //! Types for EPP host info response
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::epp::object::data::{HostAddr, HostStatus};
-use crate::epp::object::{EppObject, StringValue};
+use crate::epp::object::data::{HostAddr, HostStatus, Status};
+use crate::epp::object::{datetime, EppObject, StringValue};
 use crate::epp::response::CommandResponse;
 
 /// Type that represents the &lt;epp&gt; tag for the EPP XML host info response
@@ -14,38 +15,38 @@ pub type EppHostInfoResponse = EppObject<CommandResponse<HostInfoResult>>;
 pub struct HostInfoData {
     /// XML namespace for host response data
     #[serde(rename = "xmlns:host")]
-    xmlns: String,
+    pub xmlns: String,
     /// XML schema location for host response data
     #[serde(rename = "xsi:schemaLocation")]
-    schema_location: String,
+    pub schema_location: String,
     /// The host name
     pub name: StringValue,
     /// The host ROID
     pub roid: StringValue,
     /// The list of host statuses
     #[serde(rename = "status")]
-    pub statuses: Vec<HostStatus>,
-    /// The list of host IP addresses
-    #[serde(rename = "addr")]
+    pub statuses: Vec<Status<HostStatus>>,
+    /// The list of host IPv4 and IPv6 addresses
+    #[serde(rename = "addr", default)]
     pub addresses: Vec<HostAddr>,
     /// The epp user to whom the host belongs
     #[serde(rename = "clID")]
     pub client_id: StringValue,
-    /// THe epp user that created the host
+    /// The epp user that created the host
     #[serde(rename = "crID")]
     pub creator_id: StringValue,
     /// The host creation date
-    #[serde(rename = "crDate")]
-    pub created_at: StringValue,
+    #[serde(rename = "crDate", with = "datetime")]
+    pub created_at: DateTime<Utc>,
     /// The epp user that last updated the host
     #[serde(rename = "upID")]
     pub updater_id: Option<StringValue>,
     /// The host last update date
-    #[serde(rename = "upDate")]
-    pub updated_at: Option<StringValue>,
+    #[serde(rename = "upDate", default, with = "datetime::optional")]
+    pub updated_at: Option<DateTime<Utc>>,
     /// The host transfer date
-    #[serde(rename = "trDate")]
-    pub transferred_at: Option<StringValue>,
+    #[serde(rename = "trDate", default, with = "datetime::optional")]
+    pub transferred_at: Option<DateTime<Utc>>,
 }
 
 /// Type that represents the &lt;resData&gt; tag for host info response
@@ -1,7 +1,8 @@
 //! Types for EPP message poll response
 
-use crate::epp::object::{EppObject, StringValue};
+use crate::epp::object::{datetime, EppObject, StringValue};
 use crate::epp::response::CommandResponse;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Type that represents the &lt;epp&gt; tag for the EPP XML message poll response
@@ -12,7 +13,7 @@ pub type EppMessagePollResponse = EppObject<CommandResponse<MessagePollResult>>;
 pub struct MessageDomainTransferData {
     /// XML namespace for message response data
     #[serde(rename = "xmlns:obj")]
-    xmlns: String,
+    pub xmlns: String,
     /// The name of the domain under transfer
     pub name: StringValue,
     /// The domain transfer status
@@ -22,17 +23,17 @@ pub struct MessageDomainTransferData {
     #[serde(rename = "reID")]
     pub requester_id: StringValue,
     /// The date of the transfer request
-    #[serde(rename = "reDate")]
-    pub requested_at: StringValue,
+    #[serde(rename = "reDate", with = "datetime")]
+    pub requested_at: DateTime<Utc>,
     /// The epp user who should acknowledge the transfer request
     #[serde(rename = "acID")]
     pub ack_id: StringValue,
     /// The date by which the transfer request should be acknowledged
-    #[serde(rename = "acDate")]
-    pub ack_by: StringValue,
+    #[serde(rename = "acDate", with = "datetime")]
+    pub ack_by: DateTime<Utc>,
     /// The domain expiry date
-    #[serde(rename = "exDate")]
-    pub expiring_at: StringValue,
+    #[serde(rename = "exDate", with = "datetime")]
+    pub expiring_at: DateTime<Utc>,
 }
 
 /// Type that represents the &lt;resData&gt; tag for message poll response
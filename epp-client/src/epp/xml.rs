@@ -17,6 +17,7 @@ pub const EPP_HOST_XMLNS: &str = "urn:ietf:params:xml:ns:host-1.0";
 
 pub const EPP_CONTACT_SCHEMA_LOCATION: &str = "urn:ietf:params:xml:ns:contact-1.0 contact-1.0.xsd";
 pub const EPP_DOMAIN_SCHEMA_LOCATION: &str = "urn:ietf:params:xml:ns:domain-1.0 domain-1.0.xsd";
+pub const EPP_HOST_SCHEMA_LOCATION: &str = "urn:ietf:params:xml:ns:host-1.0 host-1.0.xsd";
 
 pub const EPP_DOMAIN_RGP_EXT_XMLNS: &str = "urn:ietf:params:xml:ns:rgp-1.0";
 pub const EPP_DOMAIN_RGP_EXT_SCHEMA_LOCATION: &str = "urn:ietf:params:xml:ns:rgp-1.0 rgp-1.0.xsd";
@@ -30,4 +31,9 @@ pub trait EppXml {
 
     fn serialize(&self) -> Result<String, Box<dyn Error>>;
     fn deserialize(epp_xml: &str) -> Result<Self::Output, error::Error>;
+
+    /// Returns true for requests that the client can safely resend on a new session
+    fn is_idempotent(&self) -> bool {
+        false
+    }
 }
@@ -1,9 +1,11 @@
 //! XML serialization using the `quick-xml` library
 
 use quick_xml::de::from_str;
+use quick_xml::events::Event;
 use quick_xml::se;
+use quick_xml::Reader;
 use serde::{de::DeserializeOwned, Serialize};
-use std::{error::Error, fmt::Debug};
+use std::{borrow::Cow, error::Error, fmt::Debug};
 
 use crate::epp::object::{ElementName, EppObject};
 use crate::epp::xml::{EppXml, EPP_XML_HEADER};
@@ -21,7 +23,7 @@ impl<T: Serialize + DeserializeOwned + ElementName + Debug> EppXml for EppObject
 
     /// Deserializes an EPP XML document to an EppObject instance
     fn deserialize(epp_xml: &str) -> Result<Self::Output, error::Error> {
-        let object: Self::Output = match from_str(epp_xml) {
+        let object: Self::Output = match from_str(&escape_result_values(epp_xml)) {
             Ok(v) => v,
             Err(e) => {
                 return Err(error::Error::EppDeserializationError(
@@ -32,4 +34,67 @@ impl<T: Serialize + DeserializeOwned + ElementName + Debug> EppXml for EppObject
         // object.xml = Some(epp_xml.to_string());
         Ok(object)
     }
+
+    fn is_idempotent(&self) -> bool {
+        self.data.is_idempotent()
+    }
+}
+
+/// Escapes the contents of the <value> tags in <result> tags, so that the XML elements echoed
+/// by the registry, which may be anything, are deserialized as the text of the tags. The tags
+/// are found with the quick-xml reader, so comments, CDATA sections and attribute values are
+/// not mistaken for them
+fn escape_result_values(xml: &str) -> Cow<'_, str> {
+    let mut reader = Reader::from_str(xml);
+    reader.check_end_names(false);
+
+    let mut buf = Vec::new();
+    let mut escaped = String::new();
+    let mut copied = 0;
+    let mut in_result = false;
+    // The offset right after the start tag of the value being escaped, if any, and the depth
+    // of the elements in it
+    let mut value_start = None;
+    let mut depth = 0;
+
+    loop {
+        let offset = reader.buffer_position();
+        let event = match reader.read_event(&mut buf) {
+            Ok(Event::Eof) | Err(_) => break,
+            Ok(event) => event,
+        };
+
+        match (event, value_start) {
+            (Event::Start(_), Some(_)) => depth += 1,
+            (Event::End(_), Some(_)) if depth > 0 => depth -= 1,
+            (Event::End(_), Some(start)) => {
+                let value = xml[start..offset]
+                    .trim()
+                    .replace('&', "&amp;")
+                    .replace('<', "&lt;")
+                    .replace('>', "&gt;");
+
+                escaped.push_str(&xml[copied..start]);
+                escaped.push_str(&value);
+                copied = offset;
+                value_start = None;
+            }
+            (_, Some(_)) => {}
+            (Event::Start(e), None) if e.local_name() == b"result" => in_result = true,
+            (Event::End(e), None) if e.local_name() == b"result" => in_result = false,
+            (Event::Start(e), None) if in_result && e.local_name() == b"value" => {
+                value_start = Some(reader.buffer_position());
+            }
+            _ => {}
+        }
+
+        buf.clear();
+    }
+
+    if copied == 0 {
+        return Cow::Borrowed(xml);
+    }
+
+    escaped.push_str(&xml[copied..]);
+    Cow::Owned(escaped)
 }
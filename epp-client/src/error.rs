@@ -1,21 +1,105 @@
 //! Error types to wrap internal errors and make EPP errors easier to read
 
-use crate::epp::response::EppCommandResponseError;
+use crate::epp::response::{EppCommandResponseError, EppResult, ResultCode};
 use std::fmt::Display;
 
 /// Error enum holding the possible error types
 #[derive(Debug)]
 pub enum Error {
+    /// An I/O error on an established connection to the registry
     EppConnectionError(std::io::Error),
-    EppCommandError(EppCommandResponseError),
+    /// The registry closed the connection or ended the session
+    EppConnectionClosed,
+    /// Invalid or missing registry connection settings
+    EppConfigError(String),
+    /// The registry host name could not be resolved
+    EppResolutionError(String),
+    /// The TCP connection to the registry could not be established
+    EppConnectError(std::io::Error),
+    /// Loading the client certificate or key, or the TLS handshake failed
+    EppTlsError(String),
+    /// The greeting could not be read from a new connection
+    EppGreetingError(String),
+    /// The registry does not announce a service extension the client requires
+    EppNegotiationError(String),
+    /// The registry rejected the login request
+    EppLoginError(Box<EppCommandError>),
+    /// An operation on the connection did not complete before its deadline. The session is
+    /// unusable afterwards
+    EppTimeoutError(String),
+    /// An EPP message had an invalid length header
+    EppFramingError(String),
+    /// The registry returned an error result for a command
+    EppCommandError(Box<EppCommandError>),
+    /// A transcript could not be read or written
+    EppTranscriptError(String),
     EppDeserializationError(String),
     Other(String),
 }
 
-/// An EPP XML error
-#[derive(Debug)]
+/// The error result of a command rejected by the registry
+#[derive(Debug, Clone, PartialEq)]
 pub struct EppCommandError {
-    pub epp_error: EppCommandResponseError,
+    /// The result code
+    pub code: ResultCode,
+    /// The result message
+    pub message: String,
+    /// The reasons given in the &lt;extValue&gt; tags of all results
+    pub reasons: Vec<String>,
+    /// All results of the response, including the values the registry flagged and the
+    /// language of the messages
+    pub results: Vec<EppResult>,
+    /// The client transaction id echoed by the registry, if any
+    pub client_tr_id: Option<String>,
+    /// The server transaction id
+    pub server_tr_id: String,
+}
+
+impl From<EppCommandResponseError> for EppCommandError {
+    fn from(response: EppCommandResponseError) -> Self {
+        // A response without results is reported as a failed command
+        let (code, message) = match response.data.result() {
+            Some(result) => (result.code, result.message.to_string()),
+            None => (ResultCode::CommandFailed, String::new()),
+        };
+        let tr_ids = &response.data.tr_ids;
+
+        EppCommandError {
+            code,
+            message,
+            reasons: response
+                .data
+                .results
+                .iter()
+                .flat_map(|result| &result.ext_values)
+                .map(|ext_value| ext_value.reason.to_string())
+                .collect(),
+            client_tr_id: tr_ids.client_tr_id.as_ref().map(|id| id.to_string()),
+            server_tr_id: tr_ids.server_tr_id.to_string(),
+            results: response.data.results,
+        }
+    }
+}
+
+impl Display for EppCommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, result) in self.results.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{} {}", result.code, result.message)?;
+            for value in &result.values {
+                write!(f, ": {}", value)?;
+            }
+            for ext_value in &result.ext_values {
+                write!(f, "; {}", ext_value.reason)?;
+                if !ext_value.value.to_string().is_empty() {
+                    write!(f, ": {}", ext_value.value)?;
+                }
+            }
+        }
+        write!(f, " (svTRID {})", self.server_tr_id)
+    }
 }
 
 impl std::error::Error for Error {}
@@ -23,11 +107,30 @@ impl std::error::Error for Error {}
 impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Error::EppCommandError(e) => {
-                write!(f, "epp-client EppCommandError: {}", e.data.result.message)
+            Error::EppCommandError(e) => write!(f, "epp-client EppCommandError: {}", e),
+            Error::EppConnectionError(e) => write!(f, "epp-client EppConnectionError: {}", e),
+            Error::EppConnectionClosed => {
+                write!(
+                    f,
+                    "epp-client EppConnectionClosed: the registry closed the session"
+                )
+            }
+            Error::EppConfigError(e) => write!(f, "epp-client EppConfigError: {}", e),
+            Error::EppResolutionError(e) => write!(f, "epp-client EppResolutionError: {}", e),
+            Error::EppConnectError(e) => write!(f, "epp-client EppConnectError: {}", e),
+            Error::EppTlsError(e) => write!(f, "epp-client EppTlsError: {}", e),
+            Error::EppGreetingError(e) => write!(f, "epp-client EppGreetingError: {}", e),
+            Error::EppNegotiationError(e) => {
+                write!(f, "epp-client EppNegotiationError: {}", e)
+            }
+            Error::EppLoginError(e) => write!(f, "epp-client EppLoginError: {}", e),
+            Error::EppTimeoutError(e) => write!(f, "epp-client EppTimeoutError: {}", e),
+            Error::EppFramingError(e) => write!(f, "epp-client EppFramingError: {}", e),
+            Error::EppTranscriptError(e) => write!(f, "epp-client EppTranscriptError: {}", e),
+            Error::EppDeserializationError(e) => {
+                write!(f, "epp-client EppDeserializationError: {}", e)
             }
             Error::Other(e) => write!(f, "epp-client Exception: {}", e),
-            _ => write!(f, "epp-client Exception: {:?}", self),
         }
     }
 }
@@ -53,10 +53,14 @@
 //! [registry.verisign.tls_files]
 //! # the full client certificate chain in PEM format
 //! cert_chain = '/path/to/certificate/chain/pemfile'
-//! # the RSA private key for your certificate
+//! # the private key for your certificate, in RSA, PKCS#8 or SEC1 EC format
 //! key = '/path/to/private/key/pemfile'
 //! ```
 //!
+//! Reading the config file is enabled by the default `config-file` feature. The connection details can
+//! also be set in code with [`EppClient::builder()`](connection/builder/struct.EppClientBuilder.html),
+//! in which case the feature can be disabled.
+//!
 //! ## Operation
 //!
 //! Once the config is set correctly, you can create a mut variable of type [`EppClient`] to transact
@@ -89,6 +93,9 @@
 //!     response.data.res_data.unwrap().check_data.domain_list
 //!         .iter()
 //!         .for_each(|chk| println!("Domain: {}, Available: {}", chk.domain.name, chk.domain.available));
+//!
+//!     // Log out and close the connection
+//!     client.close().await.unwrap();
 //! }
 //! ```
 //!
@@ -107,6 +114,7 @@ pub mod connection;
 pub mod epp;
 pub mod error;
 pub use connection::client::EppClient;
+pub use connection::pool::EppPool;
 
 #[cfg(test)]
 pub mod tests;
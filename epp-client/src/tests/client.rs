@@ -0,0 +1,327 @@
+//! `EppClient` tests against a fake registry over an in-memory transport
+
+use std::time::Duration;
+use tokio::io::{duplex, DuplexStream};
+use tokio::task::JoinHandle;
+
+use super::{get_xml, CLTRID};
+use crate::config::{EppClientConnection, EppClientReconnectPolicy};
+use crate::connection::codec::EppCodec;
+use crate::connection::registry::ConnectionStream;
+use crate::epp::response::ResultCode;
+use crate::epp::*;
+use crate::error;
+use crate::EppClient;
+
+/// Sends the greeting, then answers each request with the next response and returns the
+/// requests received. The stream is closed once the responses run out
+pub(super) fn fake_registry(stream: DuplexStream, responses: Vec<&str>) -> JoinHandle<Vec<String>> {
+    fake_registry_with_greeting(stream, "response/greeting.xml", responses)
+}
+
+/// Same as `fake_registry`, sending the given greeting fixture
+fn fake_registry_with_greeting(
+    mut stream: DuplexStream,
+    greeting: &str,
+    responses: Vec<&str>,
+) -> JoinHandle<Vec<String>> {
+    let greeting = get_xml(greeting).unwrap();
+    let responses: Vec<String> = responses.iter().map(|r| get_xml(r).unwrap()).collect();
+
+    tokio::spawn(async move {
+        let codec = EppCodec::default();
+        let mut requests = Vec::new();
+
+        codec
+            .write_frame(&mut stream, greeting.as_bytes())
+            .await
+            .unwrap();
+
+        for response in responses {
+            let request = match codec.read_frame(&mut stream).await {
+                Ok(request) => request,
+                Err(_) => break,
+            };
+            requests.push(String::from_utf8(request).unwrap());
+
+            codec
+                .write_frame(&mut stream, response.as_bytes())
+                .await
+                .unwrap();
+        }
+
+        requests
+    })
+}
+
+/// Sends the greeting and answers the login, then reads requests without ever answering them
+/// until the client closes the stream, returning the requests received after the login
+fn silent_registry(mut stream: DuplexStream) -> JoinHandle<Vec<String>> {
+    let greeting = get_xml("response/greeting.xml").unwrap();
+    let login = get_xml("response/login.xml").unwrap();
+
+    tokio::spawn(async move {
+        let codec = EppCodec::default();
+        let mut requests = Vec::new();
+
+        codec
+            .write_frame(&mut stream, greeting.as_bytes())
+            .await
+            .unwrap();
+        codec.read_frame(&mut stream).await.unwrap();
+        codec
+            .write_frame(&mut stream, login.as_bytes())
+            .await
+            .unwrap();
+
+        while let Ok(request) = codec.read_frame(&mut stream).await {
+            requests.push(String::from_utf8(request).unwrap());
+        }
+
+        requests
+    })
+}
+
+pub(super) fn connection() -> (ConnectionStream, DuplexStream) {
+    let (client, server) = duplex(4096);
+    (ConnectionStream::new(client), server)
+}
+
+pub(super) fn registry_creds() -> EppClientConnection {
+    EppClientConnection::new("localhost", 700, "username", "password")
+}
+
+#[test]
+fn builder_missing_host() {
+    let result = EppClient::builder()
+        .credentials("username", "password")
+        .build();
+
+    match result {
+        Err(error::Error::EppConfigError(e)) => assert_eq!(e, "missing host"),
+        _ => panic!("expected EppConfigError"),
+    }
+}
+
+#[test]
+fn builder_missing_credentials() {
+    let result = EppClient::builder().host("localhost", 700).build();
+
+    match result {
+        Err(error::Error::EppConfigError(e)) => assert_eq!(e, "missing credentials for localhost"),
+        _ => panic!("expected EppConfigError"),
+    }
+}
+
+#[test]
+fn builder_max_sessions() {
+    let (_, registry_creds) = EppClient::builder()
+        .host("localhost", 700)
+        .credentials("username", "password")
+        .build()
+        .unwrap();
+    assert_eq!(registry_creds.max_sessions(), None);
+
+    let (_, registry_creds) = EppClient::builder()
+        .host("localhost", 700)
+        .credentials("username", "password")
+        .max_sessions(2)
+        .build()
+        .unwrap();
+    assert_eq!(registry_creds.max_sessions(), Some(2));
+}
+
+#[tokio::test]
+async fn transact_over_duplex() {
+    let (stream, server) = connection();
+    let registry = fake_registry(
+        server,
+        vec![
+            "response/login.xml",
+            "response/domain/check.xml",
+            "response/logout.xml",
+        ],
+    );
+
+    let mut client = EppClient::connect_with_stream("test", &registry_creds(), stream)
+        .await
+        .unwrap();
+    assert!(client.greeting().is_ok());
+
+    let domain_check = EppDomainCheck::new(vec!["eppdev.com", "eppdev.net"], CLTRID);
+    let response = client
+        .transact::<_, EppDomainCheckResponse>(&domain_check)
+        .await
+        .unwrap();
+    assert_eq!(response.data.result().unwrap().code, 1000);
+
+    client.close().await.unwrap();
+
+    let requests = registry.await.unwrap();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[0].contains("<login>"));
+    assert!(requests[1].contains("<check>"));
+    assert!(requests[2].contains("<logout/>"));
+}
+
+#[tokio::test]
+async fn login_rejected() {
+    let (stream, server) = connection();
+    let registry = fake_registry(server, vec!["response/error.xml"]);
+
+    let result = EppClient::builder()
+        .host("localhost", 700)
+        .credentials("username", "wrong")
+        .connect_with_stream(stream)
+        .await;
+
+    match result {
+        Err(error::Error::EppLoginError(e)) => assert_eq!(e.code, ResultCode::ObjectDoesNotExist),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("login succeeded"),
+    }
+
+    registry.await.unwrap();
+}
+
+#[tokio::test]
+async fn negotiate_login_services() {
+    let (stream, server) = connection();
+    let registry = fake_registry_with_greeting(
+        server,
+        "response/greeting_no_contact.xml",
+        vec!["response/login.xml"],
+    );
+
+    let client = EppClient::builder()
+        .host("localhost", 700)
+        .credentials("username", "password")
+        .ext_uris(vec![
+            "urn:ietf:params:xml:ns:rgp-1.0".to_string(),
+            "urn:ietf:params:xml:ns:launch-1.0".to_string(),
+        ])
+        .required_ext_uris(vec!["urn:ietf:params:xml:ns:secDNS-1.1".to_string()])
+        .negotiate(true)
+        .connect_with_stream(stream)
+        .await
+        .unwrap();
+    drop(client);
+
+    let requests = registry.await.unwrap();
+    assert!(requests[0].contains("<objURI>urn:ietf:params:xml:ns:domain-1.0</objURI>"));
+    assert!(requests[0].contains("<objURI>urn:ietf:params:xml:ns:host-1.0</objURI>"));
+    assert!(!requests[0].contains("contact-1.0"));
+    assert!(requests[0].contains("<extURI>urn:ietf:params:xml:ns:rgp-1.0</extURI>"));
+    assert!(requests[0].contains("<extURI>urn:ietf:params:xml:ns:secDNS-1.1</extURI>"));
+    assert!(!requests[0].contains("launch-1.0"));
+}
+
+#[tokio::test]
+async fn required_extension_missing() {
+    let (stream, server) = connection();
+    let registry = fake_registry(server, vec!["response/login.xml"]);
+
+    let result = EppClient::builder()
+        .host("localhost", 700)
+        .credentials("username", "password")
+        .required_ext_uris(vec!["urn:ietf:params:xml:ns:launch-1.0".to_string()])
+        .connect_with_stream(stream)
+        .await;
+
+    match result {
+        Err(error::Error::EppNegotiationError(e)) => assert!(e.contains("launch-1.0")),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("login succeeded"),
+    }
+
+    // The client fails before sending the login
+    assert!(registry.await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn connection_closed_by_registry() {
+    let (stream, server) = connection();
+    let registry = fake_registry(server, vec!["response/login.xml"]);
+
+    let mut client = EppClient::connect_with_stream("test", &registry_creds(), stream)
+        .await
+        .unwrap();
+    registry.await.unwrap();
+
+    let domain_check = EppDomainCheck::new(vec!["eppdev.com"], CLTRID);
+    let result = client
+        .transact::<_, EppDomainCheckResponse>(&domain_check)
+        .await;
+
+    assert!(matches!(result, Err(error::Error::EppConnectionClosed)));
+    assert!(!client.is_open());
+}
+
+#[tokio::test]
+async fn stream_with_reconnect_policy() {
+    let (stream, _server) = connection();
+
+    let result = EppClient::builder()
+        .host("localhost", 700)
+        .credentials("username", "password")
+        .reconnect(EppClientReconnectPolicy::default())
+        .connect_with_stream(stream)
+        .await;
+
+    assert!(matches!(result, Err(error::Error::EppConfigError(_))));
+}
+
+#[tokio::test]
+async fn response_timeout() {
+    let (stream, server) = connection();
+    let registry = silent_registry(server);
+
+    let mut client = EppClient::builder()
+        .host("localhost", 700)
+        .credentials("username", "password")
+        .response_timeout(Duration::from_millis(50))
+        .connect_with_stream(stream)
+        .await
+        .unwrap();
+
+    let domain_check = EppDomainCheck::new(vec!["eppdev.com"], CLTRID);
+    let result = client
+        .transact::<_, EppDomainCheckResponse>(&domain_check)
+        .await;
+
+    assert!(matches!(result, Err(error::Error::EppTimeoutError(_))));
+    assert!(!client.is_open());
+
+    // The session is not used again after the timeout
+    let result = client
+        .transact::<_, EppDomainCheckResponse>(&domain_check)
+        .await;
+    assert!(matches!(result, Err(error::Error::EppConnectionClosed)));
+
+    drop(client);
+    assert_eq!(registry.await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn transact_with_timeout() {
+    let (stream, server) = connection();
+    let registry = silent_registry(server);
+
+    let mut client = EppClient::connect_with_stream("test", &registry_creds(), stream)
+        .await
+        .unwrap();
+
+    let domain_check = EppDomainCheck::new(vec!["eppdev.com"], CLTRID);
+    let result = client
+        .transact_with_timeout::<_, EppDomainCheckResponse>(
+            &domain_check,
+            Duration::from_millis(50),
+        )
+        .await;
+
+    assert!(matches!(result, Err(error::Error::EppTimeoutError(_))));
+    assert!(!client.is_open());
+
+    drop(client);
+    assert_eq!(registry.await.unwrap().len(), 1);
+}
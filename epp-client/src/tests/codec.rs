@@ -0,0 +1,113 @@
+//! RFC 5734 framing tests for `EppCodec`
+
+use bytes::BytesMut;
+use tokio_test::io::Builder;
+
+use crate::connection::codec::EppCodec;
+use crate::error::Error;
+
+const MESSAGE: &[u8] = b"<epp><hello/></epp>";
+
+fn frame(message: &[u8]) -> Vec<u8> {
+    let mut frame = ((message.len() + 4) as u32).to_be_bytes().to_vec();
+    frame.extend_from_slice(message);
+    frame
+}
+
+#[test]
+fn encode() {
+    let mut buf = BytesMut::new();
+    EppCodec::default().encode(MESSAGE, &mut buf).unwrap();
+
+    assert_eq!(&buf[..], &frame(MESSAGE)[..]);
+}
+
+#[test]
+fn encode_too_large() {
+    let mut buf = BytesMut::new();
+    let result = EppCodec::new(10).encode(MESSAGE, &mut buf);
+
+    assert!(matches!(result, Err(Error::EppFramingError(_))));
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn decode_partial() {
+    let codec = EppCodec::default();
+    let frame = frame(MESSAGE);
+    let mut buf = BytesMut::new();
+
+    // Incomplete header, then incomplete message
+    for chunk in [&frame[..2], &frame[2..9]] {
+        buf.extend_from_slice(chunk);
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+    }
+
+    buf.extend_from_slice(&frame[9..]);
+    assert_eq!(codec.decode(&mut buf).unwrap(), Some(MESSAGE.to_vec()));
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn decode_consecutive() {
+    let codec = EppCodec::default();
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(&frame(MESSAGE));
+    buf.extend_from_slice(&frame(b"<epp/>"));
+
+    assert_eq!(codec.decode(&mut buf).unwrap(), Some(MESSAGE.to_vec()));
+    assert_eq!(codec.decode(&mut buf).unwrap(), Some(b"<epp/>".to_vec()));
+    assert_eq!(codec.decode(&mut buf).unwrap(), None);
+}
+
+#[test]
+fn decode_invalid_header() {
+    let codec = EppCodec::new(64);
+
+    let mut buf = BytesMut::from(&[0u8, 0, 0, 3][..]);
+    assert!(matches!(
+        codec.decode(&mut buf),
+        Err(Error::EppFramingError(_))
+    ));
+
+    let mut buf = BytesMut::from(&[0u8, 0, 0, 65][..]);
+    assert!(matches!(
+        codec.decode(&mut buf),
+        Err(Error::EppFramingError(_))
+    ));
+}
+
+#[tokio::test]
+async fn read_frame_partial_reads() {
+    let frame = frame(MESSAGE);
+    let mut reader = Builder::new()
+        .read(&frame[..1])
+        .read(&frame[1..6])
+        .read(&frame[6..12])
+        .read(&frame[12..])
+        .build();
+
+    let message = EppCodec::default().read_frame(&mut reader).await.unwrap();
+
+    assert_eq!(message, MESSAGE);
+}
+
+#[tokio::test]
+async fn read_frame_eof() {
+    let frame = frame(MESSAGE);
+    let mut reader = Builder::new().read(&frame[..10]).build();
+
+    let result = EppCodec::default().read_frame(&mut reader).await;
+
+    assert!(matches!(result, Err(Error::EppConnectionClosed)));
+}
+
+#[tokio::test]
+async fn write_frame() {
+    let mut writer = Builder::new().write(&frame(MESSAGE)).build();
+
+    EppCodec::default()
+        .write_frame(&mut writer, MESSAGE)
+        .await
+        .unwrap();
+}
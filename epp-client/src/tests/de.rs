@@ -1,14 +1,22 @@
 //! EPP XML to `EppObject` deserialization tests
 
 mod response {
+    use chrono::{TimeZone, Utc};
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
     use super::super::get_xml;
     use super::super::CLTRID;
+    use crate::epp::object::data::{
+        ContactStatus, DiscloseInfoType, DomainStatus, HostStatus, Status,
+    };
     use crate::epp::object::StringValueTrait;
     use crate::epp::response::{
-        EppCommandResponseError, EppGreeting, EppLoginResponse, EppLogoutResponse,
+        Access, EppCommandResponseError, EppGreeting, EppLoginResponse, EppLogoutResponse, Expiry,
+        ResultCode, Retention,
     };
     use crate::epp::xml::EppXml;
     use crate::epp::*;
+    use crate::error;
 
     const SVTRID: &str = "RO-6879-1627224678242975";
     const SUCCESS_MSG: &str = "Command completed successfully";
@@ -19,13 +27,31 @@ mod response {
         let object = EppGreeting::deserialize(xml.as_str()).unwrap();
 
         assert_eq!(object.data.service_id, "ISPAPI EPP Server");
-        assert_eq!(object.data.service_date, "2021-07-25T14:51:17.0Z");
         assert_eq!(
-            object.data.svc_menu.options.version,
+            object.data.service_date,
+            Utc.with_ymd_and_hms(2021, 7, 25, 14, 51, 17).unwrap()
+        );
+        assert_eq!(
+            object.data.svc_menu.options().unwrap().version,
             "1.0".to_string_value()
         );
-        assert_eq!(object.data.svc_menu.options.lang, "en".to_string_value());
+        assert_eq!(
+            object.data.svc_menu.options().unwrap().lang,
+            "en".to_string_value()
+        );
         assert_eq!(object.data.svc_menu.services.obj_uris.len(), 4);
+        assert!(object
+            .data
+            .supports_object("urn:ietf:params:xml:ns:domain-1.0"));
+        assert!(object
+            .data
+            .supports_extension("urn:ietf:params:xml:ns:rgp-1.0"));
+        assert!(!object
+            .data
+            .supports_extension("urn:ietf:params:xml:ns:fee-0.5"));
+        assert_eq!(object.data.dcp.access, Access::All);
+        assert_eq!(object.data.dcp.statements[0].retention, Retention::Stated);
+        assert_eq!(object.data.dcp.expiry, None);
         assert_eq!(
             object
                 .data
@@ -40,20 +66,57 @@ mod response {
         );
     }
 
+    #[test]
+    fn greeting_dcp() {
+        let xml = get_xml("response/greeting_dcp.xml").unwrap();
+        let object = EppGreeting::deserialize(xml.as_str()).unwrap();
+        let greeting = &object.data;
+
+        assert_eq!(
+            greeting.svc_menu.options().unwrap().lang,
+            "en".to_string_value()
+        );
+        assert!(greeting.supports_language("fr"));
+        assert!(greeting.supports_object("urn:ietf:params:xml:ns:obj2"));
+        assert!(!greeting.supports_object("urn:ietf:params:xml:ns:domain-1.0"));
+        assert!(!greeting.supports_extension("urn:ietf:params:xml:ns:rgp-1.0"));
+
+        let dcp = &greeting.dcp;
+        assert_eq!(dcp.access, Access::Null);
+        assert_eq!(dcp.statements.len(), 2);
+        assert!(dcp.statements[0].purpose.admin.is_some());
+        assert!(dcp.statements[0].purpose.contact.is_none());
+        assert_eq!(
+            dcp.statements[0].recipient.ours[0].recipient_description,
+            Some("Registry operator".to_string_value())
+        );
+        assert_eq!(dcp.statements[0].recipient.ours.len(), 2);
+        assert_eq!(dcp.statements[0].retention, Retention::Business);
+        assert!(dcp.statements[1].recipient.unrelated.is_some());
+        assert_eq!(dcp.statements[1].retention, Retention::None);
+        assert_eq!(dcp.expiry, Some(Expiry::Relative("P1Y".to_string())));
+
+        let serialized = object.serialize().unwrap();
+        assert!(serialized.contains("<access><null/></access>"));
+        assert_eq!(EppGreeting::deserialize(&serialized).unwrap(), object);
+    }
+
     #[test]
     fn error() {
         let xml = get_xml("response/error.xml").unwrap();
         let object = EppCommandResponseError::deserialize(xml.as_str()).unwrap();
 
-        assert_eq!(object.data.result.code, 2303);
-        assert_eq!(
-            object.data.result.message,
-            "Object does not exist".to_string_value()
-        );
+        assert_eq!(object.data.results.len(), 1);
+        assert_eq!(object.data.result().unwrap().code, 2303);
         assert_eq!(
-            object.data.result.ext_value.unwrap().reason,
-            "545 Object not found".to_string_value()
+            object.data.result().unwrap().message.text,
+            "Object does not exist"
         );
+        assert_eq!(object.data.result().unwrap().message.lang, None);
+
+        let ext_value = &object.data.result().unwrap().ext_values[0];
+        assert_eq!(ext_value.value, "<epp:undef/>".to_string_value());
+        assert_eq!(ext_value.reason.text, "545 Object not found");
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
             CLTRID.to_string_value()
@@ -61,13 +124,123 @@ mod response {
         assert_eq!(object.data.tr_ids.server_tr_id, SVTRID.to_string_value());
     }
 
+    #[test]
+    fn multiple_results() {
+        let xml = get_xml("response/error_multiple.xml").unwrap();
+        let object = EppCommandResponseError::deserialize(xml.as_str()).unwrap();
+
+        let results = &object.data.results;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].code, ResultCode::ParameterValueSyntaxError);
+        assert_eq!(results[0].message.lang, Some("en".to_string()));
+        assert_eq!(
+            results[0].values,
+            vec!["<domain:name>eppdev &amp; co.com</domain:name>".to_string_value()]
+        );
+        assert_eq!(results[0].ext_values.len(), 2);
+        assert_eq!(
+            results[0].ext_values[0].value,
+            "<domain:name xmlns:domain=\"urn:ietf:params:xml:ns:domain-1.0\">eppdev &amp; co.com</domain:name>"
+                .to_string_value()
+        );
+        assert_eq!(results[0].ext_values[0].reason.text, "Invalid domain name");
+        assert_eq!(results[0].ext_values[1].reason.lang, Some("fr".to_string()));
+        assert_eq!(
+            results[0].ext_values[1].value,
+            "<domain:period unit=\"y\">11</domain:period>".to_string_value()
+        );
+        assert_eq!(results[1].code, ResultCode::ParameterValueRangeError);
+        assert!(results[1].ext_values.is_empty());
+
+        let error = error::EppCommandError::from(object);
+        assert_eq!(error.code, ResultCode::ParameterValueSyntaxError);
+        assert_eq!(error.reasons.len(), 2);
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "2005 Parameter value syntax error: \
+                 <domain:name>eppdev &amp; co.com</domain:name>; Invalid domain name: \
+                 <domain:name xmlns:domain=\"urn:ietf:params:xml:ns:domain-1.0\">eppdev &amp; co.com</domain:name>; \
+                 Période invalide: <domain:period unit=\"y\">11</domain:period>; \
+                 2004 Parameter value range error (svTRID {})",
+                SVTRID
+            )
+        );
+    }
+
+    #[test]
+    fn result_values_with_markup() {
+        let xml = get_xml("response/error_markup.xml").unwrap();
+        let object = EppCommandResponseError::deserialize(xml.as_str()).unwrap();
+
+        // The commented out result is skipped, and the comment and CDATA in the values are
+        // kept as they are
+        let results = &object.data.results;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].code, ResultCode::ParameterValueSyntaxError);
+        assert_eq!(
+            results[0].values,
+            vec!["<!-- a > b </value> --><domain:name>eppdev.com</domain:name>".to_string_value()]
+        );
+        assert_eq!(
+            results[0].ext_values[0].value,
+            "<![CDATA[a > b </value>]]>".to_string_value()
+        );
+        assert_eq!(
+            results[0].ext_values[1].value,
+            "<domain:period xmlns:domain=\"urn:ietf:params:xml:ns:domain-1.0\" unit=\"y>\">11</domain:period>"
+                .to_string_value()
+        );
+        assert_eq!(results[0].ext_values[1].reason.text, "Invalid period");
+    }
+
+    #[test]
+    fn command_error() {
+        let xml = get_xml("response/error.xml").unwrap();
+        let object = EppCommandResponseError::deserialize(xml.as_str()).unwrap();
+
+        let error = error::EppCommandError::from(object);
+        assert_eq!(error.code, ResultCode::ObjectDoesNotExist);
+        assert_eq!(error.message, "Object does not exist");
+        assert_eq!(error.reasons, vec!["545 Object not found".to_string()]);
+        assert_eq!(error.client_tr_id, Some(CLTRID.to_string()));
+        assert_eq!(error.server_tr_id, SVTRID);
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "2303 Object does not exist; 545 Object not found: <epp:undef/> (svTRID {})",
+                SVTRID
+            )
+        );
+    }
+
+    #[test]
+    fn result_codes() {
+        assert_eq!(
+            ResultCode::from(1000),
+            ResultCode::CommandCompletedSuccessfully
+        );
+        assert_eq!(ResultCode::from(2999), ResultCode::Unknown(2999));
+        assert_eq!(ResultCode::ObjectExists.code(), 2302);
+        assert_eq!(ResultCode::ObjectExists.message(), "Object exists");
+
+        assert!(ResultCode::CommandCompletedSuccessfullyNoMessages.is_success());
+        assert!(!ResultCode::ObjectExists.is_success());
+        assert!(ResultCode::CommandCompletedSuccessfullyActionPending.is_pending());
+        assert!(ResultCode::CommandCompletedSuccessfullyEndingSession.closes_session());
+        assert!(ResultCode::AuthenticationErrorServerClosingConnection.closes_session());
+        assert!(!ResultCode::CommandFailed.closes_session());
+        assert!(ResultCode::CommandFailed.is_retryable());
+        assert!(!ResultCode::AuthenticationErrorServerClosingConnection.is_retryable());
+    }
+
     #[test]
     fn login() {
         let xml = get_xml("response/login.xml").unwrap();
         let object = EppLoginResponse::deserialize(xml.as_str()).unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
             CLTRID.to_string_value()
@@ -80,10 +253,10 @@ mod response {
         let xml = get_xml("response/logout.xml").unwrap();
         let object = EppLogoutResponse::deserialize(xml.as_str()).unwrap();
 
-        assert_eq!(object.data.result.code, 1500);
+        assert_eq!(object.data.result().unwrap().code, 1500);
         assert_eq!(
-            object.data.result.message,
-            "Command completed successfully; ending session".to_string_value()
+            object.data.result().unwrap().message.text,
+            "Command completed successfully; ending session"
         );
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
@@ -99,8 +272,8 @@ mod response {
 
         let results = object.data.res_data().unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(
             results.check_data.contact_list[0].contact.id,
             "eppdev-contact-1".to_string_value()
@@ -125,12 +298,12 @@ mod response {
 
         let results = object.data.res_data().unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(results.create_data.id, "eppdev-contact-4".to_string_value());
         assert_eq!(
             results.create_data.created_at,
-            "2021-07-25T16:05:32.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2021, 7, 25, 16, 5, 32).unwrap()
         );
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
@@ -144,8 +317,8 @@ mod response {
         let xml = get_xml("response/contact/delete.xml").unwrap();
         let object = EppContactDeleteResponse::deserialize(xml.as_str()).unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
             CLTRID.to_string_value()
@@ -164,44 +337,39 @@ mod response {
         let fax_ext = fax.extension.as_ref().unwrap();
         let auth_info = result.info_data.auth_info.as_ref().unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(result.info_data.id, "eppdev-contact-3".to_string_value());
         assert_eq!(result.info_data.roid, "UNDEF-ROID".to_string_value());
-        assert_eq!(result.info_data.statuses[0].status, "ok".to_string());
-        assert_eq!(result.info_data.postal_info.info_type, "loc".to_string());
-        assert_eq!(
-            result.info_data.postal_info.name,
-            "John Doe".to_string_value()
-        );
-        assert_eq!(
-            result.info_data.postal_info.organization,
-            "Acme Widgets".to_string_value()
-        );
-        assert_eq!(
-            result.info_data.postal_info.address.street[0],
-            "58".to_string_value()
-        );
-        assert_eq!(
-            result.info_data.postal_info.address.street[1],
+        assert_eq!(result.info_data.statuses[0].status, ContactStatus::Ok);
+        assert_eq!(result.info_data.postal_info.len(), 2);
+        let int_postal_info = result.info_data.postal_info("int").unwrap();
+        assert_eq!(int_postal_info.name, "John Doe".to_string_value());
+        assert_eq!(int_postal_info.organization, None);
+        assert_eq!(int_postal_info.address.province, None);
+        assert_eq!(int_postal_info.address.postal_code, None);
+        let postal_info = result.info_data.postal_info("loc").unwrap();
+        assert_eq!(postal_info.info_type, "loc".to_string());
+        assert_eq!(postal_info.name, "John Doe".to_string_value());
+        assert_eq!(
+            postal_info.organization,
+            Some("Acme Widgets".to_string_value())
+        );
+        assert_eq!(postal_info.address.street[0], "58".to_string_value());
+        assert_eq!(
+            postal_info.address.street[1],
             "Orchid Road".to_string_value()
         );
+        assert_eq!(postal_info.address.city, "Paris".to_string_value());
         assert_eq!(
-            result.info_data.postal_info.address.city,
-            "Paris".to_string_value()
+            postal_info.address.province,
+            Some("Paris".to_string_value())
         );
         assert_eq!(
-            result.info_data.postal_info.address.province,
-            "Paris".to_string_value()
-        );
-        assert_eq!(
-            result.info_data.postal_info.address.postal_code,
-            "392374".to_string_value()
-        );
-        assert_eq!(
-            result.info_data.postal_info.address.country_code,
-            "FR".to_string_value()
+            postal_info.address.postal_code,
+            Some("392374".to_string_value())
         );
+        assert_eq!(postal_info.address.country_code, "FR".to_string_value());
         assert_eq!(result.info_data.voice.number, "+33.47237942".to_string());
         assert_eq!(*voice_ext, "123".to_string());
         assert_eq!(fax.number, "+33.86698799".to_string());
@@ -214,7 +382,7 @@ mod response {
         assert_eq!(result.info_data.creator_id, "SYSTEM".to_string_value());
         assert_eq!(
             result.info_data.created_at,
-            "2021-07-23T13:09:09.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2021, 7, 23, 13, 9, 9).unwrap()
         );
         assert_eq!(
             *(result.info_data.updater_id.as_ref().unwrap()),
@@ -222,9 +390,20 @@ mod response {
         );
         assert_eq!(
             *(result.info_data.updated_at.as_ref().unwrap()),
-            "2021-07-23T13:09:09.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2021, 7, 23, 13, 9, 9).unwrap()
+        );
+        assert_eq!(
+            auth_info.password(),
+            Some(&"eppdev-387323".to_string_value())
         );
-        assert_eq!((*auth_info).password, "eppdev-387323".to_string_value());
+        let disclose = result.info_data.disclose.as_ref().unwrap();
+        assert!(!disclose.flag);
+        assert_eq!(disclose.name, vec![DiscloseInfoType::new("loc")]);
+        assert!(disclose.organization.is_empty());
+        assert_eq!(disclose.address, vec![DiscloseInfoType::new("loc")]);
+        assert!(disclose.voice.is_some());
+        assert!(disclose.fax.is_none());
+        assert!(disclose.email.is_some());
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
             CLTRID.to_string_value()
@@ -237,8 +416,127 @@ mod response {
         let xml = get_xml("response/contact/update.xml").unwrap();
         let object = EppContactUpdateResponse::deserialize(xml.as_str()).unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
+        assert_eq!(
+            object.data.tr_ids.client_tr_id.unwrap(),
+            CLTRID.to_string_value()
+        );
+        assert_eq!(object.data.tr_ids.server_tr_id, SVTRID.to_string_value());
+    }
+
+    #[test]
+    fn contact_transfer_request() {
+        let xml = get_xml("response/contact/transfer_request.xml").unwrap();
+        let object = EppContactTransferRequestResponse::deserialize(xml.as_str()).unwrap();
+
+        let result = object.data.res_data().unwrap();
+
+        assert_eq!(object.data.result().unwrap().code, 1001);
+        assert_eq!(
+            object.data.result().unwrap().message.text,
+            "Command completed successfully; action pending"
+        );
+        assert_eq!(
+            result.transfer_data.id,
+            "eppdev-contact-3".to_string_value()
+        );
+        assert_eq!(
+            result.transfer_data.transfer_status,
+            "pending".to_string_value()
+        );
+        assert_eq!(
+            result.transfer_data.requester_id,
+            "eppdev".to_string_value()
+        );
+        assert_eq!(
+            result.transfer_data.requested_at,
+            Utc.with_ymd_and_hms(2021, 7, 23, 15, 31, 21).unwrap()
+        );
+        assert_eq!(result.transfer_data.ack_id, "ClientY".to_string_value());
+        assert_eq!(
+            result.transfer_data.ack_by,
+            Utc.with_ymd_and_hms(2021, 7, 28, 15, 31, 21).unwrap()
+        );
+        assert_eq!(
+            object.data.tr_ids.client_tr_id.unwrap(),
+            CLTRID.to_string_value()
+        );
+        assert_eq!(object.data.tr_ids.server_tr_id, SVTRID.to_string_value());
+    }
+
+    #[test]
+    fn contact_transfer_approve() {
+        let xml = get_xml("response/contact/transfer_approve.xml").unwrap();
+        let object = EppContactTransferApproveResponse::deserialize(xml.as_str()).unwrap();
+
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
+        assert_eq!(
+            object.data.tr_ids.client_tr_id.unwrap(),
+            CLTRID.to_string_value()
+        );
+        assert_eq!(object.data.tr_ids.server_tr_id, SVTRID.to_string_value());
+    }
+
+    #[test]
+    fn contact_transfer_reject() {
+        let xml = get_xml("response/contact/transfer_reject.xml").unwrap();
+        let object = EppContactTransferRejectResponse::deserialize(xml.as_str()).unwrap();
+
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
+        assert_eq!(
+            object.data.tr_ids.client_tr_id.unwrap(),
+            CLTRID.to_string_value()
+        );
+        assert_eq!(object.data.tr_ids.server_tr_id, SVTRID.to_string_value());
+    }
+
+    #[test]
+    fn contact_transfer_cancel() {
+        let xml = get_xml("response/contact/transfer_cancel.xml").unwrap();
+        let object = EppContactTransferCancelResponse::deserialize(xml.as_str()).unwrap();
+
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
+        assert_eq!(
+            object.data.tr_ids.client_tr_id.unwrap(),
+            CLTRID.to_string_value()
+        );
+        assert_eq!(object.data.tr_ids.server_tr_id, SVTRID.to_string_value());
+    }
+
+    #[test]
+    fn contact_transfer_query() {
+        let xml = get_xml("response/contact/transfer_query.xml").unwrap();
+        let object = EppContactTransferQueryResponse::deserialize(xml.as_str()).unwrap();
+
+        let result = object.data.res_data().unwrap();
+
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
+        assert_eq!(
+            result.transfer_data.id,
+            "eppdev-contact-3".to_string_value()
+        );
+        assert_eq!(
+            result.transfer_data.transfer_status,
+            "pending".to_string_value()
+        );
+        assert_eq!(
+            result.transfer_data.requester_id,
+            "eppdev".to_string_value()
+        );
+        assert_eq!(
+            result.transfer_data.requested_at,
+            Utc.with_ymd_and_hms(2021, 7, 23, 15, 31, 21).unwrap()
+        );
+        assert_eq!(result.transfer_data.ack_id, "ClientY".to_string_value());
+        assert_eq!(
+            result.transfer_data.ack_by,
+            Utc.with_ymd_and_hms(2021, 7, 28, 15, 31, 21).unwrap()
+        );
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
             CLTRID.to_string_value()
@@ -253,8 +551,8 @@ mod response {
 
         let result = object.data.res_data().unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(
             result.check_data.domain_list[0].domain.name,
             "eppdev.com".to_string_value()
@@ -279,16 +577,16 @@ mod response {
 
         let result = object.data.res_data().unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(result.create_data.name, "eppdev-2.com".to_string_value());
         assert_eq!(
             result.create_data.created_at,
-            "2021-07-25T18:11:35.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2021, 7, 25, 18, 11, 35).unwrap()
         );
         assert_eq!(
             result.create_data.expiring_at,
-            "2022-07-25T18:11:34.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2022, 7, 25, 18, 11, 34).unwrap()
         );
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
@@ -302,8 +600,8 @@ mod response {
         let xml = get_xml("response/domain/delete.xml").unwrap();
         let object = EppDomainDeleteResponse::deserialize(xml.as_str()).unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
             CLTRID.to_string_value()
@@ -322,17 +620,26 @@ mod response {
         let ns = (*ns_list).host_obj.as_ref().unwrap();
         let hosts = result.info_data.hosts.as_ref().unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(result.info_data.name, "eppdev-1.com".to_string_value());
         assert_eq!(
             result.info_data.roid,
             "125899511_DOMAIN_COM-VRSN".to_string_value()
         );
-        assert_eq!(result.info_data.statuses[0].status, "ok".to_string());
+        assert_eq!(result.info_data.statuses[0].status, DomainStatus::Ok);
         assert_eq!(
             result.info_data.statuses[1].status,
-            "clientTransferProhibited".to_string()
+            DomainStatus::ClientTransferProhibited
+        );
+        assert_eq!(
+            result.info_data.statuses[2].status,
+            DomainStatus::ClientHold
+        );
+        assert_eq!(result.info_data.statuses[2].lang, Some("en".to_string()));
+        assert_eq!(
+            result.info_data.statuses[2].reason,
+            Some("Payment overdue.".to_string())
         );
         assert_eq!(
             result.info_data.registrant,
@@ -370,18 +677,18 @@ mod response {
         assert_eq!(result.info_data.creator_id, "SYSTEM".to_string_value());
         assert_eq!(
             result.info_data.created_at,
-            "2021-07-23T15:31:20.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2021, 7, 23, 15, 31, 20).unwrap()
         );
         assert_eq!(result.info_data.updater_id, "SYSTEM".to_string_value());
         assert_eq!(
             result.info_data.updated_at,
-            "2021-07-23T15:31:21.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2021, 7, 23, 15, 31, 21).unwrap()
         );
         assert_eq!(
             result.info_data.expiring_at,
-            "2023-07-23T15:31:20.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2023, 7, 23, 15, 31, 20).unwrap()
         );
-        assert_eq!((*auth_info).password, "epP4uthd#v".to_string_value());
+        assert_eq!(auth_info.password(), Some(&"epP4uthd#v".to_string_value()));
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
             CLTRID.to_string_value()
@@ -396,12 +703,12 @@ mod response {
 
         let result = object.data.res_data().unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(result.renew_data.name, "eppdev-1.com".to_string_value());
         assert_eq!(
             result.renew_data.expiring_at,
-            "2024-07-23T15:31:20.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2024, 7, 23, 15, 31, 20).unwrap()
         );
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
@@ -410,6 +717,35 @@ mod response {
         assert_eq!(object.data.tr_ids.server_tr_id, SVTRID.to_string_value());
     }
 
+    #[test]
+    fn domain_renew_dates() {
+        let xml = get_xml("response/domain/renew.xml").unwrap();
+        let expected = Utc.with_ymd_and_hms(2024, 7, 23, 15, 31, 20).unwrap();
+
+        // Registries may omit fractional seconds or the offset, or use another offset
+        for date in [
+            "2024-07-23T15:31:20Z",
+            "2024-07-23T15:31:20.000+00:00",
+            "2024-07-23T17:31:20.0+02:00",
+            "2024-07-23T15:31:20",
+        ] {
+            let xml = xml.replace("2024-07-23T15:31:20.0Z", date);
+            let object = EppDomainRenewResponse::deserialize(xml.as_str()).unwrap();
+            assert_eq!(
+                object.data.res_data().unwrap().renew_data.expiring_at,
+                expected
+            );
+        }
+
+        let xml = xml.replace("2024-07-23T15:31:20.0Z", "2024-13-23T15:31:20.0Z");
+        match EppDomainRenewResponse::deserialize(xml.as_str()) {
+            Err(error::Error::EppDeserializationError(e)) => {
+                assert!(e.contains("invalid date: 2024-13-23T15:31:20.0Z"))
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
     #[test]
     fn domain_transfer_request() {
         let xml = get_xml("response/domain/transfer_request.xml").unwrap();
@@ -417,10 +753,10 @@ mod response {
 
         let result = object.data.res_data().unwrap();
 
-        assert_eq!(object.data.result.code, 1001);
+        assert_eq!(object.data.result().unwrap().code, 1001);
         assert_eq!(
-            object.data.result.message,
-            "Command completed successfully; action pending".to_string_value()
+            object.data.result().unwrap().message.text,
+            "Command completed successfully; action pending"
         );
         assert_eq!(
             result.transfer_data.name,
@@ -436,16 +772,16 @@ mod response {
         );
         assert_eq!(
             result.transfer_data.requested_at,
-            "2021-07-23T15:31:21.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2021, 7, 23, 15, 31, 21).unwrap()
         );
         assert_eq!(result.transfer_data.ack_id, "ClientY".to_string_value());
         assert_eq!(
             result.transfer_data.ack_by,
-            "2021-07-28T15:31:21.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2021, 7, 28, 15, 31, 21).unwrap()
         );
         assert_eq!(
             result.transfer_data.expiring_at,
-            "2022-07-02T14:53:19.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2022, 7, 2, 14, 53, 19).unwrap()
         );
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
@@ -459,8 +795,8 @@ mod response {
         let xml = get_xml("response/domain/transfer_approve.xml").unwrap();
         let object = EppDomainTransferApproveResponse::deserialize(xml.as_str()).unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
             CLTRID.to_string_value()
@@ -473,8 +809,8 @@ mod response {
         let xml = get_xml("response/domain/transfer_reject.xml").unwrap();
         let object = EppDomainTransferRejectResponse::deserialize(xml.as_str()).unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
             CLTRID.to_string_value()
@@ -487,8 +823,8 @@ mod response {
         let xml = get_xml("response/domain/transfer_cancel.xml").unwrap();
         let object = EppDomainTransferCancelResponse::deserialize(xml.as_str()).unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
             CLTRID.to_string_value()
@@ -503,8 +839,8 @@ mod response {
 
         let result = object.data.res_data().unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(
             result.transfer_data.name,
             "eppdev-transfer.com".to_string_value()
@@ -519,16 +855,16 @@ mod response {
         );
         assert_eq!(
             result.transfer_data.requested_at,
-            "2021-07-23T15:31:21.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2021, 7, 23, 15, 31, 21).unwrap()
         );
         assert_eq!(result.transfer_data.ack_id, "ClientY".to_string_value());
         assert_eq!(
             result.transfer_data.ack_by,
-            "2021-07-28T15:31:21.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2021, 7, 28, 15, 31, 21).unwrap()
         );
         assert_eq!(
             result.transfer_data.expiring_at,
-            "2022-07-02T14:53:19.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2022, 7, 2, 14, 53, 19).unwrap()
         );
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
@@ -542,8 +878,8 @@ mod response {
         let xml = get_xml("response/domain/update.xml").unwrap();
         let object = EppDomainUpdateResponse::deserialize(xml.as_str()).unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
             CLTRID.to_string_value()
@@ -558,8 +894,8 @@ mod response {
 
         let result = object.data.res_data().unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(
             result.check_data.host_list[0].host.name,
             "host1.eppdev-1.com".to_string_value()
@@ -584,15 +920,15 @@ mod response {
 
         let result = object.data.res_data().unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(
             result.create_data.name,
             "host2.eppdev-1.com".to_string_value()
         );
         assert_eq!(
             result.create_data.created_at,
-            "2021-07-26T05:28:55.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2021, 7, 26, 5, 28, 55).unwrap()
         );
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
@@ -608,35 +944,31 @@ mod response {
 
         let result = object.data.res_data().unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(
             result.info_data.name,
             "host2.eppdev-1.com".to_string_value()
         );
         assert_eq!(result.info_data.roid, "UNDEF-ROID".to_string_value());
-        assert_eq!(result.info_data.statuses[0].status, "ok".to_string());
-        assert_eq!(
-            *(result.info_data.addresses[0].ip_version.as_ref().unwrap()),
-            "v4".to_string()
-        );
+        assert_eq!(result.info_data.statuses[0].status, HostStatus::Ok);
+        assert_eq!(result.info_data.addresses[0].ip_version(), "v4");
         assert_eq!(
             result.info_data.addresses[0].address,
-            "29.245.122.14".to_string()
-        );
-        assert_eq!(
-            *(result.info_data.addresses[1].ip_version.as_ref().unwrap()),
-            "v6".to_string()
+            IpAddr::V4(Ipv4Addr::new(29, 245, 122, 14))
         );
+        assert_eq!(result.info_data.addresses[1].ip_version(), "v6");
         assert_eq!(
             result.info_data.addresses[1].address,
-            "2404:6800:4001:0801:0000:0000:0000:200e".to_string()
+            IpAddr::V6(Ipv6Addr::new(
+                0x2404, 0x6800, 0x4001, 0x801, 0, 0, 0, 0x200e
+            ))
         );
         assert_eq!(result.info_data.client_id, "eppdev".to_string_value());
         assert_eq!(result.info_data.creator_id, "creator".to_string_value());
         assert_eq!(
             result.info_data.created_at,
-            "2021-07-26T05:28:55.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2021, 7, 26, 5, 28, 55).unwrap()
         );
         assert_eq!(
             *(result.info_data.updater_id.as_ref().unwrap()),
@@ -644,7 +976,7 @@ mod response {
         );
         assert_eq!(
             *(result.info_data.updated_at.as_ref().unwrap()),
-            "2021-07-26T05:28:55.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2021, 7, 26, 5, 28, 55).unwrap()
         );
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
@@ -653,13 +985,61 @@ mod response {
         assert_eq!(object.data.tr_ids.server_tr_id, SVTRID.to_string_value());
     }
 
+    #[test]
+    fn host_info_addresses() {
+        let xml = get_xml("response/host/info_addresses.xml").unwrap();
+        let object = EppHostInfoResponse::deserialize(xml.as_str()).unwrap();
+
+        let result = object.data.res_data().unwrap();
+
+        assert_eq!(result.info_data.statuses[0].status, HostStatus::Linked);
+        assert_eq!(
+            result.info_data.statuses[1].status,
+            HostStatus::ClientUpdateProhibited
+        );
+        assert_eq!(result.info_data.addresses.len(), 3);
+        assert_eq!(result.info_data.addresses[0].ip_version(), "v4");
+        assert_eq!(
+            result.info_data.addresses[0].address,
+            IpAddr::V4(Ipv4Addr::new(29, 245, 122, 14))
+        );
+        // Addresses without an 'ip' attr are IPv4
+        assert_eq!(result.info_data.addresses[1].ip_version(), "v4");
+        assert_eq!(
+            result.info_data.addresses[1].address,
+            IpAddr::V4(Ipv4Addr::new(29, 245, 122, 15))
+        );
+        assert_eq!(result.info_data.addresses[2].ip_version(), "v6");
+        assert_eq!(
+            result.info_data.addresses[2].address,
+            IpAddr::V6(Ipv6Addr::new(
+                0x2404, 0x6800, 0x4001, 0x801, 0, 0, 0, 0x200e
+            ))
+        );
+        assert_eq!(
+            *(result.info_data.transferred_at.as_ref().unwrap()),
+            Utc.with_ymd_and_hms(2021, 7, 27, 8, 12, 31).unwrap()
+        );
+    }
+
+    #[test]
+    fn host_info_addr_mismatch() {
+        let xml = get_xml("response/host/info_addr_mismatch.xml").unwrap();
+        let result = EppHostInfoResponse::deserialize(xml.as_str());
+
+        assert!(matches!(
+            result,
+            Err(error::Error::EppDeserializationError(_))
+        ));
+    }
+
     #[test]
     fn host_update() {
         let xml = get_xml("response/host/update.xml").unwrap();
         let object = EppHostUpdateResponse::deserialize(xml.as_str()).unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
             CLTRID.to_string_value()
@@ -672,8 +1052,8 @@ mod response {
         let xml = get_xml("response/host/delete.xml").unwrap();
         let object = EppHostDeleteResponse::deserialize(xml.as_str()).unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
             CLTRID.to_string_value()
@@ -689,16 +1069,16 @@ mod response {
         let result = object.data.res_data().unwrap();
         let msg = object.data.message_queue().unwrap();
 
-        assert_eq!(object.data.result.code, 1301);
+        assert_eq!(object.data.result().unwrap().code, 1301);
         assert_eq!(
-            object.data.result.message,
-            "Command completed successfully; ack to dequeue".to_string_value()
+            object.data.result().unwrap().message.text,
+            "Command completed successfully; ack to dequeue"
         );
         assert_eq!(msg.count, 5);
         assert_eq!(msg.id, "12345".to_string());
         assert_eq!(
             *(msg.date.as_ref().unwrap()),
-            "2021-07-23T19:12:43.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2021, 7, 23, 19, 12, 43).unwrap()
         );
         assert_eq!(
             *(msg.message.as_ref().unwrap()),
@@ -715,16 +1095,16 @@ mod response {
         assert_eq!(result.message_data.requester_id, "eppdev".to_string_value());
         assert_eq!(
             result.message_data.requested_at,
-            "2021-07-23T15:31:21.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2021, 7, 23, 15, 31, 21).unwrap()
         );
         assert_eq!(result.message_data.ack_id, "ClientY".to_string_value());
         assert_eq!(
             result.message_data.ack_by,
-            "2021-07-28T15:31:21.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2021, 7, 28, 15, 31, 21).unwrap()
         );
         assert_eq!(
             result.message_data.expiring_at,
-            "2022-07-02T14:53:19.0Z".to_string_value()
+            Utc.with_ymd_and_hms(2022, 7, 2, 14, 53, 19).unwrap()
         );
         assert_eq!(
             object.data.tr_ids.client_tr_id.unwrap(),
@@ -740,8 +1120,8 @@ mod response {
 
         let msg = object.data.message_queue().unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(msg.count, 4);
         assert_eq!(msg.id, "12345".to_string());
         assert_eq!(object.data.tr_ids.server_tr_id, SVTRID.to_string_value());
@@ -752,11 +1132,131 @@ mod response {
         let xml = get_xml("response/domain/rgp_restore.xml").unwrap();
         let object = EppDomainRgpRestoreRequestResponse::deserialize(xml.as_str()).unwrap();
 
-        let ext = object.data.extension.unwrap();
+        let ext = object.data.extension.as_ref().unwrap();
 
-        assert_eq!(object.data.result.code, 1000);
-        assert_eq!(object.data.result.message, SUCCESS_MSG.to_string_value());
+        assert_eq!(object.data.result().unwrap().code, 1000);
+        assert_eq!(object.data.result().unwrap().message.text, SUCCESS_MSG);
         assert_eq!(ext.data.rgp_status.status, "pendingRestore".to_string());
         assert_eq!(object.data.tr_ids.server_tr_id, SVTRID.to_string_value());
     }
+
+    #[test]
+    fn unknown_status() {
+        let status: Status<DomainStatus> =
+            quick_xml::de::from_str(r#"<status s="serverRegistryLocked"/>"#).unwrap();
+
+        assert_eq!(
+            status.status,
+            DomainStatus::Other("serverRegistryLocked".to_string())
+        );
+        assert_eq!(status.status.to_string(), "serverRegistryLocked");
+        assert_eq!(status.reason, None);
+    }
+}
+
+mod request {
+    use super::super::get_xml;
+    use super::super::CLTRID;
+    use crate::epp::object::data::DomainStatus;
+    use crate::epp::object::StringValueTrait;
+    use crate::epp::xml::EppXml;
+    use crate::epp::*;
+
+    #[test]
+    fn domain_create() {
+        let xml = get_xml("request/domain/create_with_host_obj.xml").unwrap();
+        let object = EppDomainCreate::deserialize(xml.as_str()).unwrap();
+
+        let domain = object.data.command.domain;
+
+        assert_eq!(domain.name, "eppdev-1.com".to_string_value());
+        assert_eq!(domain.period.length, 1);
+        assert_eq!(domain.ns.unwrap().hosts.len(), 2);
+        assert_eq!(
+            domain.registrant.unwrap(),
+            "eppdev-contact-3".to_string_value()
+        );
+        assert_eq!(domain.contacts.unwrap()[0].contact_type, "admin");
+        assert_eq!(
+            domain.auth_info.password(),
+            Some(&"epP4uthd#v".to_string_value())
+        );
+        assert_eq!(object.data.client_tr_id, CLTRID.to_string_value());
+    }
+
+    #[test]
+    fn domain_update() {
+        let xml = get_xml("request/domain/update.xml").unwrap();
+        let object = EppDomainUpdate::deserialize(xml.as_str()).unwrap();
+
+        let domain = object.data.command.domain;
+
+        assert_eq!(domain.name, "eppdev.com".to_string_value());
+        assert_eq!(
+            domain.add.as_ref().unwrap().statuses.as_ref().unwrap()[0].status,
+            DomainStatus::ClientDeleteProhibited
+        );
+        assert_eq!(
+            domain.add.unwrap().statuses.unwrap()[1].reason,
+            Some("Payment overdue.".to_string())
+        );
+        assert_eq!(
+            domain.remove.unwrap().contacts.unwrap()[0].id,
+            "eppdev-contact-2"
+        );
+        assert_eq!(
+            domain.change_info.unwrap().auth_info.unwrap().password(),
+            Some(&"epP5uthd#v".to_string_value())
+        );
+    }
+
+    #[test]
+    fn domain_transfer_request() {
+        let xml = get_xml("request/domain/transfer_request.xml").unwrap();
+        let object = EppDomainTransferRequest::deserialize(xml.as_str()).unwrap();
+
+        let transfer = object.data.command;
+
+        assert_eq!(transfer.operation, "request");
+        assert_eq!(transfer.domain.name, "testing.com".to_string_value());
+        assert_eq!(transfer.domain.period.unwrap().length, 1);
+    }
+
+    #[test]
+    fn contact_create() {
+        let xml = get_xml("request/contact/create.xml").unwrap();
+        let object = EppContactCreate::deserialize(xml.as_str()).unwrap();
+
+        let contact = object.data.command.contact;
+
+        assert_eq!(contact.id, "eppdev-contact-3".to_string_value());
+        assert_eq!(contact.postal_info.len(), 2);
+        assert_eq!(
+            contact.postal_info[0].address.city,
+            "Paris".to_string_value()
+        );
+        assert_eq!(contact.postal_info[1].name, "Jean Dupré".to_string_value());
+        assert_eq!(contact.voice.number, "+33.47237942");
+        assert_eq!(contact.email, "contact@eppdev.net".to_string_value());
+    }
+
+    #[test]
+    fn host_create() {
+        let xml = get_xml("request/host/create.xml").unwrap();
+        let object = EppHostCreate::deserialize(xml.as_str()).unwrap();
+
+        let host = object.data.command.host;
+
+        assert_eq!(host.name, "host1.eppdev-1.com".to_string_value());
+        assert_eq!(host.addresses.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn message_ack() {
+        let xml = get_xml("request/message/ack.xml").unwrap();
+        let object = EppMessageAck::deserialize(xml.as_str()).unwrap();
+
+        assert_eq!(object.data.command.op, "ack");
+        assert_eq!(object.data.command.message_id, "12345");
+    }
 }
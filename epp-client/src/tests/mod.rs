@@ -1,7 +1,11 @@
 //! Module for automated tests
 
+pub mod client;
+pub mod codec;
 pub mod de;
 pub mod se;
+pub mod tls;
+pub mod transcript;
 
 use regex::Regex;
 use std::{error::Error, fs::File, io::Read};
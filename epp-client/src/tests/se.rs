@@ -4,10 +4,10 @@ mod request {
     use super::super::get_xml;
     use super::super::CLTRID;
     use crate::epp::object::data::{
-        Address, AuthInfo, ContactStatus, DomainContact, DomainStatus, HostAddr, HostAttr,
-        HostStatus, Phone, PostalInfo,
+        Address, AuthInfo, ContactStatus, Disclose, DomainContact, DomainStatus, HostAddr,
+        HostAttr, HostStatus, Phone, PostalInfo, Status,
     };
-    use crate::epp::object::StringValueTrait;
+    use crate::epp::object::{EmptyTag, StringValueTrait};
     use crate::epp::request::{EppHello, EppLogin, EppLogout};
     use crate::epp::xml::EppXml;
     use crate::epp::*;
@@ -36,6 +36,15 @@ mod request {
         assert_eq!(xml, serialized);
     }
 
+    #[test]
+    fn login_new_password() {
+        let mut object = EppLogin::new("username", "password", &None, CLTRID);
+        object.new_password("new password");
+        let serialized = object.serialize().unwrap();
+
+        assert!(serialized.contains("<pw>password</pw><newPW>new password</newPW><options>"));
+    }
+
     #[test]
     fn logout() {
         let xml = get_xml("request/logout.xml").unwrap();
@@ -59,8 +68,8 @@ mod request {
         let xml = get_xml("request/contact/create.xml").unwrap();
 
         let street = vec!["58", "Orchid Road"];
-        let address = Address::new(street, "Paris", "Paris", "392374", "FR");
-        let postal_info = PostalInfo::new("int", "John Doe", "Acme Widgets", address);
+        let address = Address::new(street, "Paris", Some("Paris"), Some("392374"), "FR");
+        let postal_info = PostalInfo::new("int", "John Doe", Some("Acme Widgets"), address);
         let mut voice = Phone::new("+33.47237942");
         voice.set_extension("123");
         let mut fax = Phone::new("+33.86698799");
@@ -76,11 +85,37 @@ mod request {
         );
         object.set_fax(fax);
 
+        let address = Address::new(vec!["58 rue des Orchidées"], "Paris", None, None, "FR");
+        object.add_postal_info(PostalInfo::new("loc", "Jean Dupré", None, address));
+
+        let mut disclose = Disclose::new(false);
+        disclose.voice = Some(EmptyTag);
+        disclose.email = Some(EmptyTag);
+        object.set_disclose(disclose);
+
         let serialized = object.serialize().unwrap();
 
         assert_eq!(xml, serialized);
     }
 
+    #[test]
+    fn contact_create_non_ascii_int_postal_info() {
+        let address = Address::new(vec!["58 rue des Orchidées"], "Paris", None, None, "FR");
+        let postal_info = PostalInfo::new("int", "Jean Dupré", None, address);
+        assert!(postal_info.validate().is_err());
+
+        let object = EppContactCreate::new(
+            "eppdev-contact-3",
+            "contact@eppdev.net",
+            postal_info,
+            Phone::new("+33.47237942"),
+            "eppdev-387323",
+            CLTRID,
+        );
+
+        assert!(object.serialize().is_err());
+    }
+
     #[test]
     fn contact_info() {
         let xml = get_xml("request/contact/info.xml").unwrap();
@@ -99,20 +134,20 @@ mod request {
         let mut object = EppContactUpdate::new("eppdev-contact-3", CLTRID);
 
         let street = vec!["58", "Orchid Road"];
-        let address = Address::new(street, "Paris", "Paris", "392374", "FR");
-        let postal_info = PostalInfo::new("loc", "John Doe", "Acme Widgets", address);
+        let address = Address::new(street, "Paris", Some("Paris"), Some("392374"), "FR");
+        let postal_info = PostalInfo::new("loc", "John Doe", Some("Acme Widgets"), address);
         let voice = Phone::new("+33.47237942");
 
         object.set_info("newemail@eppdev.net", postal_info, voice, "eppdev-387323");
-        let add_statuses = vec![ContactStatus {
-            status: "clientTransferProhibited".to_string(),
-        }];
+        let add_statuses = vec![Status::new(ContactStatus::ClientTransferProhibited)];
         object.add(add_statuses);
-        let remove_statuses = vec![ContactStatus {
-            status: "clientDeleteProhibited".to_string(),
-        }];
+        let remove_statuses = vec![Status::new(ContactStatus::ClientDeleteProhibited)];
         object.remove(remove_statuses);
 
+        let mut disclose = Disclose::new(true);
+        disclose.postal_info("int");
+        object.set_disclose(disclose);
+
         let serialized = object.serialize().unwrap();
 
         assert_eq!(xml, serialized);
@@ -129,6 +164,61 @@ mod request {
         assert_eq!(xml, serialized);
     }
 
+    #[test]
+    fn contact_transfer_request() {
+        let xml = get_xml("request/contact/transfer_request.xml").unwrap();
+
+        let object = EppContactTransferRequest::request("eppdev-contact-3", "epP4uthd#v", CLTRID);
+
+        let serialized = object.serialize().unwrap();
+
+        assert_eq!(xml, serialized);
+    }
+
+    #[test]
+    fn contact_transfer_approve() {
+        let xml = get_xml("request/contact/transfer_approve.xml").unwrap();
+
+        let object = EppContactTransferApprove::approve("eppdev-contact-3", CLTRID);
+
+        let serialized = object.serialize().unwrap();
+
+        assert_eq!(xml, serialized);
+    }
+
+    #[test]
+    fn contact_transfer_reject() {
+        let xml = get_xml("request/contact/transfer_reject.xml").unwrap();
+
+        let object = EppContactTransferReject::reject("eppdev-contact-3", CLTRID);
+
+        let serialized = object.serialize().unwrap();
+
+        assert_eq!(xml, serialized);
+    }
+
+    #[test]
+    fn contact_transfer_cancel() {
+        let xml = get_xml("request/contact/transfer_cancel.xml").unwrap();
+
+        let object = EppContactTransferCancel::cancel("eppdev-contact-3", CLTRID);
+
+        let serialized = object.serialize().unwrap();
+
+        assert_eq!(xml, serialized);
+    }
+
+    #[test]
+    fn contact_transfer_query() {
+        let xml = get_xml("request/contact/transfer_query.xml").unwrap();
+
+        let object = EppContactTransferQuery::query("eppdev-contact-3", "epP4uthd#v", CLTRID);
+
+        let serialized = object.serialize().unwrap();
+
+        assert_eq!(xml, serialized);
+    }
+
     #[test]
     fn domain_check() {
         let xml = get_xml("request/domain/check.xml").unwrap();
@@ -234,8 +324,8 @@ mod request {
             HostAttr {
                 name: "ns2.eppdev-1.com".to_string_value(),
                 addresses: Some(vec![
-                    HostAddr::new_v4("177.232.12.58"),
-                    HostAddr::new_v6("2404:6800:4001:801::200e"),
+                    HostAddr::new_v4("177.232.12.58".parse().unwrap()),
+                    HostAddr::new_v6("2404:6800:4001:801::200e".parse().unwrap()),
                 ]),
             },
         ];
@@ -266,18 +356,36 @@ mod request {
         assert_eq!(xml, serialized);
     }
 
+    #[test]
+    fn domain_info_auth_info() {
+        let xml = get_xml("request/domain/info_auth_info.xml").unwrap();
+
+        let mut object = EppDomainInfo::new("eppdev.com", CLTRID);
+        object.set_hosts(DomainInfoHosts::Del);
+        object.set_auth_info(AuthInfo::with_roid("2fooBAR", "SH8013-REP"));
+
+        let serialized = object.serialize().unwrap();
+
+        assert_eq!(xml, serialized);
+    }
+
     #[test]
     fn domain_update() {
         let xml = get_xml("request/domain/update.xml").unwrap();
 
         let mut object = EppDomainUpdate::new("eppdev.com", CLTRID);
 
+        let mut hold = Status::new(DomainStatus::ClientHold);
+        hold.set_lang("en");
+        hold.set_reason("Payment overdue.");
+
         let add = DomainAddRemove {
             ns: None,
             contacts: None,
-            statuses: Some(vec![DomainStatus {
-                status: "clientDeleteProhibited".to_string(),
-            }]),
+            statuses: Some(vec![
+                Status::new(DomainStatus::ClientDeleteProhibited),
+                hold,
+            ]),
         };
 
         let remove = DomainAddRemove {
@@ -392,13 +500,28 @@ mod request {
         assert_eq!(xml, serialized);
     }
 
+    #[test]
+    fn domain_update_null_auth_info() {
+        let xml = get_xml("request/domain/update_null_auth_info.xml").unwrap();
+
+        let mut object = EppDomainUpdate::new("eppdev.com", CLTRID);
+        object.info(DomainChangeInfo {
+            registrant: None,
+            auth_info: Some(AuthInfo::null()),
+        });
+
+        let serialized = object.serialize().unwrap();
+
+        assert_eq!(xml, serialized);
+    }
+
     #[test]
     fn host_create() {
         let xml = get_xml("request/host/create.xml").unwrap();
 
         let addresses = vec![
-            HostAddr::new("v4", "29.245.122.14"),
-            HostAddr::new("v6", "2404:6800:4001:801::200e"),
+            HostAddr::new_v4("29.245.122.14".parse().unwrap()),
+            HostAddr::new_v6("2404:6800:4001:801::200e".parse().unwrap()),
         ];
 
         let object = EppHostCreate::new("host1.eppdev-1.com", addresses, CLTRID);
@@ -423,7 +546,9 @@ mod request {
     fn host_update() {
         let xml = get_xml("request/host/update.xml").unwrap();
 
-        let addr = vec![HostAddr::new("v6", "2404:6800:4001:801::200e")];
+        let addr = vec![HostAddr::new_v6(
+            "2404:6800:4001:801::200e".parse().unwrap(),
+        )];
 
         let add = HostAddRemove {
             addresses: Some(addr),
@@ -432,9 +557,7 @@ mod request {
 
         let remove = HostAddRemove {
             addresses: None,
-            statuses: Some(vec![HostStatus {
-                status: "clientDeleteProhibited".to_string(),
-            }]),
+            statuses: Some(vec![Status::new(HostStatus::ClientDeleteProhibited)]),
         };
 
         let mut object = EppHostUpdate::new("host1.eppdev-1.com", CLTRID);
@@ -527,4 +650,52 @@ mod request {
 
         assert_eq!(xml, serialized);
     }
+
+    #[test]
+    fn idempotent_requests() {
+        assert!(EppHello::new().is_idempotent());
+        assert!(EppDomainCheck::new(vec!["eppdev.com"], CLTRID).is_idempotent());
+        assert!(EppContactInfo::new("eppdev-contact-3", "eppdev-387323", CLTRID).is_idempotent());
+        assert!(EppMessagePoll::new(CLTRID).is_idempotent());
+        assert!(EppDomainTransferQuery::query("eppdev.com", "epP4uthd#v", CLTRID).is_idempotent());
+        assert!(
+            EppContactTransferQuery::query("eppdev-contact-3", "eppdev-387323", CLTRID)
+                .is_idempotent()
+        );
+
+        assert!(!EppLogout::new(CLTRID).is_idempotent());
+        assert!(!EppDomainDelete::new("eppdev.com", CLTRID).is_idempotent());
+        assert!(!EppMessageAck::new(12345, CLTRID).is_idempotent());
+        assert!(
+            !EppDomainTransferRequest::request("eppdev.com", 1, "epP4uthd#v", CLTRID)
+                .is_idempotent()
+        );
+        assert!(!EppContactTransferApprove::approve("eppdev-contact-3", CLTRID).is_idempotent());
+    }
+}
+
+mod response {
+    use super::super::get_xml;
+    use crate::epp::object::StringValueTrait;
+    use crate::epp::xml::EppXml;
+    use crate::epp::*;
+
+    #[test]
+    fn domain_check() {
+        let xml = get_xml("response/domain/check.xml").unwrap();
+        let object = EppDomainCheckResponse::deserialize(xml.as_str()).unwrap();
+
+        let serialized = object.serialize().unwrap();
+        assert!(serialized.contains("<response><result code=\"1000\">"));
+        assert!(serialized.contains("<name avail=\"1\">eppdev.com</name>"));
+
+        let object = EppDomainCheckResponse::deserialize(serialized.as_str()).unwrap();
+        let result = object.data.res_data().unwrap();
+
+        assert_eq!(
+            result.check_data.domain_list[0].domain.name,
+            "eppdev.com".to_string_value()
+        );
+        assert_eq!(result.check_data.domain_list[0].domain.available, 1);
+    }
 }
@@ -0,0 +1,220 @@
+//! TLS certificate, private key and server verification tests
+
+use rustls::{ClientConfig, NoClientAuth, ServerConfig};
+use std::fs;
+use std::sync::Arc;
+use tokio::io::duplex;
+use tokio_rustls::TlsAcceptor;
+
+use super::RESOURCES_DIR;
+use crate::config::EppClientConnection;
+use crate::connection::registry::tls_connect;
+use crate::connection::tls::{certs_from_pem, decode_spki_pin, private_key_from_pem, spki_sha256};
+use crate::error::Error;
+use crate::EppClient;
+
+fn read(file: &str) -> Vec<u8> {
+    fs::read(format!("{}/tls/{}", RESOURCES_DIR, file)).unwrap()
+}
+
+fn path(file: &str) -> String {
+    format!("{}/tls/{}", RESOURCES_DIR, file)
+}
+
+/// Checks that rustls accepts the key for client auth
+fn assert_usable_key(file: &str) {
+    let certs = certs_from_pem(&read("cert.pem")).unwrap();
+    let key = private_key_from_pem(&read(file)).unwrap();
+
+    let mut config = ClientConfig::new();
+    config.set_single_client_cert(certs, key).unwrap();
+}
+
+#[test]
+fn rsa_key() {
+    assert_usable_key("rsa.pem");
+}
+
+#[test]
+fn pkcs8_key() {
+    assert_usable_key("pkcs8.pem");
+}
+
+#[test]
+fn sec1_ec_key() {
+    assert_usable_key("ec.pem");
+    assert_usable_key("ec_p384.pem");
+}
+
+#[test]
+fn unsupported_curve() {
+    let result = private_key_from_pem(&read("ec_unsupported.pem"));
+
+    assert!(matches!(result, Err(Error::EppTlsError(_))));
+}
+
+#[test]
+fn no_key() {
+    let result = private_key_from_pem(&read("cert.pem"));
+
+    match result {
+        Err(Error::EppTlsError(e)) => assert_eq!(e, "No RSA, PKCS#8 or EC private key found"),
+        _ => panic!("expected EppTlsError"),
+    }
+}
+
+#[test]
+fn no_certs() {
+    let result = certs_from_pem(&read("rsa.pem"));
+
+    assert!(matches!(result, Err(Error::EppTlsError(_))));
+}
+
+#[test]
+fn tls_files() {
+    let mut registry_creds = EppClientConnection::new("localhost", 700, "username", "password");
+    registry_creds.set_tls_files(&path("cert.pem"), &path("ec.pem"));
+
+    let (certs, _) = registry_creds.tls_files().unwrap().unwrap();
+    assert_eq!(certs.len(), 1);
+
+    registry_creds.set_tls_files(&path("cert.pem"), &path("missing.pem"));
+    assert!(matches!(
+        registry_creds.tls_files(),
+        Err(Error::EppTlsError(_))
+    ));
+}
+
+#[test]
+fn tls_pem() {
+    let mut registry_creds = EppClientConnection::new("localhost", 700, "username", "password");
+    registry_creds
+        .set_tls_pem(&read("cert.pem"), &read("pkcs8.pem"))
+        .unwrap();
+
+    assert!(registry_creds.tls_files().unwrap().is_some());
+}
+
+const SERVER_PIN: &str = "3UAXsrWGG8jSaEIDAKKNO2oW0bEB99Zx1HwV58De5z4=";
+
+/// Connection details trusting only the test CA, connecting by IP address
+fn test_ca_creds() -> EppClientConnection {
+    let mut registry_creds = EppClientConnection::new("127.0.0.1", 700, "username", "password");
+    registry_creds.set_bundled_roots(false);
+    registry_creds.set_ca_file(&path("ca.pem"));
+    registry_creds.set_server_name("epp.test");
+    registry_creds
+}
+
+/// Runs the TLS handshake against a server presenting the test server certificate
+async fn handshake(registry_creds: &EppClientConnection) -> Result<(), Error> {
+    let (client, server) = duplex(4096);
+
+    let mut config = ServerConfig::new(NoClientAuth::new());
+    config
+        .set_single_cert(
+            certs_from_pem(&read("server.pem")).unwrap(),
+            private_key_from_pem(&read("server_key.pem")).unwrap(),
+        )
+        .unwrap();
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+
+    let server = tokio::spawn(async move { acceptor.accept(server).await.map(|_| ()) });
+    // The client stream is kept open until the server has finished its side of the handshake
+    let stream = tls_connect(registry_creds, client).await?;
+    server.await.unwrap().unwrap();
+    drop(stream);
+
+    Ok(())
+}
+
+#[test]
+fn spki_pin() {
+    let cert = certs_from_pem(&read("server.pem")).unwrap().remove(0);
+
+    assert_eq!(
+        spki_sha256(&cert).unwrap(),
+        decode_spki_pin(SERVER_PIN).unwrap()
+    );
+    assert!(matches!(
+        decode_spki_pin("c2hvcnQ="),
+        Err(Error::EppTlsError(_))
+    ));
+}
+
+#[tokio::test]
+async fn private_ca() {
+    handshake(&test_ca_creds()).await.unwrap();
+}
+
+#[tokio::test]
+async fn builder_root_certs() {
+    let (_, registry_creds) = EppClient::builder()
+        .host("127.0.0.1", 700)
+        .credentials("username", "password")
+        .root_certs(certs_from_pem(&read("ca.pem")).unwrap())
+        .bundled_roots(false)
+        .server_name("epp.test")
+        .build()
+        .unwrap();
+    handshake(&registry_creds).await.unwrap();
+
+    let (_, registry_creds) = EppClient::builder()
+        .host("127.0.0.1", 700)
+        .credentials("username", "password")
+        .root_certs_pem(&read("ca.pem"))
+        .bundled_roots(false)
+        .server_name("epp.test")
+        .build()
+        .unwrap();
+    handshake(&registry_creds).await.unwrap();
+}
+
+#[tokio::test]
+async fn untrusted_ca() {
+    let mut registry_creds = test_ca_creds();
+    registry_creds.set_bundled_roots(true);
+    registry_creds.set_ca_file(&path("cert.pem"));
+
+    assert!(matches!(
+        handshake(&registry_creds).await,
+        Err(Error::EppTlsError(_))
+    ));
+}
+
+#[tokio::test]
+async fn no_root_certs() {
+    let mut registry_creds = EppClientConnection::new("epp.test", 700, "username", "password");
+    registry_creds.set_bundled_roots(false);
+
+    match handshake(&registry_creds).await {
+        Err(Error::EppTlsError(e)) => assert_eq!(e, "No root certificates to trust"),
+        _ => panic!("expected EppTlsError"),
+    }
+}
+
+#[tokio::test]
+async fn server_name_required_for_ip() {
+    let mut registry_creds = test_ca_creds();
+    registry_creds.set_server_name("127.0.0.1");
+
+    assert!(matches!(
+        handshake(&registry_creds).await,
+        Err(Error::EppTlsError(_))
+    ));
+}
+
+#[tokio::test]
+async fn pinned_key() {
+    let mut registry_creds = test_ca_creds();
+    registry_creds.set_spki_pins(vec![SERVER_PIN.to_string()]);
+    handshake(&registry_creds).await.unwrap();
+
+    registry_creds.set_spki_pins(vec![
+        "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=".to_string()
+    ]);
+    assert!(matches!(
+        handshake(&registry_creds).await,
+        Err(Error::EppTlsError(_))
+    ));
+}
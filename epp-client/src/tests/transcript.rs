@@ -0,0 +1,160 @@
+//! Tests for recording and replaying transcripts of EPP sessions
+
+use super::client::{connection, fake_registry, registry_creds};
+use super::{get_xml, CLTRID};
+use crate::connection::registry::ConnectionStream;
+use crate::connection::transcript::{
+    redact, ReplayTransport, Transcript, TranscriptRecorder, REDACTED,
+};
+use crate::epp::*;
+use crate::error;
+use crate::EppClient;
+
+/// Records a session with a domain check for eppdev.com
+async fn record_session() -> Transcript {
+    let (stream, server) = connection();
+    let registry = fake_registry(
+        server,
+        vec![
+            "response/login.xml",
+            "response/domain/check.xml",
+            "response/logout.xml",
+        ],
+    );
+
+    let recorder = TranscriptRecorder::new();
+    let mut registry_creds = registry_creds();
+    registry_creds.set_recorder(recorder.clone());
+
+    let mut client = EppClient::connect_with_stream("test", &registry_creds, stream)
+        .await
+        .unwrap();
+
+    let domain_check = EppDomainCheck::new(vec!["eppdev.com"], CLTRID);
+    client
+        .transact::<_, EppDomainCheckResponse>(&domain_check)
+        .await
+        .unwrap();
+
+    client.close().await.unwrap();
+    registry.await.unwrap();
+
+    recorder.transcript()
+}
+
+#[test]
+fn redact_passwords() {
+    let xml = get_xml("request/login.xml").unwrap();
+    let redacted = redact(&xml);
+    assert!(!redacted.contains("<pw>password</pw>"));
+    assert!(redacted.contains(&format!("<pw>{}</pw>", REDACTED)));
+    assert!(redacted.contains("<clID>username</clID>"));
+
+    let xml = get_xml("request/domain/create.xml").unwrap();
+    let redacted = redact(&xml);
+    assert!(!redacted.contains("epP4uthd#v"));
+    assert!(redacted.contains(&format!("<pw>{}</pw>", REDACTED)));
+
+    let redacted = redact("<domain:authInfo><domain:pw>2fooBAR</domain:pw></domain:authInfo>");
+    assert_eq!(
+        redacted,
+        format!(
+            "<domain:authInfo><domain:pw>{}</domain:pw></domain:authInfo>",
+            REDACTED
+        )
+    );
+}
+
+#[test]
+fn redact_passwords_in_markup() {
+    let redacted = redact("<pw><![CDATA[se</pw>cret]]></pw><clID>username</clID>");
+    assert_eq!(
+        redacted,
+        format!("<pw>{}</pw><clID>username</clID>", REDACTED)
+    );
+
+    let redacted = redact("<!-- a > b --><pw>secret</pw>");
+    assert_eq!(redacted, format!("<!-- a > b --><pw>{}</pw>", REDACTED));
+
+    let redacted = redact("<pw><!-- </pw> > -->secret</pw>");
+    assert_eq!(redacted, format!("<pw>{}</pw>", REDACTED));
+
+    let redacted = redact("<login><pw>secret");
+    assert_eq!(redacted, format!("<login><pw>{}", REDACTED));
+
+    let redacted = redact("<login><clID a=\"b>username</clID><pw>secret</pw></login>");
+    assert!(!redacted.contains("secret"));
+}
+
+#[test]
+fn transcript_text_format() {
+    let mut transcript = Transcript::new(&get_xml("response/greeting.xml").unwrap());
+    transcript.push(
+        &get_xml("request/login.xml").unwrap(),
+        &get_xml("response/login.xml").unwrap(),
+    );
+
+    let text = transcript.to_string();
+    assert!(text.starts_with(&format!("S: {}\n", transcript.greeting.len())));
+    assert_eq!(Transcript::parse(&text).unwrap(), transcript);
+
+    let truncated = &text[..text.len() - 10];
+    assert!(matches!(
+        Transcript::parse(truncated),
+        Err(error::Error::EppTranscriptError(_))
+    ));
+}
+
+#[tokio::test]
+async fn record_and_replay() {
+    let transcript = record_session().await;
+    assert_eq!(transcript.exchanges.len(), 3);
+    assert!(transcript.exchanges[0]
+        .request
+        .contains(&format!("<pw>{}</pw>", REDACTED)));
+
+    let replay = ReplayTransport::new(transcript);
+    let progress = replay.progress();
+
+    let mut client =
+        EppClient::connect_with_stream("test", &registry_creds(), ConnectionStream::new(replay))
+            .await
+            .unwrap();
+    assert!(client.greeting().is_ok());
+
+    // The client transaction id may differ from the recorded one
+    let domain_check = EppDomainCheck::new(vec!["eppdev.com"], "cltrid:replay");
+    let response = client
+        .transact::<_, EppDomainCheckResponse>(&domain_check)
+        .await
+        .unwrap();
+    assert_eq!(response.data.result().unwrap().code, 1000);
+
+    client.close().await.unwrap();
+    assert!(progress.is_finished());
+}
+
+#[tokio::test]
+async fn replay_different_request() {
+    let replay = ReplayTransport::new(record_session().await);
+    let progress = replay.progress();
+
+    let mut client =
+        EppClient::connect_with_stream("test", &registry_creds(), ConnectionStream::new(replay))
+            .await
+            .unwrap();
+
+    let domain_check = EppDomainCheck::new(vec!["eppdev.net"], CLTRID);
+    match client
+        .transact::<_, EppDomainCheckResponse>(&domain_check)
+        .await
+    {
+        Err(error::Error::EppConnectionError(e)) => {
+            assert!(e
+                .to_string()
+                .contains("Request 2 differs from the transcript"))
+        }
+        result => panic!("Unexpected result: {:?}", result),
+    }
+    assert_eq!(progress.remaining(), 2);
+}